@@ -0,0 +1,58 @@
+#!/usr/bin/env python3
+"""Wall-clock benchmark for `symgraph-cli scan-cxx --jobs N`.
+
+Generates a synthetic C++ project (500 translation units by default), then
+times a sequential scan against a parallel one.
+
+    python benches/bench_scan_cxx.py [--files 500] [--jobs 8] [--cli target/release/symgraph-cli]
+"""
+import argparse, json, os, shutil, subprocess, tempfile, time
+
+
+def generate_project(root, files):
+    src = os.path.join(root, 'src')
+    os.makedirs(src)
+    commands = []
+    for i in range(files):
+        path = os.path.join(src, f'unit_{i}.cpp')
+        with open(path, 'w', encoding='utf-8') as f:
+            f.write(f'class Base{i} {{ public: virtual int value() const {{ return {i}; }} }};\n')
+            f.write(f'class Derived{i} : public Base{i} {{ public: int value() const override {{ return helper{i}(); }}\n')
+            f.write(f'  int helper{i}() const {{ return {i} * 2; }} }};\n')
+            f.write(f'int run{i}() {{ Derived{i} d; return d.value(); }}\n')
+        commands.append({'directory': root, 'file': path, 'arguments': ['clang++', '-std=c++17', '-c', path]})
+    compdb = os.path.join(root, 'compile_commands.json')
+    with open(compdb, 'w', encoding='utf-8') as f:
+        json.dump(commands, f, indent=2)
+    return compdb
+
+
+def timed_scan(cli, compdb, db, jobs):
+    shutil.rmtree(db, ignore_errors=True)
+    start = time.perf_counter()
+    subprocess.run([cli, 'scan-cxx', '--compdb', compdb, '--db', db, '--jobs', str(jobs)],
+                   check=True, stdout=subprocess.DEVNULL)
+    return time.perf_counter() - start
+
+
+def main():
+    parser = argparse.ArgumentParser()
+    parser.add_argument('--files', type=int, default=500)
+    parser.add_argument('--jobs', type=int, default=os.cpu_count() or 1)
+    parser.add_argument('--cli', default=os.path.join('target', 'release', 'symgraph-cli'))
+    args = parser.parse_args()
+
+    with tempfile.TemporaryDirectory() as root:
+        compdb = generate_project(root, args.files)
+        db = os.path.join(root, 'bench.db')
+        sequential = timed_scan(args.cli, compdb, db, 1)
+        parallel = timed_scan(args.cli, compdb, db, args.jobs)
+
+    print(f'{args.files} files')
+    print(f'--jobs 1:  {sequential:.2f}s')
+    print(f'--jobs {args.jobs}: {parallel:.2f}s')
+    print(f'speedup:   {sequential / parallel:.2f}x')
+
+
+if __name__ == '__main__':
+    main()
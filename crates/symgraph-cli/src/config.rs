@@ -0,0 +1,179 @@
+//! Project configuration loaded from `.symgraph.toml`.
+//!
+//! The file is searched for from the current directory up to the filesystem
+//! root, the same way `git` finds its repository. Values are resolved in the
+//! order: CLI flag, config file, compiled-in default.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file
+pub const CONFIG_FILE_NAME: &str = ".symgraph.toml";
+
+const DEFAULT_DB_PATH: &str = "symgraph.db";
+const DEFAULT_COMPDB_PATH: &str = "compile_commands.json";
+const DEFAULT_ROOT: &str = ".";
+
+/// Skeleton written by `symgraph-cli init`
+const CONFIG_TEMPLATE: &str = r#"# symgraph project configuration
+# Paths are relative to the directory containing this file.
+# Command-line flags take precedence over the values below.
+
+db_path = "symgraph.db"
+compdb_path = "build/compile_commands.json"
+root = "."
+# build_dir = "build"
+# generator = "Ninja"
+# parallelism = 4
+"#;
+
+/// Settings read from `.symgraph.toml`
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub db_path: Option<String>,
+    pub compdb_path: Option<String>,
+    pub root: Option<String>,
+    pub build_dir: Option<String>,
+    pub generator: Option<String>,
+    pub parallelism: Option<usize>,
+}
+
+impl Config {
+    /// Load the nearest `.symgraph.toml` above the current directory, or an empty config.
+    pub fn load() -> Result<Self> {
+        let cwd = std::env::current_dir()?;
+        match find_config_file(&cwd) {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Parse a config file, resolving relative paths against its directory.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path.display(), e))?;
+        let mut config: Config = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse '{}': {}", path.display(), e))?;
+
+        if let Some(base) = path.parent() {
+            for field in [&mut config.db_path, &mut config.compdb_path, &mut config.root, &mut config.build_dir] {
+                if let Some(value) = field.as_mut() {
+                    *value = resolve(base, value);
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Database path from the CLI flag, the config file, or the default
+    pub fn db_path(&self, cli: Option<String>) -> String {
+        cli.or_else(|| self.db_path.clone())
+            .unwrap_or_else(|| DEFAULT_DB_PATH.to_string())
+    }
+
+    /// compile_commands.json path from the CLI flag, the config file, or the default
+    pub fn compdb_path(&self, cli: Option<String>) -> String {
+        cli.or_else(|| self.compdb_path.clone())
+            .unwrap_or_else(|| DEFAULT_COMPDB_PATH.to_string())
+    }
+
+    /// Project root from the CLI flag, the config file, or the default
+    pub fn root(&self, cli: Option<String>) -> String {
+        cli.or_else(|| self.root.clone())
+            .unwrap_or_else(|| DEFAULT_ROOT.to_string())
+    }
+
+    /// Parallel job count from the CLI flag, the config file, or the number of logical CPUs
+    pub fn jobs(&self, cli: Option<usize>) -> usize {
+        cli.or(self.parallelism)
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    /// Build directory from the CLI flag or the config file
+    pub fn build_dir(&self, cli: Option<String>) -> Option<String> {
+        cli.or_else(|| self.build_dir.clone())
+    }
+
+    /// CMake generator from the CLI flag or the config file
+    pub fn generator(&self, cli: Option<String>) -> Option<String> {
+        cli.or_else(|| self.generator.clone())
+    }
+}
+
+/// Walk up from `start` looking for `.symgraph.toml`
+pub fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Write a skeleton `.symgraph.toml` into `dir`.
+pub fn init_config(dir: &Path, force: bool) -> Result<PathBuf> {
+    let path = dir.join(CONFIG_FILE_NAME);
+    if path.exists() && !force {
+        return Err(anyhow::anyhow!("{} already exists (use --force to overwrite)", path.display()));
+    }
+    std::fs::write(&path, CONFIG_TEMPLATE)
+        .map_err(|e| anyhow::anyhow!("Failed to write '{}': {}", path.display(), e))?;
+    Ok(path)
+}
+
+fn resolve(base: &Path, value: &str) -> String {
+    let path = Path::new(value);
+    if path.is_absolute() {
+        value.to_string()
+    } else {
+        base.join(path).to_string_lossy().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_found_in_parent_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(CONFIG_FILE_NAME),
+            "db_path = \"project.db\"\ngenerator = \"Ninja\"\nparallelism = 8\n",
+        )
+        .unwrap();
+        let nested = temp_dir.path().join("src").join("core");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let path = find_config_file(&nested).unwrap();
+        let config = Config::from_file(&path).unwrap();
+
+        let expected_db = temp_dir.path().join("project.db");
+        assert_eq!(config.db_path(None), expected_db.to_string_lossy());
+        assert_eq!(config.generator(None).as_deref(), Some("Ninja"));
+        assert_eq!(config.parallelism, Some(8));
+    }
+
+    #[test]
+    fn test_cli_flags_override_config() {
+        let config = Config {
+            db_path: Some("from_config.db".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.db_path(Some("from_cli.db".to_string())), "from_cli.db");
+        assert_eq!(config.db_path(None), "from_config.db");
+        assert_eq!(Config::default().db_path(None), DEFAULT_DB_PATH);
+        assert_eq!(Config::default().compdb_path(None), DEFAULT_COMPDB_PATH);
+    }
+
+    #[test]
+    fn test_init_writes_parsable_skeleton() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = init_config(temp_dir.path(), false).unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert!(config.db_path.unwrap().ends_with("symgraph.db"));
+        assert!(init_config(temp_dir.path(), false).is_err());
+        assert!(init_config(temp_dir.path(), true).is_ok());
+    }
+}
@@ -85,19 +85,24 @@
 //! Edge kinds: "call", "inherit", "member", "module-import"
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use std::path::Path;
 
 // Import modules
+mod config;
 mod modules;
+mod progress;
 
+use config::Config;
 use modules::commands::{Args, Command};
-use modules::cxx_analyzer::{scan_cxx, import_modules, scan_modules};
+use modules::cxx_analyzer::{scan_cxx, scan_cxx_worker, watch_cxx, import_modules, scan_modules};
 use modules::rust_analyzer::{scan_rust, generate_lsif_file};
+use modules::server::serve;
 use modules::utils::*;
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let config = Config::load()?;
     match args.cmd {
         Command::GenerateCompdb {
             project,
@@ -107,28 +112,84 @@ fn main() -> Result<()> {
             generator,
             configuration,
             platform,
+            vcpkg_root,
+            read_cache,
+            dry_run,
         } => {
             generate_compdb(
-                &project,
+                &config.root(project),
                 output.as_deref(),
-                build_dir.as_deref(),
+                config.build_dir(build_dir).as_deref(),
                 build_system,
-                generator.as_deref(),
+                config.generator(generator).as_deref(),
                 configuration.as_deref(),
                 platform.as_deref(),
+                vcpkg_root.as_deref(),
+                read_cache,
+                dry_run,
             )?;
         }
         
-        Command::ScanCxx { compdb, db } => {
-            scan_cxx(&compdb, &db)?;
+        Command::ValidateCompdb { compdb, rewrite_paths } => {
+            validate_compdb(&config.compdb_path(compdb), rewrite_paths.as_deref())?;
+        }
+        
+        Command::ScanCxx {
+            compdb,
+            db,
+            jobs,
+            filter_file_pattern,
+            exclude_file_pattern,
+            skip_headers,
+            hash_based,
+            rewrite_paths,
+            include_anonymous,
+            quiet,
+        } => {
+            scan_cxx(
+                &config.compdb_path(compdb),
+                &config.db_path(db),
+                config.jobs(jobs),
+                &filter_file_pattern,
+                &exclude_file_pattern,
+                skip_headers,
+                hash_based,
+                rewrite_paths.as_deref(),
+                include_anonymous,
+                quiet,
+            )?;
+        }
+        
+        Command::ScanCxxWorker {
+            compdb,
+            shard,
+            shards,
+            filter_file_pattern,
+            exclude_file_pattern,
+            skip_headers,
+            include_anonymous,
+        } => {
+            scan_cxx_worker(
+                &compdb,
+                shard,
+                shards,
+                &filter_file_pattern,
+                &exclude_file_pattern,
+                skip_headers,
+                include_anonymous,
+            )?;
+        }
+        
+        Command::Watch { compdb, db, debounce_ms } => {
+            watch_cxx(&config.compdb_path(compdb), &config.db_path(db), debounce_ms)?;
         }
         
         Command::ImportModules { root, db } => {
-            import_modules(&root, &db)?;
+            import_modules(&config.root(root), &config.db_path(db))?;
         }
         
-        Command::ScanModules { root, db } => {
-            scan_modules(&root, &db)?;
+        Command::ScanModules { root, db, quiet } => {
+            scan_modules(&config.root(root), &config.db_path(db), quiet)?;
         }
         
         Command::GenerateLsif { project, output } => {
@@ -139,40 +200,227 @@ fn main() -> Result<()> {
             manifest,
             lsif,
             db,
+            include_tests,
+            skip_crates,
+        } => {
+            scan_rust(&manifest, lsif.as_deref(), &config.db_path(db), include_tests, &skip_crates)?;
+        }
+        
+        Command::ImportLsif {
+            db,
+            lsif,
+            project_name,
+            skip_missing_usrs,
         } => {
-            scan_rust(&manifest, lsif.as_deref(), &db)?;
+            import_lsif(&config.db_path(db), &lsif, &project_name, skip_missing_usrs)?;
+        }
+        
+        Command::ImportCargoLock { db, lock } => {
+            import_cargo_lock(&config.db_path(db), &lock)?;
         }
         
         Command::QueryCalls { db, usr } => {
-            query_calls(&db, &usr)?;
+            query_calls(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryConceptUses { db, usr } => {
+            query_concept_uses(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryCoroutines { db } => {
+            query_coroutines(&config.db_path(db))?;
+        }
+        
+        Command::TagSymbol { db, usr, tag, remove } => {
+            tag_symbol(&config.db_path(db), &usr, &tag, remove)?;
+        }
+        
+        Command::AddAlias { db, usr, alias } => {
+            add_alias(&config.db_path(db), &usr, &alias)?;
+        }
+        
+        Command::SymbolDoc { db, usr } => {
+            symbol_doc(&config.db_path(db), &usr)?;
+        }
+        
+        Command::FindTagged { db, tag } => {
+            find_tagged(&config.db_path(db), &tag)?;
+        }
+        
+        Command::QueryNodiscard { db } => {
+            query_nodiscard(&config.db_path(db))?;
+        }
+        
+        Command::UndefinedSymbols { db, lang } => {
+            undefined_symbols(&config.db_path(db), lang.as_deref())?;
+        }
+        
+        Command::Search { db, query, limit } => {
+            search(&config.db_path(db), &query, limit)?;
+        }
+        
+        Command::QueryMembers { db, usr, kind } => {
+            query_members(&config.db_path(db), &usr, kind)?;
+        }
+        
+        Command::QueryAsync { db } => {
+            query_async(&config.db_path(db))?;
+        }
+        
+        Command::UnsafeReport { db } => {
+            unsafe_report(&config.db_path(db))?;
+        }
+        
+        Command::QueryInstantiations { db, usr } => {
+            query_instantiations(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryNamespace { db, usr } => {
+            query_namespace(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryConstructors { db, usr } => {
+            query_constructors(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryPartitions { db, module } => {
+            query_partitions(&config.db_path(db), &module)?;
+        }
+        
+        Command::SymbolsInFile { db, file, feature_flags } => {
+            symbols_in_file(&config.db_path(db), &file, &feature_flags)?;
+        }
+        
+        Command::Usages { db, usr, kind } => {
+            usages(&config.db_path(db), &usr, kind.as_deref())?;
+        }
+        
+        Command::QueryCallers { db, usr } => {
+            query_callers(&config.db_path(db), &usr)?;
+        }
+        
+        Command::QueryInheritance { db, usr, direction } => {
+            query_inheritance(&config.db_path(db), &usr, direction.into())?;
+        }
+        
+        Command::Tree { db, root_usr, depth } => {
+            symbol_tree(&config.db_path(db), &root_usr, depth)?;
+        }
+        
+        Command::CouplingMetrics { db, sort_by, top } => {
+            coupling_metrics(&config.db_path(db), sort_by, top)?;
+        }
+        
+        Command::Pagerank { db, kind, top } => {
+            pagerank(&config.db_path(db), &kind, top)?;
+        }
+        
+        Command::HottestPaths { db, start, kind, top } => {
+            hottest_paths(&config.db_path(db), &start, &kind, top)?;
+        }
+        
+        Command::QueryMacros { db, file } => {
+            query_macros(&config.db_path(db), &file)?;
         }
         
         Command::ListModules { db } => {
-            list_modules(&db)?;
+            list_modules(&config.db_path(db))?;
+        }
+        
+        Command::TopoSort { db } => {
+            topo_sort(&config.db_path(db))?;
+        }
+        
+        Command::Diff { before, after, kind } => {
+            diff_databases(&before, &after, kind.as_deref())?;
+        }
+        
+        Command::ListFiles { db, lang, category, format, count_only } => {
+            list_files(&config.db_path(db), lang.as_deref(), category.as_deref(), format, count_only)?;
+        }
+        
+        Command::ShowStats { db, format } => {
+            show_stats(&config.db_path(db), format)?;
         }
         
-        Command::ShowStats { db } => {
-            show_stats(&db)?;
+        Command::Maintain { db, vacuum } => {
+            maintain(&config.db_path(db), vacuum)?;
         }
         
-        Command::AnnotateCompiled { root, db } => {
-            annotate_compiled_project(&root, &db)?;
+        Command::RemapPaths { db, from, to } => {
+            remap_paths(&config.db_path(db), &from, &to)?;
+        }
+        
+        Command::AnnotateCompiled { root, db, from_db } => {
+            annotate_compiled_project(&config.root(root), &config.db_path(db), from_db)?;
         }
         
         Command::ScanScripts { root, db } => {
-            scan_scripts(&root, &db)?;
+            scan_scripts(&config.root(root), &config.db_path(db))?;
+        }
+        
+        Command::DetectLanguages { project } => {
+            detect_languages(&project)?;
         }
         
         Command::ScanScip { root, db } => {
-            scan_scip(&root, &db)?;
+            scan_scip(&config.root(root), &config.db_path(db))?;
+        }
+        
+        Command::ImportScipTypescript { root, db, tsconfig, dry_run } => {
+            import_scip_typescript(&config.root(root), &config.db_path(db), tsconfig.as_deref(), dry_run)?;
+        }
+        
+        Command::ExportMermaid { db, kind, root, depth, output } => {
+            export_mermaid(&config.db_path(db), &kind, root.as_deref(), depth, output.as_deref())?;
+        }
+        
+        Command::ExportPlantuml { db, output } => {
+            export_plantuml(&config.db_path(db), output.as_deref())?;
+        }
+        
+        Command::ExportSequence { db, start, depth, output } => {
+            export_sequence(&config.db_path(db), &start, depth, output.as_deref())?;
+        }
+        
+        Command::ExportJson { db, output } => {
+            export_json(&config.db_path(db), output.as_deref())?;
+        }
+        
+        Command::ExportCsv { db, table, output, filter_lang, filter_kind, bom } => {
+            export_csv(
+                &config.db_path(db),
+                table,
+                &output,
+                filter_lang.as_deref(),
+                filter_kind.as_deref(),
+                bom,
+            )?;
+        }
+        
+        Command::ExportCscope { db, output_dir } => {
+            export_cscope(&config.db_path(db), &output_dir)?;
         }
         
         Command::WebViewer { db } => {
-            start_web_viewer(&db)?;
+            start_web_viewer(&config.db_path(db))?;
+        }
+        
+        Command::Serve { db, port, host } => {
+            serve(&config.db_path(db), &host, port)?;
+        }
+        
+        Command::Init { force } => {
+            let path = config::init_config(&std::env::current_dir()?, force)?;
+            println!("Wrote {}", path.display());
+        }
+        
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Args::command(), "symgraph-cli", &mut std::io::stdout());
         }
         
         Command::Api { endpoint, db, search } => {
-            handle_api_request(&endpoint, &db, search.as_deref())?;
+            handle_api_request(&endpoint, &config.db_path(db), search.as_deref())?;
         }
     }
 
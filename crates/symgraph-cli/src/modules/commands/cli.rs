@@ -7,12 +7,106 @@ pub enum BuildSystemType {
     Auto,
     /// CMake проект (CMakeLists.txt)
     CMake,
+    /// xmake проект (xmake.lua)
+    #[value(name = "xmake")]
+    XMake,
     /// Makefile проект
     Make,
+    /// GNU Autotools проект (configure.ac)
+    Autotools,
     /// Visual Studio solution (.sln)
     Solution,
     /// Cargo проект (Rust)
     Cargo,
+    /// Общие флаги из compile_flags.txt (простой режим clangd)
+    #[value(name = "compile-flags")]
+    CompileFlags,
+}
+
+/// Output format of `list-files`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListFilesFormatArg {
+    /// Aligned path, language and category columns
+    Table,
+    /// Pretty-printed JSON array
+    Json,
+    /// Paths terminated by NUL bytes, for `xargs -0`
+    NullSeparated,
+}
+
+/// Direction of an inheritance hierarchy query
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum InheritDirectionArg {
+    /// Base classes
+    Ancestors,
+    /// Derived classes
+    Descendants,
+}
+
+impl From<InheritDirectionArg> for symgraph_core::InheritDirection {
+    fn from(arg: InheritDirectionArg) -> Self {
+        match arg {
+            InheritDirectionArg::Ancestors => symgraph_core::InheritDirection::Ancestors,
+            InheritDirectionArg::Descendants => symgraph_core::InheritDirection::Descendants,
+        }
+    }
+}
+
+/// Table written by `export-csv`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CsvTableArg {
+    Symbols,
+    Files,
+    Edges,
+    Occurrences,
+    Modules,
+}
+
+impl From<CsvTableArg> for symgraph_core::CsvTable {
+    fn from(arg: CsvTableArg) -> Self {
+        match arg {
+            CsvTableArg::Symbols => symgraph_core::CsvTable::Symbols,
+            CsvTableArg::Files => symgraph_core::CsvTable::Files,
+            CsvTableArg::Edges => symgraph_core::CsvTable::Edges,
+            CsvTableArg::Occurrences => symgraph_core::CsvTable::Occurrences,
+            CsvTableArg::Modules => symgraph_core::CsvTable::Modules,
+        }
+    }
+}
+
+/// Column to rank symbols by in coupling metrics
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum MetricSortArg {
+    /// Number of callers
+    #[value(name = "fan_in")]
+    FanIn,
+    /// Number of callees
+    #[value(name = "fan_out")]
+    FanOut,
+}
+
+/// Output format of database statistics
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatsFormatArg {
+    /// Human-readable summary
+    Table,
+    /// Pretty-printed JSON object
+    Json,
+    /// `name,count` lines without a header
+    Csv,
+}
+
+/// Which class members `query-members` lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MembersKindArg {
+    /// Fields and methods
+    Members,
+    /// Methods only
+    Methods,
+    /// Fields only
+    Fields,
+    /// Fields, methods and base classes
+    All,
 }
 
 #[derive(Subcommand)]
@@ -24,7 +118,7 @@ pub enum Command {
     GenerateCompdb {
         /// Project root directory
         #[arg(short, long)]
-        project: String,
+        project: Option<String>,
 
         /// Output file path (default: compile_commands.json in project root)
         #[arg(short, long)]
@@ -49,39 +143,145 @@ pub enum Command {
         /// Visual Studio platform (x64/Win32)
         #[arg(short, long)]
         platform: Option<String>,
+
+        /// vcpkg root for projects with vcpkg.json (default: $VCPKG_ROOT)
+        #[arg(long)]
+        vcpkg_root: Option<String>,
+
+        /// Add compile flags and include directories from CMakeCache.txt (CMake only)
+        #[arg(long)]
+        read_cache: bool,
+
+        /// Print the detected build system and the commands that would run, without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Validate compile_commands.json and report malformed entries.
+    ValidateCompdb {
+        /// Path to compile_commands.json
+        #[arg(short, long)]
+        compdb: Option<String>,
+
+        /// Replace a path prefix before validating, as FROM:TO (e.g. /workspace:/home/user/project)
+        #[arg(long = "rewrite-paths")]
+        rewrite_paths: Option<String>,
     },
 
     /// Scan C/C++ source code using compile_commands.json.
     ScanCxx {
         /// Path to compile_commands.json
         #[arg(short, long)]
+        compdb: Option<String>,
+
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Number of parallel parse jobs (default: number of logical CPUs)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Only scan files matching this glob (repeatable; any pattern may match)
+        #[arg(long = "filter-file-pattern")]
+        filter_file_pattern: Vec<String>,
+
+        /// Skip files matching this glob (repeatable; applied after --filter-file-pattern)
+        #[arg(long = "exclude-file-pattern")]
+        exclude_file_pattern: Vec<String>,
+
+        /// Do not parse header entries of compile_commands.json
+        #[arg(long)]
+        skip_headers: bool,
+
+        /// Only re-scan files whose contents changed since the last scan
+        #[arg(long)]
+        hash_based: bool,
+
+        /// Replace a path prefix in compile_commands.json, as FROM:TO (e.g. /workspace:/home/user/project)
+        #[arg(long = "rewrite-paths")]
+        rewrite_paths: Option<String>,
+
+        /// Also record anonymous structs and unions, with synthetic `anon:` USRs
+        #[arg(long)]
+        include_anonymous: bool,
+
+        /// Do not show the progress bar
+        #[arg(short, long)]
+        quiet: bool,
+    },
+
+    /// Parse one shard of compile_commands.json (internal use by scan-cxx).
+    #[command(hide = true)]
+    ScanCxxWorker {
+        /// Path to compile_commands.json
+        #[arg(long)]
         compdb: String,
 
+        /// Index of the shard to parse
+        #[arg(long)]
+        shard: usize,
+
+        /// Total number of shards
+        #[arg(long)]
+        shards: usize,
+
+        /// Only scan files matching this glob
+        #[arg(long = "filter-file-pattern")]
+        filter_file_pattern: Vec<String>,
+
+        /// Skip files matching this glob
+        #[arg(long = "exclude-file-pattern")]
+        exclude_file_pattern: Vec<String>,
+
+        /// Do not parse header entries
+        #[arg(long)]
+        skip_headers: bool,
+
+        /// Also record anonymous structs and unions
+        #[arg(long)]
+        include_anonymous: bool,
+    },
+
+    /// Re-scan C/C++ sources from compile_commands.json whenever they change.
+    Watch {
+        /// Path to compile_commands.json
+        #[arg(short, long)]
+        compdb: Option<String>,
+
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Batch changes that arrive within this many milliseconds
+        #[arg(long, default_value_t = 300)]
+        debounce_ms: u64,
     },
 
     /// Import C++20 module dependencies.
     ImportModules {
         /// Root directory containing module files
         #[arg(short, long)]
-        root: String,
+        root: Option<String>,
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
     },
 
     /// Scan C++20 modules directly from source.
     ScanModules {
         /// Root directory containing module files
         #[arg(short, long)]
-        root: String,
+        root: Option<String>,
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Do not show the progress bar
+        #[arg(short, long)]
+        quiet: bool,
     },
 
     /// Generate LSIF index from Rust project.
@@ -96,6 +296,10 @@ pub enum Command {
     },
 
     /// Scan Rust project using LSIF or cargo metadata.
+    ///
+    /// Each workspace crate is indexed with `rust-analyzer scip` and a summary of
+    /// files and symbols per crate is printed. Symbols are stored under their SCIP
+    /// symbol as USR, e.g. `rust-analyzer cargo mycrate 0.1.0 parser/Parser#parse().`
     ScanRust {
         /// Path to Cargo.toml
         #[arg(short, long)]
@@ -107,72 +311,657 @@ pub enum Command {
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Also index the tests/ and benches/ directories of each crate
+        #[arg(long)]
+        include_tests: bool,
+
+        /// Workspace crate to leave out (repeatable)
+        #[arg(long = "skip-crate", value_name = "NAME")]
+        skip_crates: Vec<String>,
+    },
+
+    /// Import an LSIF (JSON Lines) dump into the database.
+    ImportLsif {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// LSIF file path
+        #[arg(short, long)]
+        lsif: String,
+
+        /// Project name to record the imported files under
+        #[arg(short, long)]
+        project_name: String,
+
+        /// Skip references whose USR is not found in the database instead of failing
+        #[arg(long)]
+        skip_missing_usrs: bool,
+    },
+
+    /// Import the crate dependency graph from a Cargo.lock.
+    ImportCargoLock {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Cargo.lock file path
+        #[arg(short, long)]
+        lock: String,
     },
 
     /// Query call graph for a symbol.
     QueryCalls {
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
 
         /// USR of the symbol
         #[arg(short, long)]
         usr: String,
     },
 
+    /// List the places where a C++20 concept is used.
+    QueryConceptUses {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the concept
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// List C++20 coroutine functions (bodies using co_await, co_yield or co_return).
+    QueryCoroutines {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Attach a label such as `deprecated` to a symbol, or remove it.
+    TagSymbol {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the symbol to label
+        #[arg(short, long)]
+        usr: String,
+
+        /// Label to attach
+        #[arg(short, long)]
+        tag: String,
+
+        /// Remove the label instead of attaching it
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Record a former name of a symbol so that searches still find it.
+    AddAlias {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the renamed symbol
+        #[arg(short, long)]
+        usr: String,
+
+        /// Former name of the symbol
+        #[arg(short, long)]
+        alias: String,
+    },
+
+    /// Print the documentation comment of a symbol.
+    SymbolDoc {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the symbol
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// List the symbols carrying a label.
+    FindTagged {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Label to look for
+        #[arg(short, long)]
+        tag: String,
+    },
+
+    /// List calls that ignore the result of a `[[nodiscard]]` function.
+    QueryNodiscard {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// List symbols that are declared but never defined.
+    UndefinedSymbols {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Only report declarations in files of this language (e.g. c++)
+        #[arg(short, long)]
+        lang: Option<String>,
+    },
+
+    /// Full-text search on symbol names, best matches first.
+    Search {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Words to look for; each must start a word of the name
+        #[arg(short, long)]
+        query: String,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// List the fields, methods and base classes of a C++ class.
+    QueryMembers {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the class
+        #[arg(short, long)]
+        usr: String,
+
+        /// Which members to list
+        #[arg(short, long, value_enum, default_value = "all")]
+        kind: MembersKindArg,
+    },
+
+    /// List Rust async functions (`async fn` or returning `impl Future`).
+    QueryAsync {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// List Rust files by their number of `unsafe` blocks.
+    UnsafeReport {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// List the known instantiations of a C++ template.
+    QueryInstantiations {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the primary template
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// List the functions and classes declared in a C++ namespace.
+    QueryNamespace {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the namespace or namespace alias
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// List the constructors of a C++ class.
+    QueryConstructors {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the class
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// List the partitions of a C++20 module.
+    QueryPartitions {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Primary module name
+        #[arg(short, long)]
+        module: String,
+    },
+
+    /// List symbols defined in a file.
+    SymbolsInFile {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// File path as recorded in the database
+        #[arg(short, long)]
+        file: String,
+
+        /// Only list symbols gated behind one of these Cargo features (comma-separated)
+        #[arg(long = "feature-flags", value_delimiter = ',')]
+        feature_flags: Vec<String>,
+    },
+
+    /// List where a symbol is used, one `file:line:col usage_kind` per line.
+    Usages {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the symbol
+        #[arg(short, long)]
+        usr: String,
+
+        /// Only list usages of this kind (e.g. call, reference, type_ref)
+        #[arg(short, long)]
+        kind: Option<String>,
+    },
+
+    /// Query callers of a function.
+    QueryCallers {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the symbol
+        #[arg(short, long)]
+        usr: String,
+    },
+
+    /// Show the inheritance hierarchy of a class.
+    QueryInheritance {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the class
+        #[arg(short, long)]
+        usr: String,
+
+        /// Walk towards base classes or derived classes
+        #[arg(long, value_enum, default_value = "descendants")]
+        direction: InheritDirectionArg,
+    },
+
+    /// Print the symbols nested in a namespace or class as an indented tree.
+    Tree {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the root namespace or class
+        #[arg(short, long)]
+        root_usr: String,
+
+        /// Number of nesting levels to show below the root
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+    },
+
+    /// Show fan-in and fan-out of symbols in the call graph.
+    CouplingMetrics {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Metric to sort by, highest first
+        #[arg(long, value_enum, default_value = "fan_in")]
+        sort_by: MetricSortArg,
+
+        /// Number of symbols to show
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Rank symbols by PageRank importance in the symbol graph.
+    Pagerank {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Edge kind to follow
+        #[arg(long, default_value = "call")]
+        kind: String,
+
+        /// Number of symbols to show
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Show the paths with the highest total edge weight from a symbol.
+    HottestPaths {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the symbol the paths start at
+        #[arg(short, long)]
+        start: String,
+
+        /// Edge kind to follow
+        #[arg(long, default_value = "call")]
+        kind: String,
+
+        /// Number of paths to show
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+    },
+
+    /// List macros defined in a file.
+    QueryMacros {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Source file path
+        #[arg(short, long)]
+        file: String,
+    },
+
     /// List all modules in the database.
     ListModules {
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+    },
+
+    /// Print modules in build order based on module imports.
+    TopoSort {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Compare two databases and report added/removed symbols and edges.
+    Diff {
+        /// Database before the change
+        #[arg(short, long)]
+        before: String,
+
+        /// Database after the change
+        #[arg(short, long)]
+        after: String,
+
+        /// Only compare symbols or edges of this kind (e.g. FunctionDecl, call)
+        #[arg(short, long)]
+        kind: Option<String>,
+    },
+
+    /// List the files stored in the database.
+    ListFiles {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Only list files in this language (e.g. c++, rust)
+        #[arg(short, long)]
+        lang: Option<String>,
+
+        /// Only list files in this category (e.g. header, unittest)
+        #[arg(short, long)]
+        category: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: ListFilesFormatArg,
+
+        /// Print only the number of matching files
+        #[arg(long)]
+        count_only: bool,
     },
 
     /// Show database statistics.
     ShowStats {
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: StatsFormatArg,
+    },
+
+    /// Compact the database files.
+    Maintain {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Rewrite the database so it only holds live entries
+        #[arg(long)]
+        vacuum: bool,
+    },
+
+    /// Rewrite stored paths after the project moved to another directory.
+    RemapPaths {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Old path prefix, e.g. /home/alice/project
+        #[arg(long)]
+        from: String,
+
+        /// New path prefix, e.g. /home/bob/project
+        #[arg(long)]
+        to: String,
     },
 
     /// Generate project annotation.
     AnnotateCompiled {
         /// Project root directory
         #[arg(short, long)]
-        root: String,
+        root: Option<String>,
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Infer the annotation from the stored symbol graph instead of the source tree
+        #[arg(long)]
+        from_db: bool,
     },
 
     /// Analyze script projects using SCIP.
     ScanScripts {
         /// Project root directory
         #[arg(short, long)]
-        root: String,
+        root: Option<String>,
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+    },
+
+    /// Count source files per language in a project.
+    DetectLanguages {
+        /// Project root directory
+        #[arg(short, long)]
+        project: String,
     },
 
     /// Generate SCIP index from project.
     ScanScip {
         /// Project root directory
         #[arg(short, long)]
-        root: String,
+        root: Option<String>,
+
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Index a TypeScript project with scip-typescript and load the result.
+    ImportScipTypescript {
+        /// Project root directory
+        #[arg(short, long)]
+        root: Option<String>,
+
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// tsconfig.json to index instead of the one in the project root
+        #[arg(long)]
+        tsconfig: Option<String>,
+
+        /// Generate and parse the index, print its size, but leave the database untouched
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Export a graph of one edge kind as a Mermaid flowchart.
+    ExportMermaid {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Edge kind to export (call, inherit, member, ...)
+        #[arg(short, long, default_value = "call")]
+        kind: String,
+
+        /// USR of the root symbol (exports the whole graph if omitted)
+        #[arg(short, long)]
+        root: Option<String>,
+
+        /// Maximum number of hops from the root symbol
+        #[arg(long, default_value_t = 3)]
+        depth: u8,
+
+        /// Output file path (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export classes, members and inheritance as a PlantUML class diagram.
+    ExportPlantuml {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Output file path (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export the call chain below a function as a PlantUML sequence diagram.
+    ExportSequence {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// USR of the function the sequence starts from
+        #[arg(short, long)]
+        start: String,
+
+        /// Maximum call depth below the start function
+        #[arg(long, default_value_t = 3)]
+        depth: u8,
+
+        /// Output file path (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export the whole database as a JSON snapshot.
+    ExportJson {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Output file path (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export one table as CSV for spreadsheet tools.
+    ExportCsv {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Table to export
+        #[arg(short, long, value_enum)]
+        table: CsvTableArg,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: String,
 
+        /// Only export files in this language (files table)
+        #[arg(long)]
+        filter_lang: Option<String>,
+
+        /// Only export edges of this kind (edges table)
+        #[arg(long)]
+        filter_kind: Option<String>,
+
+        /// Prepend a UTF-8 byte order mark, for Excel on Windows
+        #[arg(long)]
+        bom: bool,
+    },
+
+    /// Export a cscope cross-reference database (cscope.out).
+    ExportCscope {
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+
+        /// Directory to write cscope.out into
+        #[arg(short, long, default_value = ".")]
+        output_dir: String,
     },
 
     /// Start web viewer for database.
     WebViewer {
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
+    },
+
+    /// Serve the database as a JSON HTTP API.
+    Serve {
+        /// Database file path
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to bind to
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+
+    /// Write a skeleton .symgraph.toml to the current directory.
+    Init {
+        /// Overwrite an existing .symgraph.toml
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Generate shell completion script and print it to stdout.
+    Completions {
+        /// Target shell
+        #[arg(short, long, value_enum)]
+        shell: clap_complete::Shell,
     },
 
     /// API endpoint for web viewer (internal use).
@@ -182,7 +971,7 @@ pub enum Command {
 
         /// Database file path
         #[arg(short, long)]
-        db: String,
+        db: Option<String>,
 
         /// Search query (optional)
         #[arg(short, long)]
@@ -1,38 +1,433 @@
 use anyhow::Result;
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 use clang::{Clang, Index};
-use symgraph_core::{Db, insert_edge, insert_occurrence, insert_symbol, upsert_module};
-use symgraph_cxx::{categorize_cpp_file, infer_cpp_purpose, scan_tu};
-use symgraph_discovery::load_compile_commands;
+use notify::{EventKind, RecursiveMode, Watcher};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use symgraph_core::{Db, EdgeKindRegistry, Symbol, WriteBatch, insert_edge, insert_symbol, upsert_module};
+use symgraph_cxx::{
+    categorize_cpp_file, categorize_cpp_file_from_content, infer_cpp_purpose_from_content, scan_anonymous_records,
+    scan_tu, FileCategory,
+};
+use symgraph_discovery::{
+    load_compile_commands, normalize_to_arguments, parse_path_rewrite, rewrite_paths, stream_compile_commands,
+    CompileCommand, Debouncer, FileFilter,
+};
+
+use crate::progress::ProgressReporter;
+
+/// Symbols, occurrences and edges extracted from one translation unit
+#[derive(Serialize, Deserialize)]
+pub struct TuResult {
+    pub file: String,
+    pub category: String,
+    pub purpose: String,
+    /// Parsed as a header-only translation unit
+    #[serde(default)]
+    pub is_header: bool,
+    pub symbols: Vec<symgraph_cxx::Symbol>,
+    pub occurrences: Vec<symgraph_cxx::Occurrence>,
+    pub edges: Vec<(String, String, String)>,
+}
 
 /// Scan C/C++ source code using compile_commands.json.
-pub fn scan_cxx(compdb: &str, db_path: &str) -> Result<()> {
-    
+///
+/// libclang bindings allow only one `Clang` instance per process, so with
+/// `jobs > 1` the compile commands are split into shards that are parsed by
+/// worker processes driven from a Rayon pool. Results are merged into the
+/// database on the calling thread.
+///
+/// A translation unit is scanned if its file matches any `include` pattern
+/// (or no include patterns are given) and none of the `exclude` patterns.
+/// Header pseudo-translation-units are parsed as `c++-header`, or left out
+/// entirely with `skip_headers`. With `hash_based`, files whose contents match
+/// the hash stored at the previous scan are skipped and changed files replace
+/// their old symbols. `rewrite` is a `FROM:TO` path prefix substitution applied
+/// to the compile commands before anything else, for databases generated in a
+/// container. Anonymous structs and unions are only recorded with
+/// `include_anonymous`.
+#[allow(clippy::too_many_arguments)]
+pub fn scan_cxx(
+    compdb: &str,
+    db_path: &str,
+    jobs: usize,
+    include: &[String],
+    exclude: &[String],
+    skip_headers: bool,
+    hash_based: bool,
+    rewrite: Option<&str>,
+    include_anonymous: bool,
+    quiet: bool,
+) -> Result<()> {
+    let filter = FileFilter::new(include, exclude)?;
+    let path_rewrite = rewrite.map(parse_path_rewrite).transpose()?;
+    // Stream the compilation database so filtered-out entries are dropped as
+    // they are read; the kept commands, and later their results, stay in memory
+    let mut compile_commands = Vec::new();
+    let (mut skipped_files, mut skipped_headers) = (0, 0);
+    stream_compile_commands(compdb, |mut cc| {
+        if let Some((from, to)) = &path_rewrite {
+            rewrite_paths(std::slice::from_mut(&mut cc), from, to);
+        }
+        if !filter.matches(&cc.file) {
+            skipped_files += 1;
+        } else if is_skipped_header(&cc, skip_headers) {
+            skipped_headers += 1;
+        } else {
+            compile_commands.push(cc);
+        }
+        Ok(())
+    })?;
+    if skipped_files > 0 {
+        println!("Skipped {} files not matching the file patterns", skipped_files);
+    }
+    if skipped_headers > 0 {
+        println!("Skipped {} header files", skipped_headers);
+    }
+
+    let db = Db::open(db_path)?;
+    let compile_commands = if hash_based {
+        let (changed, unchanged) = changed_only(&db, compile_commands)?;
+        if unchanged > 0 {
+            println!("Skipped {} unchanged files", unchanged);
+        }
+        changed
+    } else {
+        compile_commands
+    };
+    // Workers read their shard from a compilation database, so hand them only
+    // the changed entries, with rewritten paths
+    let mut changed_compdb = None;
+    if hash_based || rewrite.is_some() {
+        let file = tempfile::NamedTempFile::new()?;
+        serde_json::to_writer(&file, &compile_commands)?;
+        changed_compdb = Some(file);
+    }
+    let compdb = match &changed_compdb {
+        Some(file) => file
+            .path()
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Temporary compilation database path is not UTF-8: {}", file.path().display()))?,
+        None => compdb,
+    };
+    let jobs = jobs.clamp(1, compile_commands.len().max(1));
+
+    let progress = ProgressReporter::new(compile_commands.len(), quiet);
+    let results = if jobs == 1 {
+        parse_compile_commands(&compile_commands, include_anonymous, &progress)?
+    } else {
+        progress.println(format!("Parsing {} translation units with {} jobs", compile_commands.len(), jobs));
+        parse_in_workers(compdb, jobs, include, exclude, skip_headers, include_anonymous, &progress)?
+    };
+
+    let file_count = results.len();
+    let mut symbol_count = 0;
+    let mut relation_count = 0;
+
+    // Collect every write and apply them at once, so a failure partway
+    // through leaves the database as it was before the scan
+    let mut batch = db.write_batch();
+    if hash_based {
+        remove_rescanned_symbols(&mut batch, &results)?;
+    }
+    for result in results {
+        let file = result.file.clone();
+        let (symbols, relations) = merge_tu_result(&mut batch, result)?;
+        symbol_count += symbols;
+        relation_count += relations;
+        if hash_based {
+            batch.ensure_file_with_category("1", &file, "c++", None, None)?;
+            batch.update_file_hash(&file)?;
+        }
+    }
+    batch.apply()?;
+    db.db.flush()?;
+    progress.finish(compile_commands.len());
+
+    println!("\n=== Summary ===");
+    println!("Files processed: {}", file_count);
+    println!("Symbols extracted: {}", symbol_count);
+    println!("Relations found: {}", relation_count);
+
+    Ok(())
+}
+
+/// Parse one shard of compile_commands.json and print each result as a JSON line.
+///
+/// Entry point of the worker processes spawned by [`scan_cxx`].
+pub fn scan_cxx_worker(
+    compdb: &str,
+    shard: usize,
+    shards: usize,
+    include: &[String],
+    exclude: &[String],
+    skip_headers: bool,
+    include_anonymous: bool,
+) -> Result<()> {
+    let filter = FileFilter::new(include, exclude)?;
+    // Keep only this worker's shard of the entries that survive filtering
+    let mut compile_commands = Vec::new();
+    let mut kept = 0;
+    stream_compile_commands(compdb, |cc| {
+        if filter.matches(&cc.file) && !is_skipped_header(&cc, skip_headers) {
+            if kept % shards == shard {
+                compile_commands.push(cc);
+            }
+            kept += 1;
+        }
+        Ok(())
+    })?;
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for result in parse_compile_commands(&compile_commands, include_anonymous, &ProgressReporter::hidden())? {
+        serde_json::to_writer(&mut out, &result)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Watch the sources listed in compile_commands.json and re-scan them on change.
+///
+/// The directories of all translation units are watched; saves that arrive
+/// within `debounce_ms` of each other are re-analyzed as one batch. Header
+/// changes are not tracked, only files that have a compile command. Runs until
+/// SIGINT/Ctrl-C or SIGTERM.
+pub fn watch_cxx(compdb: &str, db_path: &str, debounce_ms: u64) -> Result<()> {
+    let compile_commands = load_compile_commands(compdb)?;
+    let by_path: HashMap<PathBuf, usize> = compile_commands
+        .iter()
+        .enumerate()
+        .map(|(i, cc)| (resolve_path(&Path::new(&cc.directory).join(&cc.file)), i))
+        .collect();
+    let directories: BTreeSet<PathBuf> = by_path
+        .keys()
+        .filter_map(|path| path.parent().map(Path::to_path_buf))
+        .collect();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for dir in &directories {
+        if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            eprintln!("Warning: Cannot watch {}: {}", dir.display(), e);
+        }
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&stop))?;
+    signal_hook::flag::register(signal_hook::consts::SIGTERM, Arc::clone(&stop))?;
+
     let clang = Clang::new().map_err(|e| anyhow::anyhow!(e))?;
     let index = Index::new(&clang, false, false);
-
     let mut db = Db::open(db_path)?;
-    let compile_commands = load_compile_commands(compdb)?;
+    let mut debouncer = Debouncer::new(Duration::from_millis(debounce_ms));
+
+    println!("Watching {} directories for changes (Ctrl-C to stop)", directories.len());
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        let path = resolve_path(&path);
+                        if by_path.contains_key(&path) {
+                            debouncer.push(path, Instant::now());
+                        }
+                    }
+                }
+            }
+            Ok(Err(e)) => eprintln!("Warning: Watch error: {}", e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        if let Some(changed) = debouncer.take_ready(Instant::now()) {
+            let selected: Vec<CompileCommand> = changed
+                .iter()
+                .map(|path| compile_commands[by_path[path]].clone())
+                .collect();
+            // A failed batch must not end a long-running watch
+            let (symbols, relations) = match scan_cxx_files(&index, &mut db, &selected) {
+                Ok(counts) => counts,
+                Err(e) => {
+                    let files: Vec<&str> = selected.iter().map(|cc| cc.file.as_str()).collect();
+                    eprintln!("Warning: Failed to re-analyze {}: {:#}", files.join(", "), e);
+                    continue;
+                }
+            };
+            if let Err(e) = db.db.flush() {
+                eprintln!("Warning: Failed to flush database: {}", e);
+            }
+            for cc in &selected {
+                println!("Re-analyzed {}", cc.file);
+            }
+            match db.get_stats() {
+                Ok(stats) => println!(
+                    "Symbols extracted: {}, relations found: {}, symbols in database: {}",
+                    symbols, relations, stats.symbols
+                ),
+                Err(e) => eprintln!("Warning: Failed to read database stats: {}", e),
+            }
+        }
+    }
+
+    println!("Stopped watching");
+    Ok(())
+}
+
+/// Canonical form of a path so watcher events match compile command entries
+fn resolve_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether a header pseudo-translation-unit is dropped because `skip` is set
+fn is_skipped_header(cc: &CompileCommand, skip: bool) -> bool {
+    skip && matches!(categorize_cpp_file(&cc.file), FileCategory::Header)
+}
+
+/// Keep the compile commands whose file contents changed since the last scan; returns them and the number dropped
+fn changed_only(db: &Db, compile_commands: Vec<CompileCommand>) -> Result<(Vec<CompileCommand>, usize)> {
+    let total = compile_commands.len();
+    let mut changed = Vec::new();
+    for cc in compile_commands {
+        if db.file_has_changed(&cc.file)? {
+            changed.push(cc);
+        }
+    }
+    let unchanged = total - changed.len();
+    Ok((changed, unchanged))
+}
+
+/// Spawn one worker process per shard and collect their results as they stream in
+fn parse_in_workers(
+    compdb: &str,
+    jobs: usize,
+    include: &[String],
+    exclude: &[String],
+    skip_headers: bool,
+    include_anonymous: bool,
+    progress: &ProgressReporter,
+) -> Result<Vec<TuResult>> {
+    let exe = std::env::current_exe()?;
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+    let shards: Vec<Result<Vec<TuResult>>> = pool.install(|| {
+        (0..jobs)
+            .into_par_iter()
+            .map(|shard| {
+                let mut command = std::process::Command::new(&exe);
+                command
+                    .args(["scan-cxx-worker", "--compdb", compdb])
+                    .args(["--shard", &shard.to_string(), "--shards", &jobs.to_string()]);
+                for pattern in include {
+                    command.args(["--filter-file-pattern", pattern]);
+                }
+                for pattern in exclude {
+                    command.args(["--exclude-file-pattern", pattern]);
+                }
+                if skip_headers {
+                    command.arg("--skip-headers");
+                }
+                if include_anonymous {
+                    command.arg("--include-anonymous");
+                }
+                let mut child = command
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::inherit())
+                    .spawn()?;
+
+                let mut results = Vec::new();
+                let stdout = child.stdout.take().expect("worker stdout is piped");
+                for line in std::io::BufReader::new(stdout).lines() {
+                    let line = line?;
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let result: TuResult = serde_json::from_str(&line)?;
+                    progress.advance(&result.file);
+                    results.push(result);
+                }
+
+                let status = child.wait()?;
+                if !status.success() {
+                    return Err(anyhow::anyhow!("Worker {} failed with {}", shard, status));
+                }
+                Ok(results)
+            })
+            .collect()
+    });
+
+    let mut results = Vec::new();
+    for shard in shards {
+        results.extend(shard?);
+    }
+    Ok(results)
+}
+
+/// Parse translation units with a single libclang instance
+fn parse_compile_commands(
+    compile_commands: &[CompileCommand],
+    include_anonymous: bool,
+    progress: &ProgressReporter,
+) -> Result<Vec<TuResult>> {
+    let clang = Clang::new().map_err(|e| anyhow::anyhow!(e))?;
+    let index = Index::new(&clang, false, false);
+    Ok(parse_with_index(&index, compile_commands, include_anonymous, progress))
+}
+
+/// Re-scan the given translation units with an existing libclang index.
+///
+/// Symbols previously stored for each re-scanned file and the headers it
+/// includes are removed first, so repeated scans do not accumulate duplicates. Returns (symbols, relations) counts.
+pub fn scan_cxx_files(index: &Index, db: &mut Db, compile_commands: &[CompileCommand]) -> Result<(usize, usize)> {
+    let results = parse_with_index(index, compile_commands, false, &ProgressReporter::hidden());
+    let mut batch = db.write_batch();
+    remove_rescanned_symbols(&mut batch, &results)?;
 
-    let mut file_count = 0;
     let mut symbol_count = 0;
     let mut relation_count = 0;
+    for result in results {
+        let (symbols, relations) = merge_tu_result(&mut batch, result)?;
+        symbol_count += symbols;
+        relation_count += relations;
+    }
+    batch.apply()?;
+    Ok((symbol_count, relation_count))
+}
 
+/// Parse translation units, skipping files that are missing or fail to parse
+fn parse_with_index(
+    index: &Index,
+    compile_commands: &[CompileCommand],
+    include_anonymous: bool,
+    progress: &ProgressReporter,
+) -> Vec<TuResult> {
+    let mut results = Vec::new();
     for cc in compile_commands {
+        progress.advance(&cc.file);
         // Skip if file doesn't exist
         if !Path::new(&cc.file).exists() {
             eprintln!("Warning: File not found: {}", cc.file);
             continue;
         }
 
-        // Categorize file
-        let category = categorize_cpp_file(&cc.file);
-        let purpose = infer_cpp_purpose(&cc.file, &category);
+        // Categorize file; test framework includes mark tests outside test directories
+        let content = std::fs::read_to_string(&cc.file).unwrap_or_default();
+        let category = categorize_cpp_file_from_content(&cc.file, &content);
+        let purpose = infer_cpp_purpose_from_content(&cc.file, &content, &category);
         let category_str = format!("{:?}", category).to_lowercase();
-        
+        let is_header = matches!(categorize_cpp_file(&cc.file), FileCategory::Header);
+        let args = if is_header { header_args(clean_args(cc)) } else { clean_args(cc) };
+
         // Create TranslationUnit from compile command
         let tu = match index.parser(&cc.file)
-            .arguments(&cc.arguments.as_deref().unwrap_or(&[]))
+            .arguments(&args)
+            .detailed_preprocessing_record(true)
             .parse()
         {
             Ok(tu) => tu,
@@ -43,82 +438,187 @@ pub fn scan_cxx(compdb: &str, db_path: &str) -> Result<()> {
         };
         
         // Scan the translation unit for symbols
-        let (symbols, occs, edges) = scan_tu(&tu);
-        
-        file_count += 1;
-        
-        // Process symbols
-        for s in symbols {
-            let fid = db.ensure_file_with_category(
-                &"1", &s.file, "c++", Some(&category_str), Some(&purpose)
-            )?;
-            let _sid = insert_symbol(
-                &mut db,
-                &fid,
-                s.usr.as_deref(),
-                None,
-                &s.name,
-                &s.kind,
-                s.is_definition,
-            )?;
-            symbol_count += 1;
+        let (mut symbols, occurrences, mut edges) = scan_tu(&tu);
+        if include_anonymous {
+            let (anonymous, members) = scan_anonymous_records(&tu);
+            symbols.extend(anonymous);
+            edges.extend(members);
         }
+        results.push(TuResult {
+            file: cc.file.clone(),
+            category: category_str,
+            purpose,
+            is_header,
+            symbols,
+            occurrences,
+            edges,
+        });
+    }
 
-        // Process occurrences
-        for o in occs {
-            let fid = db.ensure_file_with_category(
-                &"1", &o.file, "c++", Some(&category_str), Some(&purpose)
-            )?;
-            
-            // Find symbol by USR first
-            if let Some(usr) = &o.usr {
-                if let Some(sym_id) = db.find_symbol_by_usr(usr)? {
-                    let _oid = insert_occurrence(
-                        &mut db,
-                        &sym_id,
-                        &fid,
-                        &o.usage_kind,
-                        o.line,
-                        o.column,
-                    )?;
-                    relation_count += 1;
-                }
+    results
+}
+
+/// Compiler arguments of a compile command as libclang expects them.
+///
+/// Uses `arguments`, or splits `command` with shell quoting rules. The compiler
+/// executable is dropped and `@file` response files (as written by MSVC and
+/// some CMake generators) are expanded in place, relative to the entry's
+/// `directory`.
+fn clean_args(cc: &CompileCommand) -> Vec<String> {
+    let args = normalize_to_arguments(cc).unwrap_or_else(|e| {
+        eprintln!("Warning: {}", e);
+        Vec::new()
+    });
+
+    let mut cleaned = Vec::new();
+    let mut args = args.into_iter();
+    // The compiler itself is not an argument to libclang
+    if let Some(first) = args.next() {
+        if first.starts_with('-') || first.starts_with('@') {
+            expand_arg(first, Path::new(&cc.directory), &mut cleaned, 0);
+        }
+    }
+    for arg in args {
+        expand_arg(arg, Path::new(&cc.directory), &mut cleaned, 0);
+    }
+    cleaned
+}
+
+/// Arguments for a header pseudo-translation-unit: without output and
+/// compile-only flags, parsed as a C++ header
+fn header_args(args: Vec<String>) -> Vec<String> {
+    let mut cleaned = vec!["-x".to_string(), "c++-header".to_string()];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => {
+                args.next();
             }
+            "-c" => {}
+            a if a.starts_with("-o") => {}
+            "-x" => {
+                args.next();
+            }
+            _ => cleaned.push(arg),
         }
+    }
+    cleaned
+}
 
-        // Process edges
-        for (kind, from, to) in &edges {
-            if let (Some(from_id), Some(to_id)) = (
-                db.find_symbol_by_usr(from)?,
-                db.find_symbol_by_usr(to)?
-            ) {
-                let _eid = insert_edge(
-                    &mut db,
-                    Some(&from_id),
-                    Some(&to_id),
-                    None,
-                    None,
-                    kind,
-                )?;
+/// Push `arg`, replacing a `@file` response file by its (recursively expanded) contents
+fn expand_arg(arg: String, directory: &Path, out: &mut Vec<String>, depth: usize) {
+    const MAX_RESPONSE_FILE_DEPTH: usize = 8;
+
+    let Some(rsp) = arg.strip_prefix('@') else {
+        out.push(arg);
+        return;
+    };
+    let path = directory.join(rsp);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) if depth < MAX_RESPONSE_FILE_DEPTH => contents,
+        Ok(_) => {
+            eprintln!("Warning: Response files nested too deeply at {}", path.display());
+            return;
+        }
+        Err(e) => {
+            eprintln!("Warning: Cannot read response file {}: {}", path.display(), e);
+            out.push(arg);
+            return;
+        }
+    };
+    match shell_words::split(&contents) {
+        Ok(words) => {
+            for word in words {
+                expand_arg(word, directory, out, depth + 1);
+            }
+        }
+        Err(e) => eprintln!("Warning: Cannot parse response file {}: {}", path.display(), e),
+    }
+}
+
+/// Remove the stored symbols of every file a batch of results covers: the
+/// translation units and the headers their symbols were found in
+fn remove_rescanned_symbols(batch: &mut WriteBatch, results: &[TuResult]) -> Result<()> {
+    let files: BTreeSet<&str> = results
+        .iter()
+        .flat_map(|result| std::iter::once(result.file.as_str()).chain(result.symbols.iter().map(|s| s.file.as_str())))
+        .collect();
+    for file in files {
+        batch.remove_symbols_in_file(file)?;
+    }
+    Ok(())
+}
+
+/// Add one translation unit's results to a write batch, returning (symbols, relations) counts
+fn merge_tu_result(batch: &mut WriteBatch, result: TuResult) -> Result<(usize, usize)> {
+    let mut symbol_count = 0;
+    let mut relation_count = 0;
+    let category = Some(result.category.as_str());
+    let purpose = Some(result.purpose.as_str());
+
+    // Process symbols
+    for s in result.symbols {
+        let fid = batch.ensure_file_with_category("1", &s.file, "c++", category, purpose)?;
+        if result.is_header {
+            batch.mark_file_header(&fid)?;
+        }
+        let sid = batch.insert_symbol(Symbol {
+            id: String::new(),
+            file_id: fid.clone(),
+            usr: s.usr,
+            key: None,
+            name: s.name,
+            kind: s.kind,
+            is_definition: s.is_definition,
+            cfg: None,
+            metadata: s.metadata,
+            template_args: s.template_args,
+            def_start_line: s.def_lines.map(|(start, _)| start),
+            def_end_line: s.def_lines.map(|(_, end)| end),
+            documentation: s.doc,
+            parent_usr: s.parent_usr,
+        })?;
+        if s.is_definition {
+            batch.insert_occurrence(&sid, &fid, "definition", s.line, s.column, None, None)?;
+        }
+        symbol_count += 1;
+    }
+
+    // Process occurrences
+    for o in result.occurrences {
+        let fid = batch.ensure_file_with_category("1", &o.file, "c++", category, purpose)?;
+        
+        // Find symbol by USR first
+        if let Some(usr) = &o.usr {
+            if let Some(sym_id) = batch.find_symbol_by_usr(usr)? {
+                batch.insert_occurrence(&sym_id, &fid, &o.usage_kind, o.line, o.column, None, None)?;
                 relation_count += 1;
             }
         }
     }
 
-    println!("\n=== Summary ===");
-    println!("Files processed: {}", file_count);
-    println!("Symbols extracted: {}", symbol_count);
-    println!("Relations found: {}", relation_count);
+    // Process edges
+    let edge_kinds = EdgeKindRegistry::new();
+    for (kind, from, to) in &result.edges {
+        if let (Some(from_id), Some(to_id)) = (
+            batch.find_symbol_by_usr(from)?,
+            batch.find_symbol_by_usr(to)?
+        ) {
+            batch.insert_edge(&edge_kinds, Some(&from_id), Some(&to_id), None, None, kind)?;
+            relation_count += 1;
+        }
+    }
 
-    Ok(())
+    Ok((symbol_count, relation_count))
 }
 
 /// Import C++20 module dependencies.
 pub fn import_modules(root: &str, db_path: &str) -> Result<()> {
-    use symgraph_cxx::modules::scan_cpp20_module;
+    use symgraph_cxx::modules::{primary_module_name, scan_cpp20_module};
     use walkdir::WalkDir;
 
     let mut db = Db::open(db_path)?;
+    let edge_kinds = EdgeKindRegistry::new();
 
     for entry in WalkDir::new(root)
         .into_iter()
@@ -142,6 +642,13 @@ pub fn import_modules(root: &str, db_path: &str) -> Result<()> {
                 &path.to_string_lossy(),
             )?;
 
+            // A partition belongs to its primary module
+            let primary = primary_module_name(&module_info.name);
+            if module_info.partition.is_some() {
+                let primary_id = upsert_module(&mut db, primary, "cpp20-module", "")?;
+                insert_edge(&mut db, &edge_kinds, None, None, Some(&primary_id), Some(&module_id), "module-partition")?;
+            }
+
             // Import module dependencies
             for dep in &module_info.imports {
                     let dep_id = upsert_module(
@@ -152,12 +659,17 @@ pub fn import_modules(root: &str, db_path: &str) -> Result<()> {
                     )?;
                     insert_edge(
                         &mut db,
+                        &edge_kinds,
                         None,
                         None,
                         Some(&module_id),
                         Some(&dep_id),
                         "module-import",
                     )?;
+                    if dep.contains(':') && primary_module_name(dep) == primary {
+                        let primary_id = upsert_module(&mut db, primary, "cpp20-module", "")?;
+                        insert_edge(&mut db, &edge_kinds, None, None, Some(&primary_id), Some(&dep_id), "module-partition")?;
+                    }
             }
         }
     }
@@ -166,13 +678,19 @@ pub fn import_modules(root: &str, db_path: &str) -> Result<()> {
 }
 
 /// Scan C++20 modules directly from source.
-pub fn scan_modules(root: &str, db_path: &str) -> Result<()> {
+///
+/// Relations whose target is defined in another scanned module are stored as edges
+/// between the two symbols with both modules filled in. Rust sources found under
+/// `root` are analyzed as well, so that `impl Trait for Type` relations end up in
+/// the database as `"impl_trait"` edges.
+pub fn scan_modules(root: &str, db_path: &str, quiet: bool) -> Result<()> {
     use symgraph_cxx::modules::analyze_cpp_module;
+    use symgraph_models::resolve_cross_module_relations;
     use walkdir::WalkDir;
 
     let mut db = Db::open(db_path)?;
 
-    for entry in WalkDir::new(root)
+    let entries: Vec<_> = WalkDir::new(root)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| {
@@ -180,13 +698,23 @@ pub fn scan_modules(root: &str, db_path: &str) -> Result<()> {
             path.is_file()
                 && (path.extension().map_or(false, |ext| ext == "cppm")
                     || path.extension().map_or(false, |ext| ext == "ixx")
-                    || path.extension().map_or(false, |ext| ext == "mxx"))
+                    || path.extension().map_or(false, |ext| ext == "mxx")
+                    || path.extension().map_or(false, |ext| ext == "rs"))
         })
-    {
+        .collect();
+
+    let mut analyses = Vec::new();
+    let progress = ProgressReporter::new(entries.len(), quiet);
+    for entry in &entries {
         let path = entry.path();
+        progress.advance(&path.to_string_lossy());
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            scan_rust_module_file(&mut db, path, &progress)?;
+            continue;
+        }
         let analysis_result = analyze_cpp_module(path.to_str().unwrap())?;
         if let Some(analysis) = analysis_result {
-            println!("Analyzing module: {}", path.display());
+            progress.println(format!("Analyzing module: {}", path.display()));
             let _module_id = upsert_module(
                 &mut db,
                 &analysis.info.name,
@@ -194,25 +722,247 @@ pub fn scan_modules(root: &str, db_path: &str) -> Result<()> {
                 &path.to_string_lossy(),
             )?;
 
-            // Add module dependencies - skip for now until we have proper symbol name resolution
-            // for rel in &analysis.relations {
-            //     if let (Some(from_id), Some(to_id)) = (
-            //         db.find_symbol_by_name(&rel.from_name)?,
-            //         db.find_symbol_by_name(&rel.to_name)?
-            //     ) {
-            //         let _eid = insert_edge(
-            //             &mut db,
-            //             Some(&from_id),
-            //             Some(&to_id),
-            //             None,
-            //             None,
-            //             &rel.kind,
-            //         )?;
-            //     }
-            // }
+            let fid = db.ensure_file(&analysis.info.path, "c++")?;
+            for sym in &analysis.symbols {
+                let usr = format!("module:{}:{}", analysis.info.name, sym.name);
+                let sid = insert_symbol(&mut db, &fid, Some(&usr), None, &sym.name, &sym.kind, sym.is_exported)?;
+                if !sym.metadata.is_empty() {
+                    db.set_symbol_metadata(&sid, &sym.metadata)?;
+                }
+                if sym.doc.is_some() {
+                    db.set_symbol_doc(&sid, sym.doc.as_deref())?;
+                }
+            }
+            analyses.push(analysis);
+        }
+    }
+    progress.finish(entries.len());
+
+    // Symbol names are only unique within a module, so relations can be resolved
+    // once every module is known
+    let edge_kinds = EdgeKindRegistry::new();
+    for rel in resolve_cross_module_relations(&analyses) {
+        let from_usr = format!("module:{}:{}", rel.from_module, rel.from_symbol);
+        let to_usr = format!("module:{}:{}", rel.to_module, rel.to_symbol);
+        let (Some(from_id), Some(to_id)) = (db.find_symbol_by_usr(&from_usr)?, db.find_symbol_by_usr(&to_usr)?) else {
+            continue;
+        };
+        let from_module = upsert_module(&mut db, &rel.from_module, "cpp20-module", "")?;
+        let to_module = upsert_module(&mut db, &rel.to_module, "cpp20-module", "")?;
+        insert_edge(&mut db, &edge_kinds, Some(&from_id), Some(&to_id), Some(&from_module), Some(&to_module), &rel.kind)?;
+    }
+
+    println!("\n=== Summary ===");
+    for entry in &entries {
+        let path = entry.path().to_string_lossy();
+        let count = db.find_symbols_in_file(&path)?.len();
+        if count > 0 {
+            println!("{}: {} symbols", path, count);
         }
     }
 
     Ok(())
 }
 
+/// Analyze a single Rust source file and store its symbols and trait implementations.
+fn scan_rust_module_file(db: &mut Db, path: &Path, progress: &ProgressReporter) -> Result<()> {
+    use symgraph_rust::{analyze_rust_module_from_text, analyze_rust_module_syn, categorize_rust_file, infer_rust_purpose};
+
+    let text = std::fs::read_to_string(path)?;
+    let path_str = path.to_string_lossy();
+    // Fall back to the regex analyzer for files syn cannot parse
+    let analysis = match analyze_rust_module_syn(&text, &path_str)
+        .or_else(|_| analyze_rust_module_from_text(&text, &path_str))?
+    {
+        Some(analysis) => analysis,
+        None => return Ok(()),
+    };
+
+    progress.println(format!("Analyzing Rust module: {}", path.display()));
+    let _module_id = upsert_module(
+        db,
+        &analysis.info.name,
+        "rust-module",
+        &path.to_string_lossy(),
+    )?;
+
+    let category = categorize_rust_file(&analysis.info.path);
+    let purpose = infer_rust_purpose(&analysis.info.path, &category);
+    let category = format!("{:?}", category).to_lowercase();
+    let fid = db.ensure_file_with_category("1", &analysis.info.path, "rust", Some(&category), Some(&purpose))?;
+    for sym in &analysis.symbols {
+        let usr = format!("module:{}:{}", analysis.info.name, sym.name);
+        let sid = insert_symbol(
+            db,
+            &fid,
+            Some(&usr),
+            None,
+            &sym.name,
+            &sym.kind,
+            sym.is_exported,
+        )?;
+        if sym.cfg.is_some() {
+            db.set_symbol_cfg(&sid, sym.cfg.as_deref())?;
+        }
+        if !sym.metadata.is_empty() {
+            db.set_symbol_metadata(&sid, &sym.metadata)?;
+        }
+        if sym.doc.is_some() {
+            db.set_symbol_doc(&sid, sym.doc.as_deref())?;
+        }
+    }
+
+    // Trait implementations: the implementing type must be a known symbol, while the
+    // trait itself is usually foreign (e.g. `Display`) and gets a declaration-only symbol
+    let edge_kinds = EdgeKindRegistry::new();
+    for rel in analysis.relations.iter().filter(|r| r.kind == "impl_trait") {
+        let from_usr = format!("module:{}:{}", analysis.info.name, rel.from_name);
+        let Some(from_id) = db.find_symbol_by_usr(&from_usr)? else {
+            continue;
+        };
+
+        let local_trait_usr = format!("module:{}:{}", analysis.info.name, rel.to_name);
+        let trait_usr = format!("rust-trait:{}", rel.to_name);
+        let to_id = match db.find_symbol_by_usr(&local_trait_usr)? {
+            Some(id) => id,
+            None => match db.find_symbol_by_usr(&trait_usr)? {
+                Some(id) => id,
+                None => insert_symbol(
+                    db,
+                    &fid,
+                    Some(&trait_usr),
+                    None,
+                    &rel.to_name,
+                    "trait",
+                    false,
+                )?,
+            },
+        };
+
+        insert_edge(db, &edge_kinds, Some(&from_id), Some(&to_id), None, None, &rel.kind)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_args_expands_response_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("args.rsp"), "-std=c++20 -I/usr/include\n").unwrap();
+
+        let cc = CompileCommand {
+            directory: dir.path().to_string_lossy().to_string(),
+            file: "main.cpp".to_string(),
+            command: None,
+            arguments: Some(vec![
+                "clang++".to_string(),
+                "@args.rsp".to_string(),
+                "-DNDEBUG".to_string(),
+                "main.cpp".to_string(),
+            ]),
+        };
+        assert_eq!(clean_args(&cc), ["-std=c++20", "-I/usr/include", "-DNDEBUG", "main.cpp"]);
+
+        let cc = CompileCommand {
+            arguments: None,
+            command: Some("cl.exe @args.rsp \"/Fo out.obj\"".to_string()),
+            ..cc
+        };
+        assert_eq!(clean_args(&cc), ["-std=c++20", "-I/usr/include", "/Fo out.obj"]);
+    }
+
+    #[test]
+    fn test_rescan_does_not_duplicate_header_symbols() {
+        let symbol = |usr: &str, name: &str, file: &str| symgraph_cxx::Symbol {
+            usr: Some(usr.to_string()),
+            name: name.to_string(),
+            kind: "ClassDecl".to_string(),
+            file: file.to_string(),
+            line: 1,
+            column: 7,
+            is_definition: true,
+            template_args: None,
+            metadata: Default::default(),
+            def_lines: None,
+            doc: None,
+            parent_usr: None,
+        };
+        let result = || TuResult {
+            file: "/project/src/main.cpp".to_string(),
+            category: "source".to_string(),
+            purpose: String::new(),
+            is_header: false,
+            symbols: vec![
+                symbol("c:@S@App", "App", "/project/src/main.cpp"),
+                symbol("c:@S@Widget", "Widget", "/project/include/widget.hpp"),
+            ],
+            occurrences: Vec::new(),
+            edges: Vec::new(),
+        };
+
+        let db = Db::open_temporary().unwrap();
+        for _ in 0..3 {
+            let results = vec![result()];
+            let mut batch = db.write_batch();
+            remove_rescanned_symbols(&mut batch, &results).unwrap();
+            for result in results {
+                merge_tu_result(&mut batch, result).unwrap();
+            }
+            batch.apply().unwrap();
+        }
+        assert_eq!(db.find_symbols_in_file("/project/include/widget.hpp").unwrap().len(), 1);
+        assert_eq!(db.find_symbols_in_file("/project/src/main.cpp").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_header_tu_is_stored_with_header_flag() {
+        let cc = CompileCommand {
+            directory: "/project".to_string(),
+            file: "/project/include/widget.hpp".to_string(),
+            command: Some("clang++ -std=c++20 -c /project/include/widget.hpp -o widget.o".to_string()),
+            arguments: None,
+        };
+        assert!(matches!(categorize_cpp_file(&cc.file), FileCategory::Header));
+        assert_eq!(
+            header_args(clean_args(&cc)),
+            ["-x", "c++-header", "-std=c++20", "/project/include/widget.hpp"]
+        );
+
+        let result = TuResult {
+            file: cc.file.clone(),
+            category: "header".to_string(),
+            purpose: String::new(),
+            is_header: true,
+            symbols: vec![symgraph_cxx::Symbol {
+                usr: Some("c:@S@Widget".to_string()),
+                name: "Widget".to_string(),
+                kind: "ClassDecl".to_string(),
+                file: cc.file.clone(),
+                line: 3,
+                column: 7,
+                is_definition: true,
+                template_args: None,
+                metadata: Default::default(),
+                def_lines: None,
+                doc: None,
+                parent_usr: None,
+            }],
+            occurrences: Vec::new(),
+            edges: Vec::new(),
+        };
+        let db = Db::open_temporary().unwrap();
+        let mut batch = db.write_batch();
+        merge_tu_result(&mut batch, result).unwrap();
+        batch.apply().unwrap();
+
+        let file = db.find_file_by_path(&cc.file).unwrap().unwrap();
+        assert!(file.is_header);
+
+        assert!(is_skipped_header(&cc, true));
+        assert!(!is_skipped_header(&cc, false));
+    }
+}
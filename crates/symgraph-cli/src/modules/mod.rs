@@ -1,4 +1,5 @@
 pub mod commands;
 pub mod cxx_analyzer;
 pub mod rust_analyzer;
+pub mod server;
 pub mod utils;
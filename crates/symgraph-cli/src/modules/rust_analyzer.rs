@@ -1,14 +1,21 @@
 use anyhow::{Result, Context};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use cargo_metadata::MetadataCommand;
 use symgraph_core::Db;
 use walkdir::WalkDir;
 
 /// Analyze Rust projects: collect functions and call edges using SCIP indexing.
-pub fn scan_rust(manifest_path: &str, lsif: Option<&str>, db_path: &str) -> Result<()> {
+///
+/// Every workspace crate not named in `skip_crates` is indexed from its `src`
+/// directory, plus `tests` and `benches` when `include_tests` is set.
+pub fn scan_rust(
+    manifest_path: &str,
+    lsif: Option<&str>,
+    db_path: &str,
+    include_tests: bool,
+    skip_crates: &[String],
+) -> Result<()> {
     use symgraph_discovery::{ScipLanguage, check_scip_tool_availability};
-    use symgraph_core::scip::{load_scip_to_database, parse_scip_file};
-    use std::path::PathBuf;
     
     // Resolve manifest path and metadata
     let manifest_path = Path::new(manifest_path).canonicalize()?;
@@ -21,7 +28,16 @@ pub fn scan_rust(manifest_path: &str, lsif: Option<&str>, db_path: &str) -> Resu
     
     // If LSIF file is provided, parse it and insert into database
     if let Some(lsif_path) = lsif {
-        parse_lsif_and_insert(lsif_path, &mut db, &metadata.workspace_root.to_string())?;
+        let stats = symgraph_core::import_lsif_file(
+            &mut db,
+            Path::new(lsif_path),
+            metadata.workspace_root.as_str(),
+            true,
+        )?;
+        println!(
+            "Imported LSIF: {} definitions, {} references",
+            stats.definitions, stats.references
+        );
         return Ok(());
     }
     
@@ -29,73 +45,48 @@ pub fn scan_rust(manifest_path: &str, lsif: Option<&str>, db_path: &str) -> Resu
     println!("Generating SCIP index for Rust project...");
     match check_scip_tool_availability(&ScipLanguage::Rust) {
         Ok(true) => {
-            // Find all Rust files
-            let rust_files: Vec<PathBuf> = WalkDir::new(project_dir.join("src"))
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.path().extension().map_or(false, |ext| ext == "rs")
-                })
-                .map(|e| e.path().to_path_buf())
-                .collect();
-            
-            println!("Found {} Rust files to index", rust_files.len());
-            
+            let mut summary = Vec::new();
             let mut total_symbols = 0;
             let mut total_documents = 0;
-            
-            for rust_file in &rust_files {
-                let scip_output = project_dir.join(format!("dump_{}.scip", 
-                    rust_file.file_stem().unwrap().to_str().unwrap()));
-                
-                // Generate SCIP for individual file
-                let mut cmd = std::process::Command::new("rust-analyzer");
-                cmd.arg("scip")
-                    .arg(rust_file.strip_prefix(project_dir).unwrap())
-                    .arg("--output")
-                    .arg(&scip_output)
-                    .current_dir(project_dir);
-                
-                let output = cmd.output()
-                    .with_context(|| "Failed to execute rust-analyzer. Install with: rustup component add rust-analyzer")?;
-                
-                if !output.status.success() {
-                    eprintln!("Failed to generate SCIP for {}: {}", 
-                        rust_file.display(), 
-                        String::from_utf8_lossy(&output.stderr));
+
+            for package in metadata.workspace_packages() {
+                let name = package.name.to_string();
+                if skip_crates.contains(&name) {
+                    println!("Skipping crate {}", name);
                     continue;
                 }
-                
-                if !scip_output.exists() {
-                    eprintln!("SCIP file was not generated for: {}", scip_output.display());
-                    continue;
+                let package_dir = package.manifest_path.parent().unwrap().as_std_path();
+                let mut dirs = vec![package_dir.join("src")];
+                if include_tests {
+                    dirs.push(package_dir.join("tests"));
+                    dirs.push(package_dir.join("benches"));
                 }
-                
-                // Load SCIP data from file
-                match parse_scip_file(&scip_output) {
-                    Ok(scip_data) => {
-                        total_documents += scip_data.documents.len();
-                        total_symbols += scip_data.symbols.len();
-                        
-                        // Load SCIP data into database
-                        match load_scip_to_database(&mut db, &scip_data, &metadata.workspace_root.to_string()) {
-                            Ok(_) => {
-                                // Success
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to load SCIP data into database: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse SCIP file: {}", e);
+
+                let rust_files: Vec<PathBuf> = dirs
+                    .iter()
+                    .filter(|dir| dir.is_dir())
+                    .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+                    .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+                    .map(|e| e.path().to_path_buf())
+                    .collect();
+
+                let mut crate_symbols = 0;
+                for rust_file in &rust_files {
+                    if let Some((documents, symbols)) =
+                        index_rust_file(&mut db, project_dir, rust_file, metadata.workspace_root.as_str())
+                    {
+                        total_documents += documents;
+                        crate_symbols += symbols;
                     }
                 }
-                
-                // Clean up temporary SCIP file
-                let _ = std::fs::remove_file(&scip_output);
+                total_symbols += crate_symbols;
+                summary.push((name, rust_files.len(), crate_symbols));
+            }
+
+            println!("{:<30} {:>7} {:>9}", "CRATE", "FILES", "SYMBOLS");
+            for (name, files, symbols) in &summary {
+                println!("{:<30} {:>7} {:>9}", name, files, symbols);
             }
-            
             println!("SCIP indexing completed: {} documents, {} symbols total", 
                 total_documents, total_symbols);
         }
@@ -112,12 +103,64 @@ pub fn scan_rust(manifest_path: &str, lsif: Option<&str>, db_path: &str) -> Resu
     Ok(())
 }
 
-/// Parse LSIF file and insert into database (legacy support)
-fn parse_lsif_and_insert(lsif_path: &str, _db: &mut Db, _project_name: &str) -> Result<()> {
-    // This function can be implemented for legacy LSIF support
-    // For now, we'll just log that LSIF parsing is not implemented
-    println!("LSIF parsing not yet implemented: {}", lsif_path);
-    Ok(())
+/// Index one file with `rust-analyzer scip` and load the result.
+///
+/// Returns the number of documents and symbols loaded; failures are reported
+/// and yield `None` so that the remaining files are still indexed.
+fn index_rust_file(db: &mut Db, project_dir: &Path, rust_file: &Path, project_name: &str) -> Option<(usize, usize)> {
+    use symgraph_core::scip::{load_scip_to_database, parse_scip_file};
+
+    let scip_output = project_dir.join(format!("dump_{}.scip", 
+        rust_file.file_stem().unwrap().to_str().unwrap()));
+    
+    // Generate SCIP for individual file
+    let mut cmd = std::process::Command::new("rust-analyzer");
+    cmd.arg("scip")
+        .arg(rust_file.strip_prefix(project_dir).unwrap_or(rust_file))
+        .arg("--output")
+        .arg(&scip_output)
+        .current_dir(project_dir);
+    
+    let output = match cmd.output()
+        .with_context(|| "Failed to execute rust-analyzer. Install with: rustup component add rust-analyzer")
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return None;
+        }
+    };
+    
+    if !output.status.success() {
+        eprintln!("Failed to generate SCIP for {}: {}", 
+            rust_file.display(), 
+            String::from_utf8_lossy(&output.stderr));
+        return None;
+    }
+    
+    if !scip_output.exists() {
+        eprintln!("SCIP file was not generated for: {}", scip_output.display());
+        return None;
+    }
+    
+    // Load SCIP data from file
+    let loaded = match parse_scip_file(&scip_output) {
+        Ok(scip_data) => match load_scip_to_database(db, &scip_data, project_name) {
+            Ok(_) => Some((scip_data.documents.len(), scip_data.symbols.len())),
+            Err(e) => {
+                eprintln!("Failed to load SCIP data into database: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            eprintln!("Failed to parse SCIP file: {}", e);
+            None
+        }
+    };
+    
+    // Clean up temporary SCIP file
+    let _ = std::fs::remove_file(&scip_output);
+    loaded
 }
 
 /// Generate LSIF file using rust-analyzer (legacy support).
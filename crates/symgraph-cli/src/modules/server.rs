@@ -0,0 +1,108 @@
+//! JSON HTTP API over a symgraph database.
+//!
+//! Exposes the read-only query functions of [`SymgraphDb`] so editors and
+//! dashboards can consume graph data without spawning `symgraph-cli` per request.
+
+use anyhow::Result;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use symgraph_core::SymgraphDb;
+
+type SharedDb = Arc<Mutex<SymgraphDb>>;
+
+/// Error returned from handlers, rendered as `{"error": "..."}` with status 500
+struct ApiError(anyhow::Error);
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        ApiError(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": self.0.to_string() }));
+        (StatusCode::INTERNAL_SERVER_ERROR, body).into_response()
+    }
+}
+
+type ApiResult = std::result::Result<Json<Value>, ApiError>;
+
+/// Start the JSON API server and block until it exits.
+pub fn serve(db_path: &str, host: &str, port: u16) -> Result<()> {
+    let db = SymgraphDb::open(db_path)?;
+    let state: SharedDb = Arc::new(Mutex::new(db));
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async move {
+        let listener = tokio::net::TcpListener::bind((host, port))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to bind {}:{}: {}", host, port, e))?;
+        println!("Serving {} on http://{}", db_path, listener.local_addr()?);
+        axum::serve(listener, router(state)).await?;
+        Ok(())
+    })
+}
+
+fn router(state: SharedDb) -> Router {
+    Router::new()
+        .route("/stats", get(stats))
+        .route("/files", get(files))
+        .route("/modules", get(modules))
+        .route("/symbols", get(symbols))
+        .route("/symbols/:id/callers", get(callers))
+        .route("/symbols/:id/callees", get(callees))
+        .with_state(state)
+}
+
+async fn stats(State(db): State<SharedDb>) -> ApiResult {
+    let db = db.lock().unwrap();
+    Ok(Json(json!(db.get_stats()?)))
+}
+
+async fn files(State(db): State<SharedDb>) -> ApiResult {
+    let db = db.lock().unwrap();
+    Ok(Json(json!(db.list_files()?)))
+}
+
+async fn modules(State(db): State<SharedDb>) -> ApiResult {
+    let db = db.lock().unwrap();
+    Ok(Json(json!(db.list_modules()?)))
+}
+
+async fn symbols(State(db): State<SharedDb>, Query(params): Query<HashMap<String, String>>) -> ApiResult {
+    let db = db.lock().unwrap();
+    let symbols = match params.get("pattern") {
+        Some(pattern) => db.search_symbols(pattern)?,
+        None => db.list_symbols()?,
+    };
+    Ok(Json(json!(symbols)))
+}
+
+async fn callers(State(db): State<SharedDb>, Path(id): Path<String>) -> ApiResult {
+    let db = db.lock().unwrap();
+    let mut result = Vec::new();
+    for edge in db.edges_to(&id, "call")? {
+        if let Some(symbol) = edge.from_sym.map(|s| db.get_symbol(&s)).transpose()?.flatten() {
+            result.push(symbol);
+        }
+    }
+    Ok(Json(json!(result)))
+}
+
+async fn callees(State(db): State<SharedDb>, Path(id): Path<String>) -> ApiResult {
+    let db = db.lock().unwrap();
+    let mut result = Vec::new();
+    for edge in db.edges_from(&id, "call")? {
+        if let Some(symbol) = edge.to_sym.map(|s| db.get_symbol(&s)).transpose()?.flatten() {
+            result.push(symbol);
+        }
+    }
+    Ok(Json(json!(result)))
+}
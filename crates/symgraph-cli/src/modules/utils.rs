@@ -1,6 +1,8 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
 use symgraph_discovery::generate_scip_index;
 use serde_json;
 use tempfile;
@@ -16,6 +18,10 @@ use symgraph_core::scip::parse_scip_file;
 /// * `generator` - CMake generator
 /// * `configuration` - VS configuration (Debug/Release)
 /// * `platform` - VS platform (x64/Win32)
+/// * `vcpkg_root` - vcpkg root used when the project has a vcpkg.json manifest
+/// * `read_cache` - Add flags from CMakeCache.txt (CMake only)
+/// * `dry_run` - Print the commands that would run instead of running them
+#[allow(clippy::too_many_arguments)]
 pub fn generate_compdb(
     project: &str,
     output: Option<&str>,
@@ -24,73 +30,148 @@ pub fn generate_compdb(
     generator: Option<&str>,
     configuration: Option<&str>,
     platform: Option<&str>,
+    vcpkg_root: Option<&str>,
+    read_cache: bool,
+    dry_run: bool,
 ) -> Result<()> {
+    use crate::modules::commands::cli::BuildSystemType;
+
     let project_path = Path::new(project);
     let output_path = output.unwrap_or("compile_commands.json");
 
     // Detect build system if not specified
-    let build_system = build_system.unwrap_or(crate::modules::commands::cli::BuildSystemType::Auto);
+    let build_system = match build_system.unwrap_or(BuildSystemType::Auto) {
+        BuildSystemType::Auto => detect_build_system_type(project_path),
+        build_system => build_system,
+    };
+
+    if dry_run {
+        let plan = compdb_plan(&build_system, project_path, output_path, build_dir, generator, configuration, platform, vcpkg_root)?;
+        println!("Build system: {:?}", build_system);
+        for command in plan {
+            let verb = if command.fallback { "Would fall back to" } else { "Would run" };
+            println!("{}", console::style(format!("{}: {}", verb, command)).yellow());
+        }
+        println!("Output: {}", output_path);
+        return Ok(());
+    }
 
     match build_system {
-        crate::modules::commands::cli::BuildSystemType::Auto => {
-            // Try to detect build system automatically
-            if project_path.join("CMakeLists.txt").exists() {
-                return generate_cmake_compdb(project_path, output_path, build_dir, generator);
-            } else if project_path.join("Makefile").exists() {
-                return generate_make_compdb(project_path, output_path);
-            } else if project_path.join("Cargo.toml").exists() {
-                return generate_cargo_compdb(project_path, output_path);
-            } else if find_file_with_ext(project_path, "sln").is_ok() {
-                return generate_vs_compdb(project_path, output_path, configuration, platform);
-            } else {
-                anyhow::bail!("Could not detect build system in {}", project);
-            }
+        BuildSystemType::Auto => {
+            anyhow::bail!("Could not detect build system in {}", project);
+        }
+        BuildSystemType::CMake => {
+            generate_cmake_compdb(project_path, output_path, build_dir, generator, vcpkg_root, read_cache)
+        }
+        BuildSystemType::XMake => {
+            generate_xmake_compdb(project_path, output_path)
         }
-        crate::modules::commands::cli::BuildSystemType::CMake => {
-            return generate_cmake_compdb(project_path, output_path, build_dir, generator);
+        BuildSystemType::Make => {
+            generate_make_compdb(project_path, output_path)
         }
-        crate::modules::commands::cli::BuildSystemType::Make => {
-            return generate_make_compdb(project_path, output_path);
+        BuildSystemType::Autotools => {
+            generate_autotools_compdb(project_path, output_path)
         }
-        crate::modules::commands::cli::BuildSystemType::Solution => {
-            return generate_vs_compdb(project_path, output_path, configuration, platform);
+        BuildSystemType::Solution => {
+            generate_vs_compdb(project_path, output_path, configuration, platform)
         }
-        crate::modules::commands::cli::BuildSystemType::Cargo => {
-            return generate_cargo_compdb(project_path, output_path);
+        BuildSystemType::Cargo => {
+            generate_cargo_compdb(project_path, output_path)
+        }
+        BuildSystemType::CompileFlags => {
+            generate_compile_flags_compdb(project_path, output_path)
         }
     }
 }
 
-/// Generate compile_commands.json from CMake project
-fn generate_cmake_compdb(project_path: &Path, output: &str, build_dir: Option<&str>, generator: Option<&str>) -> Result<()> {
-    let build_dir = build_dir.unwrap_or("build");
-    let build_dir_path = project_path.join(build_dir);
+/// Detect the build system of a project; `Auto` when none is found
+fn detect_build_system_type(project_path: &Path) -> crate::modules::commands::cli::BuildSystemType {
+    use crate::modules::commands::cli::BuildSystemType;
 
-    // Create build directory if it doesn't exist
-    if !build_dir_path.exists() {
-        std::fs::create_dir_all(&build_dir_path)
-            .map_err(|e| anyhow::anyhow!("Failed to create build directory '{}': {}", build_dir_path.display(), e))?;
+    if project_path.join("CMakeLists.txt").exists() {
+        BuildSystemType::CMake
+    } else if project_path.join("xmake.lua").exists() {
+        BuildSystemType::XMake
+    } else if project_path.join("configure.ac").exists() || project_path.join("configure.in").exists() {
+        BuildSystemType::Autotools
+    } else if project_path.join("Makefile").exists() {
+        BuildSystemType::Make
+    } else if project_path.join("Cargo.toml").exists() {
+        BuildSystemType::Cargo
+    } else if find_file_with_ext(project_path, "sln").is_ok() {
+        BuildSystemType::Solution
+    } else if project_path.join("compile_flags.txt").exists() {
+        BuildSystemType::CompileFlags
+    } else {
+        BuildSystemType::Auto
     }
+}
 
-    let mut cmake_cmd = Command::new("cmake");
-    cmake_cmd.current_dir(&build_dir_path);
+/// Commands `generate_compdb` runs for a build system, built by the same planners as the real run
+#[allow(clippy::too_many_arguments)]
+fn compdb_plan(
+    build_system: &crate::modules::commands::cli::BuildSystemType,
+    project_path: &Path,
+    output: &str,
+    build_dir: Option<&str>,
+    generator: Option<&str>,
+    configuration: Option<&str>,
+    platform: Option<&str>,
+    vcpkg_root: Option<&str>,
+) -> Result<Vec<symgraph_discovery::PlannedCommand>> {
+    use crate::modules::commands::cli::BuildSystemType;
 
-    // Configure with generator if specified
-    if let Some(gen) = generator {
-        cmake_cmd.args(&["-G", gen]);
-    }
+    let output = Path::new(output);
+    let plan = match build_system {
+        BuildSystemType::Auto => anyhow::bail!("Could not detect build system in {}", project_path.display()),
+        BuildSystemType::CMake => vec![symgraph_discovery::plan_cmake(
+            project_path,
+            &project_path.join(build_dir.unwrap_or("build")),
+            generator,
+            &cmake_extra_args(),
+            vcpkg_root.map(Path::new),
+        )?],
+        BuildSystemType::XMake => vec![symgraph_discovery::plan_xmake(project_path, output)?],
+        BuildSystemType::Make => symgraph_discovery::plan_makefile(project_path, output, &[])?,
+        BuildSystemType::Autotools => symgraph_discovery::plan_autotools(project_path, output, &[])?,
+        BuildSystemType::Solution => vec![plan_vs_compdb(project_path, configuration, platform)?],
+        BuildSystemType::Cargo => vec![plan_cargo_compdb(project_path)],
+        // The flags file is read directly, nothing is executed
+        BuildSystemType::CompileFlags => Vec::new(),
+    };
+    Ok(plan)
+}
 
-    cmake_cmd.args(&[
-        "..",
-        "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON",
-        "-DCMAKE_BUILD_TYPE=Debug",
-    ]);
+/// Arguments `generate_compdb` passes to CMake besides the generator and toolchain
+fn cmake_extra_args() -> Vec<String> {
+    vec!["-DCMAKE_BUILD_TYPE=Debug".to_string()]
+}
+
+/// Generate compile_commands.json from CMake project
+fn generate_cmake_compdb(
+    project_path: &Path,
+    output: &str,
+    build_dir: Option<&str>,
+    generator: Option<&str>,
+    vcpkg_root: Option<&str>,
+    read_cache: bool,
+) -> Result<()> {
+    let build_dir_path = project_path.join(build_dir.unwrap_or("build"));
 
-    let cmake_output = cmake_cmd.output()?;
-    if !cmake_output.status.success() {
-        anyhow::bail!("CMake configuration failed: {}", String::from_utf8_lossy(&cmake_output.stderr));
+    let vcpkg_deps = symgraph_discovery::extract_vcpkg_dependencies(project_path)?;
+    if !vcpkg_deps.is_empty() {
+        let names: Vec<_> = vcpkg_deps.iter().map(|d| d.name.as_str()).collect();
+        println!("vcpkg dependencies: {}", names.join(", "));
     }
 
+    symgraph_discovery::generate_from_cmake(
+        project_path,
+        &build_dir_path,
+        generator,
+        &cmake_extra_args(),
+        vcpkg_root.map(Path::new),
+    )?;
+
     // Copy compile_commands.json to project root if needed
     let compdb_path = build_dir_path.join("compile_commands.json");
     let output_path = Path::new(output);
@@ -104,54 +185,44 @@ fn generate_cmake_compdb(project_path: &Path, output: &str, build_dir: Option<&s
         anyhow::bail!("compile_commands.json not found in build directory");
     }
 
+    if read_cache {
+        let cache = symgraph_discovery::read_cmake_cache(&build_dir_path)?;
+        let mut commands = symgraph_discovery::load_compile_commands(output)?;
+        symgraph_discovery::apply_cmake_cache(&mut commands, &cache)?;
+        std::fs::write(output_path, serde_json::to_string_pretty(&commands)?)
+            .map_err(|e| anyhow::anyhow!("Failed to write compile_commands.json to '{}': {}", output_path.display(), e))?;
+    }
+
     Ok(())
 }
 
-/// Generate compile_commands.json from Makefile project
-fn generate_make_compdb(project_path: &Path, output: &str) -> Result<()> {
-    // Use bear to generate compile_commands.json from Make
-    let bear_output = Command::new("bear")
-        .arg("--")
-        .arg("make")
-        .current_dir(project_path)
-        .output()?;
+/// Generate compile_commands.json from xmake project
+fn generate_xmake_compdb(project_path: &Path, output: &str) -> Result<()> {
+    symgraph_discovery::generate_from_xmake(project_path, Path::new(output))?;
+    Ok(())
+}
 
-    if !bear_output.status.success() {
-        anyhow::bail!("bear make failed: {}", String::from_utf8_lossy(&bear_output.stderr));
-    }
+/// Generate compile_commands.json from compile_flags.txt
+fn generate_compile_flags_compdb(project_path: &Path, output: &str) -> Result<()> {
+    symgraph_discovery::generate_from_compile_flags(project_path, Path::new(output))?;
+    Ok(())
+}
 
-    // Move compile_commands.json to desired location
-    let compdb_path = project_path.join("compile_commands.json");
-    let output_path = Path::new(output);
-    if compdb_path.exists() {
-        if compdb_path != output_path {
-            std::fs::copy(&compdb_path, output_path)
-                .map_err(|e| anyhow::anyhow!("Failed to copy compile_commands.json from '{}' to '{}': {}", 
-                    compdb_path.display(), output_path.display(), e))?;
-        }
-    } else {
-        anyhow::bail!("compile_commands.json not generated");
-    }
+/// Generate compile_commands.json from Makefile project (bear, then compiledb, then `make -n`)
+fn generate_make_compdb(project_path: &Path, output: &str) -> Result<()> {
+    symgraph_discovery::generate_from_makefile(project_path, Path::new(output), &[])?;
+    Ok(())
+}
 
+/// Generate compile_commands.json from GNU Autotools project (uses `bear` when available)
+fn generate_autotools_compdb(project_path: &Path, output: &str) -> Result<()> {
+    symgraph_discovery::generate_from_autotools(project_path, Path::new(output), &[])?;
     Ok(())
 }
 
 /// Generate compile_commands.json from Visual Studio solution
 fn generate_vs_compdb(project_path: &Path, output: &str, configuration: Option<&str>, platform: Option<&str>) -> Result<()> {
-    let sln_path = find_file_with_ext(project_path, "sln")?;
-    
-    let mut vs_cmd = Command::new("compdb");
-    vs_cmd.arg("-p").arg(&sln_path);
-
-    if let Some(config) = configuration {
-        vs_cmd.arg("-c").arg(config);
-    }
-
-    if let Some(plat) = platform {
-        vs_cmd.arg("-p").arg(plat);
-    }
-
-    let vs_output = vs_cmd.output()?;
+    let vs_output = plan_vs_compdb(project_path, configuration, platform)?.command().output()?;
     if !vs_output.status.success() {
         anyhow::bail!("compdb failed: {}", String::from_utf8_lossy(&vs_output.stderr));
     }
@@ -172,12 +243,26 @@ fn generate_vs_compdb(project_path: &Path, output: &str, configuration: Option<&
     Ok(())
 }
 
+/// `compdb` invocation for the first Visual Studio solution in the project
+fn plan_vs_compdb(
+    project_path: &Path,
+    configuration: Option<&str>,
+    platform: Option<&str>,
+) -> Result<symgraph_discovery::PlannedCommand> {
+    let sln_path = find_file_with_ext(project_path, "sln")?;
+    let mut command = symgraph_discovery::PlannedCommand::new(project_path, "compdb").arg("-p").arg(&sln_path);
+    if let Some(config) = configuration {
+        command = command.arg("-c").arg(config);
+    }
+    if let Some(plat) = platform {
+        command = command.arg("-p").arg(plat);
+    }
+    Ok(command)
+}
+
 /// Generate compile_commands.json from Cargo project
 fn generate_cargo_compdb(project_path: &Path, output: &str) -> Result<()> {
-    let cargo_output = Command::new("cargo")
-        .args(&["check", "--message-format=json"])
-        .current_dir(project_path)
-        .output()?;
+    let cargo_output = plan_cargo_compdb(project_path).command().output()?;
 
     if !cargo_output.status.success() {
         anyhow::bail!("cargo check failed: {}", String::from_utf8_lossy(&cargo_output.stderr));
@@ -197,42 +282,608 @@ fn generate_cargo_compdb(project_path: &Path, output: &str) -> Result<()> {
         }
     ]);
 
-    let output_path = Path::new(output);
-    std::fs::write(&output_path, serde_json::to_string_pretty(&compdb)?)
-        .map_err(|e| anyhow::anyhow!("Failed to write compile_commands.json to '{}': {}", output_path.display(), e))?;
+    let output_path = Path::new(output);
+    std::fs::write(&output_path, serde_json::to_string_pretty(&compdb)?)
+        .map_err(|e| anyhow::anyhow!("Failed to write compile_commands.json to '{}': {}", output_path.display(), e))?;
+    Ok(())
+}
+
+/// `cargo check` invocation whose JSON messages describe the crate
+fn plan_cargo_compdb(project_path: &Path) -> symgraph_discovery::PlannedCommand {
+    symgraph_discovery::PlannedCommand::new(project_path, "cargo").args(["check", "--message-format=json"])
+}
+
+/// Находит файл с указанным расширением в директории
+fn find_file_with_ext(dir: &Path, ext: &str) -> Result<std::path::PathBuf> {
+    std::fs::read_dir(dir)
+        .map_err(|e| anyhow::anyhow!("Failed to read directory '{}': {}", dir.display(), e))?;
+    
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            if let Some(extension) = path.extension() {
+                if extension == ext {
+                    return Ok(path);
+                }
+            }
+        }
+    }
+    anyhow::bail!("No .{} file found in {}", ext, dir.display());
+}
+
+/// Validate compile_commands.json and report malformed entries.
+///
+/// `rewrite` is a `FROM:TO` path prefix substitution applied before validation.
+pub fn validate_compdb(compdb: &str, rewrite: Option<&str>) -> Result<()> {
+    let mut commands = symgraph_discovery::load_compile_commands(compdb)?;
+    if let Some(spec) = rewrite {
+        let (from, to) = symgraph_discovery::parse_path_rewrite(spec)?;
+        symgraph_discovery::rewrite_paths(&mut commands, &from, &to);
+    }
+    let errors = symgraph_discovery::validate_compile_commands(&commands);
+
+    if errors.is_empty() {
+        println!("{}: {} entries, no problems found", compdb, commands.len());
+        return Ok(());
+    }
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    Err(anyhow::anyhow!("{}: {} validation errors in {} entries", compdb, errors.len(), commands.len()))
+}
+
+/// Query call graph for a symbol.
+pub fn query_calls(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    // Print each callee, decoding its USR when it has one
+    for edge in db.edges_from(&symbol_id, "call")? {
+        if let Some(callee) = edge.to_sym.as_deref().map(|id| db.get_symbol(id)).transpose()?.flatten() {
+            println!("{}", display_name(&callee));
+        }
+    }
+    Ok(())
+}
+
+/// List where a symbol is used, clang-style: `file:line:col usage_kind`.
+pub fn usages(db_path: &str, usr: &str, kind: Option<&str>) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    db.find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let usages: Vec<_> = db
+        .find_symbol_usages(usr)?
+        .into_iter()
+        .filter(|u| kind.is_none_or(|kind| u.usage_kind == kind))
+        .collect();
+    if usages.is_empty() {
+        println!("No usages found");
+    }
+    for usage in usages {
+        println!("{}:{}:{} {}", usage.file_path, usage.line, usage.col, usage.usage_kind);
+    }
+    Ok(())
+}
+
+/// Query callers of a function.
+pub fn query_callers(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    for edge in db.edges_to(&symbol_id, "call")? {
+        if let Some(caller) = edge.from_sym.as_deref().map(|id| db.get_symbol(id)).transpose()?.flatten() {
+            println!("{}", display_name(&caller));
+        }
+    }
+    Ok(())
+}
+
+/// Readable name of a symbol: its decoded USR, or the plain name without one
+fn display_name(symbol: &symgraph_core::Symbol) -> String {
+    match &symbol.usr {
+        Some(usr) => symgraph_core::usr_to_display_name(usr),
+        None => symbol.name.clone(),
+    }
+}
+
+/// Show the inheritance hierarchy of a class.
+pub fn query_inheritance(db_path: &str, usr: &str, direction: symgraph_core::InheritDirection) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let nodes = db.query_inheritance_tree(usr, direction)?;
+
+    if nodes.is_empty() {
+        return Err(anyhow::anyhow!("Symbol not found: {}", usr));
+    }
+    for node in nodes {
+        println!("{}{}", "  ".repeat(node.depth as usize), node.name);
+    }
+
+    Ok(())
+}
+
+/// Render the symbols nested in `root_usr` as `name (kind)` lines, indented by nesting level.
+fn render_symbol_tree(db: &symgraph_core::Db, root_usr: &str, depth: usize) -> Result<String> {
+    use std::fmt::Write;
+
+    fn walk(db: &symgraph_core::Db, usr: &str, level: usize, depth: usize, out: &mut String) -> Result<()> {
+        if level > depth {
+            return Ok(());
+        }
+        for (id, name, kind) in db.query_symbol_children(usr)? {
+            writeln!(out, "{}{} ({})", "  ".repeat(level), name, kind)?;
+            if let Some(child_usr) = db.get_symbol(&id)?.and_then(|symbol| symbol.usr) {
+                walk(db, &child_usr, level + 1, depth, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    let root_id = db
+        .find_symbol_by_usr(root_usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", root_usr))?;
+    let root = db
+        .get_symbol(&root_id)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", root_usr))?;
+
+    let mut out = String::new();
+    writeln!(out, "{} ({})", root.name, root.kind)?;
+    walk(db, root_usr, 1, depth, &mut out)?;
+    Ok(out)
+}
+
+/// Print the symbol tree below a namespace or class.
+pub fn symbol_tree(db_path: &str, root_usr: &str, depth: usize) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    print!("{}", render_symbol_tree(&db, root_usr, depth)?);
+    Ok(())
+}
+
+/// Print the symbols with the highest fan-in or fan-out.
+pub fn coupling_metrics(db_path: &str, sort_by: crate::modules::commands::cli::MetricSortArg, top: usize) -> Result<()> {
+    use crate::modules::commands::cli::MetricSortArg;
+
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut metrics = db.query_symbol_metrics()?;
+    match sort_by {
+        MetricSortArg::FanIn => metrics.sort_by(|a, b| b.fan_in.cmp(&a.fan_in).then(b.fan_out.cmp(&a.fan_out))),
+        MetricSortArg::FanOut => metrics.sort_by(|a, b| b.fan_out.cmp(&a.fan_out).then(b.fan_in.cmp(&a.fan_in))),
+    }
+
+    println!("{:<40} {:<20} {:>7} {:>7}", "SYMBOL", "KIND", "FAN-IN", "FAN-OUT");
+    for m in metrics.iter().take(top) {
+        println!("{:<40} {:<20} {:>7} {:>7}", m.name, m.kind, m.fan_in, m.fan_out);
+    }
+
+    Ok(())
+}
+
+/// Print the `top` symbols with the highest PageRank over edges of `kind`.
+pub fn pagerank(db_path: &str, kind: &str, top: usize) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let ranked = db.compute_pagerank(kind)?;
+
+    println!("{:<40} {:<20} {:>10}", "SYMBOL", "KIND", "SCORE");
+    for (symbol_id, score) in ranked.iter().take(top) {
+        if let Some(symbol) = db.get_symbol(symbol_id)? {
+            println!("{:<40} {:<20} {:>10.6}", symbol.name, symbol.kind, score);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the `top` highest-weight paths from `start`, one `weight<TAB>a -> b -> c` per line.
+pub fn hottest_paths(db_path: &str, start: &str, kind: &str, top: usize) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    db.find_symbol_by_usr(start)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", start))?;
+
+    let paths = db.hottest_paths(start, kind, top)?;
+    if paths.is_empty() {
+        println!("No paths found");
+    }
+    for path in paths {
+        let mut names = Vec::with_capacity(path.symbols.len());
+        for symbol_id in &path.symbols {
+            names.push(db.get_symbol(symbol_id)?.map_or_else(|| symbol_id.clone(), |s| s.name));
+        }
+        println!("{}\t{}", path.weight, names.join(" -> "));
+    }
+    Ok(())
+}
+
+/// List the places where a C++20 concept is used.
+pub fn query_concept_uses(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let mut uses: Vec<_> = db
+        .occurrences_of(&symbol_id)?
+        .into_iter()
+        .filter(|o| o.usage_kind == "concept_use")
+        .collect();
+    uses.sort_by(|a, b| (&a.file_id, a.line, a.column).cmp(&(&b.file_id, b.line, b.column)));
+
+    for occurrence in uses {
+        let path = db
+            .get_file(&occurrence.file_id)?
+            .map(|f| f.path)
+            .unwrap_or(occurrence.file_id);
+        println!("{}:{}:{}", path, occurrence.line, occurrence.column);
+    }
+
+    Ok(())
+}
+
+/// List coroutine functions with the file that defines them.
+pub fn query_coroutines(db_path: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut rows = Vec::new();
+    for symbol in db.symbols_with_metadata("is_coroutine", "true")? {
+        let path = db.get_file(&symbol.file_id)?.map(|f| f.path).unwrap_or(symbol.file_id);
+        rows.push((path, symbol.name));
+    }
+    rows.sort();
+
+    if rows.is_empty() {
+        println!("No coroutines found");
+    }
+    for (path, name) in rows {
+        println!("{}\t{}", path, name);
+    }
+
+    Ok(())
+}
+
+/// Attach `tag` to the symbol with `usr`, or remove it.
+pub fn tag_symbol(db_path: &str, usr: &str, tag: &str, remove: bool) -> Result<()> {
+    let mut db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    if remove {
+        db.untag_symbol(&symbol_id, tag)?;
+        println!("Removed tag '{}' from {}", tag, usr);
+    } else {
+        db.tag_symbol(&symbol_id, tag)?;
+        println!("Tagged {} as '{}'", usr, tag);
+    }
+    Ok(())
+}
+
+/// Record `alias` as a former name of the symbol with `usr`.
+pub fn add_alias(db_path: &str, usr: &str, alias: &str) -> Result<()> {
+    let mut db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    db.add_alias(&symbol_id, alias)?;
+    println!("Added alias '{}' for {}", alias, usr);
+    Ok(())
+}
+
+/// Print the documentation comment stored for the symbol with `usr`.
+pub fn symbol_doc(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let symbol_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    match db.get_symbol_doc(&symbol_id)? {
+        Some(doc) => println!("{}", doc),
+        None => println!("No documentation for {}", usr),
+    }
+    Ok(())
+}
+
+/// List the symbols carrying `tag`, one `usr<TAB>name` per line.
+pub fn find_tagged(db_path: &str, tag: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut rows = Vec::new();
+    for symbol_id in db.find_symbols_by_tag(tag)? {
+        if let Some(symbol) = db.get_symbol(&symbol_id)? {
+            rows.push((symbol.usr.unwrap_or(symbol.id), symbol.name));
+        }
+    }
+    rows.sort();
+
+    if rows.is_empty() {
+        println!("No symbols tagged '{}'", tag);
+    }
+    for (usr, name) in rows {
+        println!("{}\t{}", usr, name);
+    }
+    Ok(())
+}
+
+/// List `[[nodiscard]]` functions whose result is ignored, one `file:line<TAB>name` per call site.
+pub fn query_nodiscard(db_path: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut rows = Vec::new();
+    for symbol in db.symbols_with_metadata("nodiscard", "true")? {
+        for occurrence in db.occurrences_of(&symbol.id)? {
+            if occurrence.usage_kind != "discarded_result" {
+                continue;
+            }
+            let path = db
+                .get_file(&occurrence.file_id)?
+                .map(|f| f.path)
+                .unwrap_or(occurrence.file_id);
+            rows.push((path, occurrence.line, symbol.name.clone()));
+        }
+    }
+    rows.sort();
+    rows.dedup();
+
+    if rows.is_empty() {
+        println!("No discarded [[nodiscard]] results found");
+    }
+    for (path, line, name) in rows {
+        println!("{}:{}\t{}", path, line, name);
+    }
+    Ok(())
+}
+
+/// List the names of symbols that are declared but never defined.
+pub fn undefined_symbols(db_path: &str, lang: Option<&str>) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let names = db.find_undefined_symbols_in_lang(lang)?;
+
+    if names.is_empty() {
+        println!("No undefined symbols found");
+    }
+    for name in names {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// Full-text search on symbol names, one `name<TAB>kind<TAB>file` per line.
+pub fn search(db_path: &str, query: &str, limit: usize) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let results = db.fts_search_symbols(query)?;
+
+    if results.is_empty() {
+        println!("No symbols match '{}'", query);
+    }
+    for result in results.into_iter().take(limit) {
+        println!("{}\t{}\t{}", result.name, result.kind, result.file_path.unwrap_or_default());
+    }
+    Ok(())
+}
+
+/// Print the members and base classes of the class with `usr`.
+pub fn query_members(db_path: &str, usr: &str, kind: crate::modules::commands::cli::MembersKindArg) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    print!("{}", render_members(&db, usr, kind)?);
+    Ok(())
+}
+
+/// `Fields: ...`, `Methods: ...` and `Inherits: ...` lines for a class
+fn render_members(db: &symgraph_core::Db, usr: &str, kind: crate::modules::commands::cli::MembersKindArg) -> Result<String> {
+    use crate::modules::commands::cli::MembersKindArg;
+
+    let class_id = db
+        .find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let mut fields = Vec::new();
+    let mut methods = Vec::new();
+    for edge in db.edges_from(&class_id, "member")? {
+        if let Some(member) = edge.to_sym.as_deref().map(|id| db.get_symbol(id)).transpose()?.flatten() {
+            let is_method = member.kind.contains("Method")
+                || matches!(member.kind.as_str(), "Constructor" | "Destructor" | "FunctionDecl" | "FunctionTemplate");
+            if is_method {
+                methods.push(member.name);
+            } else {
+                fields.push(member.name);
+            }
+        }
+    }
+
+    // Inherit edges point from the base class to the derived class
+    let mut bases = Vec::new();
+    for edge in db.edges_to(&class_id, "inherit")? {
+        if let Some(base) = edge.from_sym.as_deref().map(|id| db.get_symbol(id)).transpose()?.flatten() {
+            bases.push(base.name);
+        }
+    }
+
+    let mut output = String::new();
+    let sections = [
+        ("Fields", fields, kind != MembersKindArg::Methods),
+        ("Methods", methods, kind != MembersKindArg::Fields),
+        ("Inherits", bases, kind == MembersKindArg::All),
+    ];
+    for (title, mut names, shown) in sections {
+        if shown && !names.is_empty() {
+            names.sort();
+            output.push_str(&format!("{}: {}\n", title, names.join(", ")));
+        }
+    }
+    Ok(output)
+}
+
+/// List async functions, one `file<TAB>name` per line.
+pub fn query_async(db_path: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut rows = Vec::new();
+    for symbol in db.symbols_with_metadata("is_async", "true")? {
+        let path = db.get_file(&symbol.file_id)?.map(|f| f.path).unwrap_or(symbol.file_id);
+        rows.push((path, symbol.name));
+    }
+    rows.sort();
+
+    if rows.is_empty() {
+        println!("No async functions found");
+    }
+    for (path, name) in rows {
+        println!("{}\t{}", path, name);
+    }
+
+    Ok(())
+}
+
+/// List files with unsafe Rust code, one `blocks<TAB>unsafe_fns<TAB>file` per line, most `unsafe` blocks first.
+pub fn unsafe_report(db_path: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for symbol in db.symbols_with_metadata_key("unsafe_blocks")? {
+        let blocks: usize = symbol.metadata["unsafe_blocks"].parse().unwrap_or(0);
+        counts.entry(symbol.file_id).or_default().0 += blocks;
+    }
+    for symbol in db.symbols_with_metadata("is_unsafe", "true")? {
+        counts.entry(symbol.file_id).or_default().1 += 1;
+    }
+
+    let mut rows = Vec::new();
+    for (file_id, (blocks, unsafe_fns)) in counts {
+        let path = db.get_file(&file_id)?.map(|f| f.path).unwrap_or(file_id);
+        rows.push((blocks, unsafe_fns, path));
+    }
+    rows.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)).then_with(|| a.2.cmp(&b.2)));
+
+    if rows.is_empty() {
+        println!("No unsafe code found");
+    }
+    for (blocks, unsafe_fns, path) in rows {
+        println!("{}\t{}\t{}", blocks, unsafe_fns, path);
+    }
+
+    Ok(())
+}
+
+/// List the known instantiations of a template, one `name<TAB>arguments` per line.
+pub fn query_instantiations(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    db.find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let mut rows: Vec<_> = db
+        .query_instantiations(usr)?
+        .into_iter()
+        .map(|symbol| (symbol.name, symbol.template_args.unwrap_or_default()))
+        .collect();
+    rows.sort();
+    rows.dedup();
+
+    if rows.is_empty() {
+        println!("No instantiations found");
+    }
+    for (name, args) in rows {
+        println!("{}\t{}", name, args);
+    }
+    Ok(())
+}
+
+/// List the functions and classes declared in a namespace, one name per line.
+pub fn query_namespace(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    db.find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let members = db.query_namespace_members(usr)?;
+    if members.is_empty() {
+        println!("No namespace members found");
+    }
+    for name in members {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// List the constructors of a class, one signature per line.
+pub fn query_constructors(db_path: &str, usr: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    db.find_symbol_by_usr(usr)?
+        .ok_or_else(|| anyhow::anyhow!("Symbol not found: {}", usr))?;
+
+    let constructors = db.query_constructors(usr)?;
+    if constructors.is_empty() {
+        println!("No constructors found");
+    }
+    for name in constructors {
+        println!("{}", name);
+    }
     Ok(())
 }
 
-/// Находит файл с указанным расширением в директории
-fn find_file_with_ext(dir: &Path, ext: &str) -> Result<std::path::PathBuf> {
-    std::fs::read_dir(dir)
-        .map_err(|e| anyhow::anyhow!("Failed to read directory '{}': {}", dir.display(), e))?;
-    
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(extension) = path.extension() {
-                if extension == ext {
-                    return Ok(path);
-                }
-            }
+/// List the partitions of a C++20 module.
+pub fn query_partitions(db_path: &str, module: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let partitions = db.query_module_partitions(module)?;
+    if partitions.is_empty() {
+        println!("No partitions found for module {}", module);
+    }
+    for name in partitions {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// List symbols defined in a file.
+pub fn symbols_in_file(db_path: &str, file: &str, feature_flags: &[String]) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let mut rows = Vec::new();
+    for (id, name, kind, _) in db.find_symbols_in_file(file)? {
+        let cfg = db.get_symbol(&id)?.and_then(|s| s.cfg);
+        if feature_flags.is_empty() || cfg.as_ref().is_some_and(|f| feature_flags.contains(f)) {
+            rows.push((name, kind, cfg));
         }
     }
-    anyhow::bail!("No .{} file found in {}", ext, dir.display());
+
+    if rows.is_empty() {
+        println!("No symbols found in {}", file);
+    }
+    for (name, kind, cfg) in rows {
+        match cfg {
+            Some(feature) => println!("{}\t{}\t[feature = \"{}\"]", kind, name, feature),
+            None => println!("{}\t{}", kind, name),
+        }
+    }
+
+    Ok(())
 }
 
-/// Query call graph for a symbol.
-pub fn query_calls(db_path: &str, usr: &str) -> Result<()> {
+/// List macros defined in a file.
+pub fn query_macros(db_path: &str, file: &str) -> Result<()> {
     let db = symgraph_core::Db::open(db_path)?;
 
-    // Query edges where kind="call" and from_sym matches the USR
-    let rows = db.query_edges_by_kind_from("call", usr)?;
+    // libclang records absolute paths, so retry with the canonical path
+    let record = match db.find_file_by_path(file)? {
+        Some(record) => Some(record),
+        None => match std::fs::canonicalize(file) {
+            Ok(path) => db.find_file_by_path(&path.to_string_lossy())?,
+            Err(_) => None,
+        },
+    };
+    let record = record.ok_or_else(|| anyhow::anyhow!("File not found in database: {}", file))?;
+
+    let mut macros: Vec<_> = db
+        .symbols_by_kind("MacroDefinition")?
+        .into_iter()
+        .filter(|s| s.file_id == record.id)
+        .collect();
+    macros.sort_by(|a, b| a.name.cmp(&b.name));
 
-    // Print each callee name
-    for r in rows {
-        println!("{}", r);
+    if macros.is_empty() {
+        println!("No macros found in {}", record.path);
+    }
+    for m in macros {
+        println!("{}", m.name);
     }
+
     Ok(())
 }
 
@@ -288,83 +939,341 @@ pub fn list_modules(db_path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Show database statistics.
-pub fn show_stats(db_path: &str) -> Result<()> {
-    let db = symgraph_core::Db::open(db_path)?;
-
-    let file_count = db.db.scan_prefix("file:").count();
-    let symbol_count = db.db.scan_prefix("symbol:").count();
-    let occurrence_count = db.db.scan_prefix("occurrence:").count();
-    let edge_count = db.db.scan_prefix("edge:").count();
-    let module_count = db.db.scan_prefix("module:").count();
-
-    println!("=== Database Statistics ===");
-    println!("Files:       {}", file_count);
-    println!("Symbols:     {}", symbol_count);
-    println!("Occurrences: {}", occurrence_count);
-    println!("Edges:       {}", edge_count);
-    println!("Modules:     {}", module_count);
-
-    // Symbol breakdown
-    println!("\n=== Symbol Types ===");
-    let mut symbol_types: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-    for item in db.db.scan_prefix("symbol:") {
+/// Import an LSIF dump into the database.
+pub fn import_lsif(db_path: &str, lsif_path: &str, project_name: &str, skip_missing_usrs: bool) -> Result<()> {
+    let mut db = symgraph_core::Db::open(db_path)?;
+    let stats = symgraph_core::import_lsif_file(&mut db, Path::new(lsif_path), project_name, skip_missing_usrs)?;
+
+    println!(
+        "Imported {} documents: {} definitions, {} references",
+        stats.documents, stats.definitions, stats.references
+    );
+    if stats.skipped_references > 0 {
+        println!("Skipped {} references with unknown USRs", stats.skipped_references);
+    }
+
+    Ok(())
+}
+
+/// Import the crate dependency graph of a `Cargo.lock`.
+pub fn import_cargo_lock(db_path: &str, lock_path: &str) -> Result<()> {
+    let mut db = symgraph_core::Db::open(db_path)?;
+    let packages = symgraph_discovery::extract_cargo_lock_graph(Path::new(lock_path))?;
+    let edges = store_cargo_graph(&mut db, &packages)?;
+    println!("Imported {} crates with {} dependencies", packages.len(), edges);
+    Ok(())
+}
+
+/// Store crates as `"cargo-crate"` modules linked by `"cargo-dep"` edges.
+///
+/// Versions of the same crate share one module, so an edge is stored once per
+/// pair of crate names. Returns the number of edges written.
+fn store_cargo_graph(db: &mut symgraph_core::Db, packages: &[symgraph_discovery::CargoDep]) -> Result<usize> {
+    let mut module_ids = std::collections::HashMap::new();
+    for package in packages {
+        if !module_ids.contains_key(&package.name) {
+            let id = symgraph_core::upsert_module(db, &package.name, "cargo-crate", "")?;
+            module_ids.insert(package.name.clone(), id);
+        }
+    }
+
+    let edge_kinds = symgraph_core::EdgeKindRegistry::new();
+    let mut stored = std::collections::HashSet::new();
+    for package in packages {
+        for dep in &package.dependencies {
+            let Some(dep_id) = module_ids.get(dep) else {
+                continue;
+            };
+            let from_id = &module_ids[&package.name];
+            if stored.insert((from_id.clone(), dep_id.clone())) {
+                symgraph_core::insert_edge(db, &edge_kinds, None, None, Some(from_id), Some(dep_id), "cargo-dep")?;
+            }
+        }
+    }
+    Ok(stored.len())
+}
+
+/// Compare two databases and report added/removed symbols and edges.
+pub fn diff_databases(before_path: &str, after_path: &str, kind: Option<&str>) -> Result<()> {
+    let before = symgraph_core::Db::open(before_path)?;
+    let after = symgraph_core::Db::open(after_path)?;
+    let diff = symgraph_core::diff_databases(&before, &after, kind)?;
+
+    println!("--- {}", before_path);
+    println!("+++ {}", after_path);
+    for name in &diff.removed_symbols {
+        println!("- {}", name);
+    }
+    for name in &diff.added_symbols {
+        println!("+ {}", name);
+    }
+    println!(
+        "symbols: +{} -{}, edges: +{} -{}",
+        diff.added_symbols.len(),
+        diff.removed_symbols.len(),
+        diff.added_edges,
+        diff.removed_edges
+    );
+
+    Ok(())
+}
+
+/// Print modules in build order based on module imports.
+pub fn topo_sort(db_path: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let order = db.topological_sort_modules()?;
+
+    if order.is_empty() {
+        println!("No modules found.");
+    }
+    for (i, name) in order.iter().enumerate() {
+        println!("{}. {}", i + 1, name);
+    }
+
+    Ok(())
+}
+
+/// Counts of database records, as printed by `show-stats`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsOutput {
+    pub files: usize,
+    pub symbols: usize,
+    pub occurrences: usize,
+    pub edges: usize,
+    pub modules: usize,
+    pub edge_types: BTreeMap<String, usize>,
+    pub symbol_types: BTreeMap<String, usize>,
+    /// Files per language
+    #[serde(default)]
+    pub languages: BTreeMap<String, usize>,
+}
+
+/// Count the records of a database by kind
+fn collect_stats(db: &symgraph_core::Db) -> Result<StatsOutput> {
+    let counts = |distribution: Vec<(String, i64)>| -> BTreeMap<String, usize> {
+        distribution.into_iter().map(|(name, count)| (name, count as usize)).collect()
+    };
+    let symbol_types = counts(db.symbol_kind_distribution()?);
+    let languages = counts(db.file_lang_distribution()?);
+    let mut edge_types = BTreeMap::new();
+    for item in db.db.scan_prefix("edge:") {
         let (_, value): (_, sled::IVec) = item?;
-        if let Ok(symbol) = serde_json::from_slice::<symgraph_core::Symbol>(&value) {
-            *symbol_types.entry(symbol.kind).or_insert(0) += 1;
+        if let Ok(edge) = serde_json::from_slice::<symgraph_core::Edge>(&value) {
+            *edge_types.entry(edge.kind).or_insert(0) += 1;
         }
     }
-    
-    let mut sorted_types: Vec<_> = symbol_types.iter().collect();
-    sorted_types.sort_by(|a, b| b.1.cmp(a.1));
-    
-    for (kind, count) in sorted_types.iter().take(10) {
-        println!("  {}: {}", kind, count);
+
+    Ok(StatsOutput {
+        files: db.db.scan_prefix("file:").count(),
+        symbols: db.db.scan_prefix("symbol:").count(),
+        occurrences: db.db.scan_prefix("occurrence:").count(),
+        edges: db.db.scan_prefix("edge:").count(),
+        modules: db.db.scan_prefix("module:").count(),
+        edge_types,
+        symbol_types,
+        languages,
+    })
+}
+
+/// Render statistics in the requested format
+fn render_stats(stats: &StatsOutput, format: crate::modules::commands::cli::StatsFormatArg) -> Result<String> {
+    use crate::modules::commands::cli::StatsFormatArg;
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    match format {
+        StatsFormatArg::Json => writeln!(out, "{}", serde_json::to_string_pretty(stats)?)?,
+        StatsFormatArg::Csv => {
+            let totals = [
+                ("files", stats.files),
+                ("symbols", stats.symbols),
+                ("occurrences", stats.occurrences),
+                ("edges", stats.edges),
+                ("modules", stats.modules),
+            ];
+            for (name, count) in totals {
+                writeln!(out, "{},{}", name, count)?;
+            }
+            for (kind, count) in &stats.edge_types {
+                writeln!(out, "edge_types.{},{}", kind, count)?;
+            }
+            for (kind, count) in &stats.symbol_types {
+                writeln!(out, "symbol_types.{},{}", kind, count)?;
+            }
+            for (lang, count) in &stats.languages {
+                writeln!(out, "languages.{},{}", lang, count)?;
+            }
+        }
+        StatsFormatArg::Table => {
+            writeln!(out, "=== Database Statistics ===")?;
+            writeln!(out, "Files:       {}", stats.files)?;
+            writeln!(out, "Symbols:     {}", stats.symbols)?;
+            writeln!(out, "Occurrences: {}", stats.occurrences)?;
+            writeln!(out, "Edges:       {}", stats.edges)?;
+            writeln!(out, "Modules:     {}", stats.modules)?;
+
+            writeln!(out, "\n=== Edge Types ===")?;
+            for (kind, count) in &stats.edge_types {
+                writeln!(out, "  {}: {}", kind, count)?;
+            }
+
+            // Symbol breakdown
+            writeln!(out, "\n=== Symbol Types ===")?;
+            let mut sorted_types: Vec<_> = stats.symbol_types.iter().collect();
+            sorted_types.sort_by(|a, b| b.1.cmp(a.1));
+            for (kind, count) in sorted_types {
+                writeln!(out, "  {}: {}", kind, count)?;
+            }
+
+            writeln!(out, "\n=== Languages ===")?;
+            for (lang, count) in &stats.languages {
+                writeln!(out, "  {}: {}", lang, count)?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Show database statistics.
+pub fn show_stats(db_path: &str, format: crate::modules::commands::cli::StatsFormatArg) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let stats = collect_stats(&db)?;
+    print!("{}", render_stats(&stats, format)?);
+    Ok(())
+}
+
+/// Files matching the optional language and category filters, sorted by path
+fn filter_files(
+    db: &symgraph_core::SymgraphDb,
+    lang: Option<&str>,
+    category: Option<&str>,
+) -> Result<Vec<symgraph_core::database::FileInfo>> {
+    let mut files: Vec<_> = db
+        .list_files()?
+        .into_iter()
+        .filter(|f| lang.is_none_or(|lang| f.language == lang))
+        .filter(|f| category.is_none_or(|category| f.category == category))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Render a file list in the requested format
+fn render_file_list(
+    files: &[symgraph_core::database::FileInfo],
+    format: crate::modules::commands::cli::ListFilesFormatArg,
+) -> Result<String> {
+    use crate::modules::commands::cli::ListFilesFormatArg;
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    match format {
+        ListFilesFormatArg::Json => writeln!(out, "{}", serde_json::to_string_pretty(files)?)?,
+        ListFilesFormatArg::NullSeparated => {
+            for file in files {
+                write!(out, "{}\0", file.path)?;
+            }
+        }
+        ListFilesFormatArg::Table => {
+            let path_width = files.iter().map(|f| f.path.len()).max().unwrap_or(0).max("PATH".len());
+            let lang_width = files.iter().map(|f| f.language.len()).max().unwrap_or(0).max("LANGUAGE".len());
+            writeln!(out, "{:<path_width$}  {:<lang_width$}  CATEGORY", "PATH", "LANGUAGE")?;
+            for file in files {
+                let row = format!("{:<path_width$}  {:<lang_width$}  {}", file.path, file.language, file.category);
+                writeln!(out, "{}", row.trim_end())?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// List the files stored in the database, optionally filtered by language and category.
+pub fn list_files(
+    db_path: &str,
+    lang: Option<&str>,
+    category: Option<&str>,
+    format: crate::modules::commands::cli::ListFilesFormatArg,
+    count_only: bool,
+) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let files = filter_files(&db, lang, category)?;
+    if count_only {
+        println!("{}", files.len());
+    } else {
+        print!("{}", render_file_list(&files, format)?);
+    }
+    Ok(())
+}
+
+/// Compact the database, printing its size before and after.
+pub fn maintain(db_path: &str, vacuum: bool) -> Result<()> {
+    if !vacuum {
+        anyhow::bail!("Nothing to do: pass --vacuum");
     }
+    let stats = symgraph_core::vacuum_database(db_path)?;
+    println!("Before: {} bytes, after: {} bytes", stats.before, stats.after);
+    Ok(())
+}
 
+/// Replace the path prefix of stored projects, files and modules.
+pub fn remap_paths(db_path: &str, from: &str, to: &str) -> Result<()> {
+    let mut db = symgraph_core::Db::open(db_path)?;
+    let updated = db.remap_paths(from, to)?;
+    println!("Remapped {} paths from {} to {}", updated, from, to);
     Ok(())
 }
 
 /// Generate project annotation for compiled languages (C++/Rust).
-pub fn annotate_compiled_project(root: &str, db_path: &str) -> Result<()> {
-    use symgraph_core::annotations::{analyze_cpp_project, analyze_rust_project};
+///
+/// With `from_db` the annotation is derived from the stored symbol graph instead of
+/// the files on disk.
+pub fn annotate_compiled_project(root: &str, db_path: &str, from_db: bool) -> Result<()> {
+    use symgraph_core::annotations::{analyze_cpp_project, analyze_rust_project, annotate_from_db};
     
     let mut db = symgraph_core::Db::open(db_path)?;
     
-    // Get files from database with categories
-    let files: Vec<(String, String, String)> = {
-        let mut files = Vec::new();
-        for item in db.db.scan_prefix("file:") {
-            let (_, value): (_, sled::IVec) = item?;
-            if let Ok(file) = serde_json::from_slice::<symgraph_core::File>(&value) {
-                files.push((
-                    file.path,
-                    file.category.unwrap_or("unknown".to_string()),
-                    file.purpose.unwrap_or("".to_string())
-                ));
-            }
+    let annotation = if from_db {
+        let mut annotation = annotate_from_db(&db)?;
+        if annotation.root_path.is_empty() {
+            annotation.root_path = root.to_string();
         }
-        files
-    };
+        annotation
+    } else {
+        // Get files from database with categories
+        let files: Vec<(String, String, String)> = {
+            let mut files = Vec::new();
+            for item in db.db.scan_prefix("file:") {
+                let (_, value): (_, sled::IVec) = item?;
+                if let Ok(file) = serde_json::from_slice::<symgraph_core::File>(&value) {
+                    files.push((
+                        file.path,
+                        file.category.unwrap_or("unknown".to_string()),
+                        file.purpose.unwrap_or("".to_string())
+                    ));
+                }
+            }
+            files
+        };
     
-    if files.is_empty() {
-        println!("No files found in database. Run scan-cxx or scan-rust first.");
-        return Ok(());
-    }
+        if files.is_empty() {
+            println!("No files found in database. Run scan-cxx or scan-rust first.");
+            return Ok(());
+        }
     
-    // Detect language from file extensions
-    let is_cpp = files.iter().any(|(path, _, _): &(String, String, String)| {
-        path.ends_with(".cpp") || path.ends_with(".cc") || path.ends_with(".cxx") || path.ends_with(".h")
-    });
-    let is_rust = files.iter().any(|(path, _, _): &(String, String, String)| path.ends_with(".rs"));
+        // Detect language from file extensions
+        let is_cpp = files.iter().any(|(path, _, _): &(String, String, String)| {
+            path.ends_with(".cpp") || path.ends_with(".cc") || path.ends_with(".cxx") || path.ends_with(".h")
+        });
+        let is_rust = files.iter().any(|(path, _, _): &(String, String, String)| path.ends_with(".rs"));
     
-    let annotation = if is_cpp {
-        analyze_cpp_project(root, &files)?
-    } else if is_rust {
-        analyze_rust_project(root, &files)?
-    } else {
-        println!("Unknown project type. Supported: C++, Rust");
-        return Ok(());
+        if is_cpp {
+            analyze_cpp_project(root, &files)?
+        } else if is_rust {
+            analyze_rust_project(root, &files)?
+        } else {
+            println!("Unknown project type. Supported: C++, Rust");
+            return Ok(());
+        }
     };
     
     // Update database
@@ -394,6 +1303,21 @@ pub fn annotate_compiled_project(root: &str, db_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Print the number of source files of each language found in `project`.
+pub fn detect_languages(project: &str) -> Result<()> {
+    let languages = symgraph_discovery::detect_all_languages(std::path::Path::new(project));
+    if languages.is_empty() {
+        println!("No source files found in {}", project);
+        return Ok(());
+    }
+
+    println!("{:<12} {:>7}", "LANGUAGE", "FILES");
+    for (language, count) in languages {
+        println!("{:<12} {:>7}", language.to_string(), count);
+    }
+    Ok(())
+}
+
 /// Analyze script projects (Python, JavaScript, TypeScript) using SCIP.
 pub fn scan_scripts(root: &str, db_path: &str) -> Result<()> {
     use anyhow::bail;
@@ -481,6 +1405,177 @@ pub fn scan_scip(root: &str, db_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Index a TypeScript project with `scip-typescript` and load the result.
+///
+/// With `dry_run` the index is generated and parsed, but the database is not
+/// opened.
+pub fn import_scip_typescript(root: &str, db_path: &str, tsconfig: Option<&str>, dry_run: bool) -> Result<()> {
+    use symgraph_discovery::{ScipConfig, ScipLanguage};
+
+    let project_path = Path::new(root);
+    let mut config = ScipConfig::new(ScipLanguage::TypeScript, project_path, project_path.join(".scip"));
+    if let Some(tsconfig) = tsconfig {
+        config = config.with_extra_args(vec![tsconfig.to_string()]);
+    }
+    let scip_file_path = generate_scip_index(&config)?;
+
+    let mut db = if dry_run { None } else { Some(symgraph_core::Db::open(db_path)?) };
+    import_scip_index(&scip_file_path, db.as_mut(), &format!("{}_project", ScipLanguage::TypeScript))
+}
+
+/// Parse a SCIP index and load it into `db`; only report its size without one.
+fn import_scip_index(scip_path: &Path, db: Option<&mut symgraph_core::Db>, project_name: &str) -> Result<()> {
+    let scip_data = parse_scip_file(scip_path)?;
+    println!("SCIP index parsed:");
+    println!("  Documents: {}", scip_data.documents.len());
+    println!("  Symbols: {}", scip_data.symbols.len());
+    println!("  Occurrences: {}", scip_data.occurrences.len());
+
+    match db {
+        Some(db) => {
+            symgraph_core::scip::load_scip_to_database(db, &scip_data, project_name)?;
+            println!("SCIP data loaded into database successfully.");
+        }
+        None => println!("Dry run: database not modified."),
+    }
+    Ok(())
+}
+
+/// Export a graph of one edge kind as a Mermaid flowchart.
+pub fn export_mermaid(
+    db_path: &str,
+    kind: &str,
+    root: Option<&str>,
+    depth: u8,
+    output: Option<&str>,
+) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)
+                .map_err(|e| anyhow::anyhow!("Failed to create output file '{}': {}", path, e))?;
+            symgraph_core::export_mermaid(&db, kind, root, depth, &mut file)?;
+            println!("Mermaid diagram written to {}", path);
+        }
+        None => {
+            let stdout = std::io::stdout();
+            symgraph_core::export_mermaid(&db, kind, root, depth, &mut stdout.lock())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Export classes, members and inheritance as a PlantUML class diagram.
+pub fn export_plantuml(db_path: &str, output: Option<&str>) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)
+                .map_err(|e| anyhow::anyhow!("Failed to create output file '{}': {}", path, e))?;
+            symgraph_core::export_plantuml(&db, &mut file)?;
+            println!("PlantUML diagram written to {}", path);
+        }
+        None => {
+            let stdout = std::io::stdout();
+            symgraph_core::export_plantuml(&db, &mut stdout.lock())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Export the call chain below `start` as a PlantUML sequence diagram.
+pub fn export_sequence(db_path: &str, start: &str, depth: u8, output: Option<&str>) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)
+                .map_err(|e| anyhow::anyhow!("Failed to create output file '{}': {}", path, e))?;
+            symgraph_core::export_sequence(&db, start, depth, &mut file)?;
+            println!("PlantUML sequence diagram written to {}", path);
+        }
+        None => {
+            let stdout = std::io::stdout();
+            symgraph_core::export_sequence(&db, start, depth, &mut stdout.lock())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Export the whole database as a JSON snapshot.
+pub fn export_json(db_path: &str, output: Option<&str>) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+
+    match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .map_err(|e| anyhow::anyhow!("Failed to create output file '{}': {}", path, e))?;
+            let mut writer = std::io::BufWriter::new(file);
+            db.export_json(&mut writer)?;
+            writer.flush()?;
+            println!("JSON snapshot written to {}", path);
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut writer = std::io::BufWriter::new(stdout.lock());
+            db.export_json(&mut writer)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `table` as CSV to `output`, optionally behind a UTF-8 byte order mark.
+fn write_csv(
+    db: &symgraph_core::Db,
+    table: symgraph_core::CsvTable,
+    output: &str,
+    filter: symgraph_core::CsvFilter,
+    bom: bool,
+) -> Result<usize> {
+    let file = std::fs::File::create(output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file '{}': {}", output, e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    if bom {
+        writer.write_all("\u{FEFF}".as_bytes())?;
+    }
+    let count = db.export_csv(table, filter, &mut writer)?;
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Export one table of the database as CSV.
+pub fn export_csv(
+    db_path: &str,
+    table: crate::modules::commands::cli::CsvTableArg,
+    output: &str,
+    filter_lang: Option<&str>,
+    filter_kind: Option<&str>,
+    bom: bool,
+) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    let filter = symgraph_core::CsvFilter { lang: filter_lang, kind: filter_kind };
+    let count = write_csv(&db, table.into(), output, filter, bom)?;
+    println!("{} rows written to {}", count, output);
+    Ok(())
+}
+
+/// Export the database as a cscope cross-reference in `output_dir/cscope.out`.
+pub fn export_cscope(db_path: &str, output_dir: &str) -> Result<()> {
+    let db = symgraph_core::Db::open(db_path)?;
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create output directory '{}': {}", output_dir, e))?;
+    symgraph_core::export_cscope(&db, Path::new(output_dir))?;
+    println!("cscope database written to {}", Path::new(output_dir).join("cscope.out").display());
+    Ok(())
+}
+
 /// Start web viewer for database.
 pub fn start_web_viewer(db_path: &str) -> Result<()> {
     use std::process::Command;
@@ -825,3 +1920,214 @@ fn determine_symbol_type(symbol: &str) -> &str {
         "symbol"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::commands::cli::{ListFilesFormatArg, MembersKindArg, StatsFormatArg};
+    use symgraph_core::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_stats_json_round_trip() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let main = insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        let helper = insert_symbol(&mut db, &file_id, Some("c:@F@helper#"), None, "helper", "FunctionDecl", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main), Some(&helper), None, None, "call").unwrap();
+
+        let json = render_stats(&collect_stats(&db).unwrap(), StatsFormatArg::Json).unwrap();
+        let stats: StatsOutput = serde_json::from_str(&json).unwrap();
+        assert_eq!(stats.symbols, 2);
+        assert_eq!(stats.edges, 1);
+        assert_eq!(stats.edge_types["call"], 1);
+        assert_eq!(stats.symbol_types["FunctionDecl"], 2);
+        assert_eq!(stats.languages["c++"], 1);
+
+        let csv = render_stats(&stats, StatsFormatArg::Csv).unwrap();
+        assert!(csv.lines().all(|line| line.split(',').count() == 2));
+        assert!(csv.contains("edge_types.call,1\n"));
+    }
+
+    #[test]
+    fn test_generate_compdb_dry_run_creates_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("CMakeLists.txt"), "project(demo CXX)\n").unwrap();
+        let project = dir.path().to_str().unwrap();
+        let output = dir.path().join("compile_commands.json");
+
+        generate_compdb(project, output.to_str(), None, None, Some("Ninja"), None, None, None, false, true).unwrap();
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(entries, vec!["CMakeLists.txt"]);
+
+        let plan = compdb_plan(
+            &detect_build_system_type(dir.path()),
+            dir.path(),
+            output.to_str().unwrap(),
+            None,
+            Some("Ninja"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(plan.len(), 1);
+        assert!(plan[0].to_string().ends_with("-DCMAKE_EXPORT_COMPILE_COMMANDS=ON -G Ninja -DCMAKE_BUILD_TYPE=Debug"), "{}", plan[0]);
+    }
+
+    #[test]
+    fn test_generate_compdb_dry_run_plans_make_fallbacks() {
+        use crate::modules::commands::cli::BuildSystemType;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Makefile"), "all:\n\tcc -c main.c\n").unwrap();
+        let plan = compdb_plan(&BuildSystemType::Make, dir.path(), "compile_commands.json", None, None, None, None, None).unwrap();
+
+        // bear and compiledb are only planned when installed; make -n is the last resort
+        let last = plan.last().unwrap();
+        assert_eq!(last.program, "make");
+        assert_eq!(last.fallback, plan.len() > 1);
+        assert_eq!(plan[0].program == "bear", symgraph_discovery::is_bear_available());
+    }
+
+    #[test]
+    fn test_list_files_by_lang() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        db.ensure_file("src/main.cpp", "c++").unwrap();
+        db.ensure_file("include/util.h", "c++").unwrap();
+        db.ensure_file("src/lib.rs", "rust").unwrap();
+
+        let files = filter_files(&db, Some("c++"), None).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["include/util.h", "src/main.cpp"]);
+        assert_eq!(filter_files(&db, None, None).unwrap().len(), 3);
+
+        let nul = render_file_list(&files, ListFilesFormatArg::NullSeparated).unwrap();
+        assert_eq!(nul, "include/util.h\0src/main.cpp\0");
+        let table = render_file_list(&files, ListFilesFormatArg::Table).unwrap();
+        assert_eq!(table.lines().count(), 3);
+        assert!(table.lines().nth(1).unwrap().starts_with("include/util.h  c++"));
+    }
+
+    #[test]
+    fn test_export_csv_symbols_headers() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.cpp", "c++").unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+
+        let output = tempfile::NamedTempFile::new().unwrap();
+        let path = output.path().to_str().unwrap();
+        let count = write_csv(&db, symgraph_core::CsvTable::Symbols, path, Default::default(), true).unwrap();
+        assert_eq!(count, 1);
+
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with("\u{FEFF}".as_bytes()));
+        let mut reader = csv::Reader::from_reader(&bytes[3..]);
+        let headers: Vec<_> = reader.headers().unwrap().iter().map(str::to_string).collect();
+        assert_eq!(&headers[..], symgraph_core::CsvTable::Symbols.headers());
+        assert_eq!(&headers[..7], ["id", "file_id", "usr", "key", "name", "kind", "is_definition"]);
+
+        let row = reader.records().next().unwrap().unwrap();
+        assert_eq!(&row[2], "c:@F@main#");
+        // No key: NULL becomes an empty cell
+        assert_eq!(&row[3], "");
+        assert_eq!(&row[4], "main");
+    }
+
+    #[test]
+    fn test_symbol_tree() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/geometry.cpp", "c++").unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@N@geo"), None, "geo", "Namespace", true).unwrap();
+        let shape = insert_symbol(&mut db, &file_id, Some("c:@N@geo@S@Shape"), None, "Shape", "ClassDecl", true).unwrap();
+        let area = insert_symbol(&mut db, &file_id, Some("c:@N@geo@S@Shape@F@area#"), None, "area()", "Method", true).unwrap();
+        db.set_symbol_parent(&shape, Some("c:@N@geo")).unwrap();
+        db.set_symbol_parent(&area, Some("c:@N@geo@S@Shape")).unwrap();
+
+        let tree = render_symbol_tree(&db, "c:@N@geo", 3).unwrap();
+        assert_eq!(tree, "geo (Namespace)\n  Shape (ClassDecl)\n    area() (Method)\n");
+        let shallow = render_symbol_tree(&db, "c:@N@geo", 1).unwrap();
+        assert_eq!(shallow, "geo (Namespace)\n  Shape (ClassDecl)\n");
+        assert!(render_symbol_tree(&db, "c:@N@missing", 3).is_err());
+    }
+
+    #[test]
+    fn test_store_cargo_graph() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        let package = |name: &str, version: &str, deps: &[&str]| symgraph_discovery::CargoDep {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        };
+        let packages = [
+            package("app", "0.1.0", &["serde", "syn"]),
+            package("serde", "1.0.195", &["serde_derive", "syn"]),
+            package("serde_derive", "1.0.195", &["syn"]),
+            package("syn", "1.0.109", &[]),
+            package("syn", "2.0.48", &[]),
+        ];
+
+        assert_eq!(store_cargo_graph(&mut db, &packages).unwrap(), 5);
+        assert_eq!(db.edges_by_kind("cargo-dep").unwrap().len(), 5);
+        let modules: Vec<symgraph_core::Module> = db
+            .db
+            .scan_prefix("module:")
+            .map(|item| serde_json::from_slice(&item.unwrap().1).unwrap())
+            .collect();
+        assert!(modules.iter().all(|m| m.kind == "cargo-crate"));
+        // Every module is stored under both its name and its ID
+        assert_eq!(modules.len(), 8);
+    }
+
+    #[test]
+    fn test_import_typescript_scip_index() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/typescript_project.scip");
+        let greet = "scip-typescript npm app 1.0.0 src/`greet.ts`/greet().";
+
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        import_scip_index(&fixture, None, "typescript_project").unwrap();
+        assert!(db.find_symbol_by_usr(greet).unwrap().is_none());
+
+        import_scip_index(&fixture, Some(&mut db), "typescript_project").unwrap();
+        let symbol_id = db.find_symbol_by_usr(greet).unwrap().unwrap();
+        let symbol = db.get_symbol(&symbol_id).unwrap().unwrap();
+        assert_eq!((symbol.name.as_str(), symbol.kind.as_str()), ("greet", "function"));
+        let greeter = db.find_symbol_by_usr("scip-typescript npm app 1.0.0 src/`greet.ts`/Greeter#").unwrap().unwrap();
+        assert_eq!(db.get_symbol(&greeter).unwrap().unwrap().kind, "class");
+        assert_eq!(db.find_file_by_path("src/greet.ts").unwrap().unwrap().lang, "typescript");
+    }
+
+    #[test]
+    fn test_render_members() {
+        let mut db = symgraph_core::Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("person.cpp", "c++").unwrap();
+        let base = insert_symbol(&mut db, &file_id, Some("c:@S@Base#"), None, "Base", "ClassDecl", true).unwrap();
+        let person = insert_symbol(&mut db, &file_id, Some("c:@S@Person#"), None, "Person", "ClassDecl", true).unwrap();
+        let name = insert_symbol(&mut db, &file_id, Some("c:@S@Person#name_"), None, "name_", "FieldDecl", true).unwrap();
+        let age = insert_symbol(&mut db, &file_id, Some("c:@S@Person#age_"), None, "age_", "FieldDecl", true).unwrap();
+        let get_name = insert_symbol(&mut db, &file_id, Some("c:@S@Person#getName#"), None, "getName", "Method", true).unwrap();
+        for member in [&name, &age, &get_name] {
+            insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&person), Some(member), None, None, "member").unwrap();
+        }
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&base), Some(&person), None, None, "inherit").unwrap();
+
+        let all = render_members(&db, "c:@S@Person#", MembersKindArg::All).unwrap();
+        assert_eq!(all, "Fields: age_, name_\nMethods: getName\nInherits: Base\n");
+        let members = render_members(&db, "c:@S@Person#", MembersKindArg::Members).unwrap();
+        assert_eq!(members, "Fields: age_, name_\nMethods: getName\n");
+        let methods = render_members(&db, "c:@S@Person#", MembersKindArg::Methods).unwrap();
+        assert_eq!(methods, "Methods: getName\n");
+        let fields = render_members(&db, "c:@S@Person#", MembersKindArg::Fields).unwrap();
+        assert_eq!(fields, "Fields: age_, name_\n");
+    }
+
+    #[test]
+    fn test_scan_rust_help_describes_rust_usrs() {
+        use clap::CommandFactory;
+
+        let mut command = crate::modules::commands::Args::command();
+        let help = command.find_subcommand_mut("scan-rust").unwrap().render_long_help().to_string();
+        assert!(help.contains("rust-analyzer cargo mycrate 0.1.0 parser/Parser#parse()."));
+        assert!(help.contains("--include-tests"));
+        assert!(help.contains("--skip-crate <NAME>"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Progress bar for long-running scans.
+//!
+//! The bar is drawn on stderr and only when stdout is a terminal, so output
+//! piped into files or other tools stays clean. `--quiet` hides it as well.
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::Instant;
+
+/// Counts processed files and reports throughput when done
+pub struct ProgressReporter {
+    bar: ProgressBar,
+    start: Instant,
+}
+
+impl ProgressReporter {
+    /// Progress over `total` files; hidden when `quiet` or stdout is not a TTY.
+    pub fn new(total: usize, quiet: bool) -> Self {
+        let target = std::io::stdout().is_terminal().then(ProgressDrawTarget::stderr);
+        Self::with_target(total, quiet, target)
+    }
+
+    /// Like [`ProgressReporter::new`], drawing to `target`; `None` means stdout is not a TTY
+    fn with_target(total: usize, quiet: bool, target: Option<ProgressDrawTarget>) -> Self {
+        let Some(target) = target.filter(|_| !quiet) else {
+            return Self::hidden();
+        };
+        let bar = ProgressBar::with_draw_target(Some(total as u64), target);
+        bar.set_style(
+            ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} [{elapsed_precise}] {msg}")
+                .expect("valid progress template"),
+        );
+        Self {
+            bar,
+            start: Instant::now(),
+        }
+    }
+
+    /// A reporter that never draws anything
+    pub fn hidden() -> Self {
+        Self {
+            bar: ProgressBar::hidden(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Count one processed file and show its stem
+    pub fn advance(&self, file: &str) {
+        self.bar.inc(1);
+        if let Some(stem) = Path::new(file).file_stem() {
+            self.bar.set_message(stem.to_string_lossy().to_string());
+        }
+    }
+
+    /// Print a line without tearing the bar
+    pub fn println(&self, line: impl AsRef<str>) {
+        if self.bar.is_hidden() {
+            println!("{}", line.as_ref());
+        } else {
+            self.bar.println(line);
+        }
+    }
+
+    /// Remove the bar and print elapsed time and files per second
+    pub fn finish(&self, files: usize) {
+        self.bar.finish_and_clear();
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 { files as f64 / elapsed } else { 0.0 };
+        println!("Elapsed: {:.2}s ({:.1} files/s)", elapsed, rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indicatif::InMemoryTerm;
+
+    fn terminal() -> Option<ProgressDrawTarget> {
+        Some(ProgressDrawTarget::term_like(Box::new(InMemoryTerm::new(10, 80))))
+    }
+
+    #[test]
+    fn test_quiet_hides_progress() {
+        let progress = ProgressReporter::with_target(10, true, terminal());
+        assert!(progress.bar.is_hidden());
+        progress.advance("src/main.cpp");
+        assert_eq!(progress.bar.position(), 1);
+    }
+
+    #[test]
+    fn test_progress_shown_only_on_terminal() {
+        assert!(!ProgressReporter::with_target(10, false, terminal()).bar.is_hidden());
+        assert!(ProgressReporter::with_target(10, false, None).bar.is_hidden());
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::Command;
+
+use assert_cmd::cargo::CommandCargoExt;
+use tempfile::TempDir;
+
+// Under `Command::output()` stdout is a pipe, so the bar is hidden even
+// without `--quiet`; the `--quiet` case is covered by unit tests in progress.rs
+#[test]
+fn test_scan_modules_piped_has_no_progress_output() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("lib.rs"), "pub fn helper() {}\n").unwrap();
+    let db_path = temp_dir.path().join("modules.db");
+
+    let output = Command::cargo_bin("symgraph-cli")
+        .unwrap()
+        .args(["scan-modules", "--root"])
+        .arg(temp_dir.path())
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("files/s"));
+}
@@ -0,0 +1,53 @@
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use assert_cmd::cargo::CommandCargoExt;
+use symgraph_core::{insert_symbol, SymgraphDb};
+use tempfile::TempDir;
+
+/// Reserve a free port by binding to port 0 and releasing it
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+#[test]
+fn test_serve_stats_endpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("serve.db");
+    let db_path = db_path.to_str().unwrap();
+
+    {
+        let mut db = SymgraphDb::open(db_path).unwrap();
+        let file_id = db.ensure_file("main.cpp", "c++").unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+    }
+
+    let port = free_port();
+    let mut child = Command::cargo_bin("symgraph-cli")
+        .unwrap()
+        .args(["serve", "--db", db_path, "--port", &port.to_string()])
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let url = format!("http://127.0.0.1:{}/stats", port);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let response = loop {
+        match reqwest::blocking::get(&url) {
+            Ok(response) => break Some(response),
+            Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+            Err(_) => break None,
+        }
+    };
+
+    child.kill().ok();
+    child.wait().ok();
+
+    let response = response.expect("server did not start in time");
+    assert!(response.status().is_success());
+    let stats: serde_json::Value = response.json().unwrap();
+    assert_eq!(stats["symbols"], 1);
+    assert_eq!(stats["edges"], 0);
+}
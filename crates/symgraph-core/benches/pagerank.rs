@@ -0,0 +1,49 @@
+//! PageRank over a synthetic 10 000-symbol call graph
+//!
+//!     cargo bench -p symgraph-core --bench pagerank
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use symgraph_core::{insert_edge, insert_symbol, EdgeKindRegistry, SymgraphDb};
+
+const SYMBOLS: usize = 10_000;
+const CALLS_PER_SYMBOL: usize = 3;
+
+/// Database with `SYMBOLS` functions, each calling `CALLS_PER_SYMBOL` others
+fn seed() -> SymgraphDb {
+    let mut db = SymgraphDb::open_temporary().unwrap();
+    let registry = EdgeKindRegistry::new();
+    let file_id = db.ensure_file("src/bench.cpp", "c++").unwrap();
+    let ids: Vec<String> = (0..SYMBOLS)
+        .map(|i| {
+            let usr = format!("c:@F@fn{}#", i);
+            insert_symbol(&mut db, &file_id, Some(&usr), None, &format!("fn{}", i), "FunctionDecl", true).unwrap()
+        })
+        .collect();
+
+    // Fixed LCG so every run ranks the same graph
+    let mut state: u64 = 42;
+    for from in &ids {
+        for _ in 0..CALLS_PER_SYMBOL {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let to = &ids[(state >> 33) as usize % SYMBOLS];
+            insert_edge(&mut db, &registry, Some(from), Some(to), None, None, "call").unwrap();
+        }
+    }
+    db
+}
+
+fn bench_pagerank(c: &mut Criterion) {
+    let db = seed();
+    let mut group = c.benchmark_group("pagerank");
+    group.sample_size(10);
+    group.bench_function("10k_symbols", |b| {
+        b.iter(|| {
+            let ranked = db.compute_pagerank("call").unwrap();
+            assert_eq!(ranked.len(), SYMBOLS);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_pagerank);
+criterion_main!(benches);
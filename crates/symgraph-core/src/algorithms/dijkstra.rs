@@ -0,0 +1,136 @@
+//! Hottest call paths
+//!
+//! Dijkstra's algorithm from a start symbol, where following an edge costs
+//! the inverse of its weight. Heavy edges are therefore cheap, and the
+//! cheapest path to a symbol runs through the most frequently taken edges.
+//! Each reachable symbol contributes its cheapest path, and the paths are
+//! ranked by their total weight. Edges with weight 0 are never followed.
+
+use anyhow::Result;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::SymgraphDb;
+
+/// A path through the symbol graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedPath {
+    /// Symbol IDs from the start symbol to the last symbol
+    pub symbols: Vec<String>,
+    /// Sum of the weights of the edges on the path
+    pub weight: f64,
+}
+
+/// Queue entry ordered so that `BinaryHeap` pops the lowest cost first
+struct Candidate {
+    cost: f64,
+    symbol: String,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| other.symbol.cmp(&self.symbol))
+    }
+}
+
+impl SymgraphDb {
+    /// The `top` paths with the highest total weight from the symbol with
+    /// `start_usr`, following edges of `kind`
+    pub fn hottest_paths(&self, start_usr: &str, kind: &str, top: usize) -> Result<Vec<WeightedPath>> {
+        let Some(start) = self.find_symbol_by_usr(start_usr)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut adjacency: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+        for edge in self.edges_by_kind(kind)? {
+            if let (Some(from), Some(to)) = (edge.from_sym, edge.to_sym) {
+                if edge.weight > 0.0 {
+                    adjacency.entry(from).or_default().push((to, edge.weight));
+                }
+            }
+        }
+
+        // Cheapest known cost, total weight and predecessor of every reached symbol
+        let mut best: HashMap<String, (f64, f64, Option<String>)> = HashMap::new();
+        best.insert(start.clone(), (0.0, 0.0, None));
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate { cost: 0.0, symbol: start.clone() });
+        while let Some(Candidate { cost, symbol }) = queue.pop() {
+            let (known_cost, weight, _) = best[&symbol];
+            if cost > known_cost {
+                continue;
+            }
+            for (next, edge_weight) in adjacency.get(&symbol).into_iter().flatten() {
+                let next_cost = cost + 1.0 / edge_weight;
+                if best.get(next).is_none_or(|(c, _, _)| next_cost < *c) {
+                    best.insert(next.clone(), (next_cost, weight + edge_weight, Some(symbol.clone())));
+                    queue.push(Candidate { cost: next_cost, symbol: next.clone() });
+                }
+            }
+        }
+
+        let mut paths: Vec<WeightedPath> = best
+            .keys()
+            .filter(|symbol| **symbol != start)
+            .map(|end| {
+                let mut symbols = vec![end.clone()];
+                while let Some(previous) = &best[symbols.last().unwrap()].2 {
+                    symbols.push(previous.clone());
+                }
+                symbols.reverse();
+                WeightedPath { symbols, weight: best[end].1 }
+            })
+            .collect();
+        paths.sort_by(|a, b| b.weight.total_cmp(&a.weight).then_with(|| a.symbols.cmp(&b.symbols)));
+        paths.truncate(top);
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_hottest_path_follows_heavy_edges() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.c", "c").unwrap();
+        let mut ids = HashMap::new();
+        for name in ["main", "parse", "lex", "log", "flush"] {
+            let usr = format!("c:@F@{}", name);
+            let id = insert_symbol(&mut db, &file_id, Some(&usr), None, name, "FunctionDecl", true).unwrap();
+            ids.insert(name, id);
+        }
+        let mut call = |from: &str, to: &str, weight: f64| {
+            let edge = insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&ids[from]), Some(&ids[to]), None, None, "call").unwrap();
+            db.update_edge_weight(&edge, weight).unwrap();
+        };
+        call("main", "parse", 100.0);
+        call("parse", "lex", 80.0);
+        call("main", "log", 1.0);
+        call("log", "flush", 1.0);
+
+        let path = |names: &[&str]| names.iter().map(|name| ids[name].clone()).collect::<Vec<_>>();
+        let paths = db.hottest_paths("c:@F@main", "call", 5).unwrap();
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[0].symbols, path(&["main", "parse", "lex"]));
+        assert_eq!(paths[0].weight, 180.0);
+        assert_eq!(paths[3].symbols, path(&["main", "log"]));
+        assert!(db.hottest_paths("c:@F@missing", "call", 5).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,6 @@
+//! Graph algorithms over the stored symbol graph.
+
+pub mod dijkstra;
+pub mod pagerank;
+
+pub use dijkstra::WeightedPath;
@@ -0,0 +1,123 @@
+//! PageRank of symbols
+//!
+//! A symbol is important when important symbols point at it, e.g. a function
+//! called from many widely used functions. A symbol passes its rank along
+//! its outgoing edges in proportion to their weights. Every stored symbol
+//! takes part, so symbols without edges keep the base score. The rank of a
+//! symbol with no outgoing weight is spread evenly over all symbols, which
+//! keeps the scores summing to 1.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::{Symbol, SymgraphDb};
+
+/// Number of update rounds
+const ITERATIONS: usize = 20;
+
+/// Probability of following an edge rather than jumping to a random symbol
+const DAMPING: f64 = 0.85;
+
+impl SymgraphDb {
+    /// PageRank of every symbol over edges of `kind`, highest score first
+    pub fn compute_pagerank(&self, kind: &str) -> Result<Vec<(String, f64)>> {
+        let mut ids = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for item in self.db.scan_prefix("symbol:") {
+            let (_, value) = item?;
+            let symbol: Symbol = serde_json::from_slice(&value)?;
+            index.insert(symbol.id.clone(), ids.len());
+            ids.push(symbol.id);
+        }
+
+        let mut edges = Vec::new();
+        for edge in self.edges_by_kind(kind)? {
+            let (Some(from), Some(to)) = (edge.from_sym, edge.to_sym) else {
+                continue;
+            };
+            if let (Some(&from), Some(&to)) = (index.get(&from), index.get(&to)) {
+                edges.push((from, to, edge.weight));
+            }
+        }
+
+        let scores = pagerank(ids.len(), &edges);
+        let mut ranked: Vec<(String, f64)> = ids.into_iter().zip(scores).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(ranked)
+    }
+}
+
+/// Scores of nodes `0..node_count` for directed `(from, to, weight)` edges
+fn pagerank(node_count: usize, edges: &[(usize, usize, f64)]) -> Vec<f64> {
+    if node_count == 0 {
+        return Vec::new();
+    }
+    let n = node_count as f64;
+    let mut out_weight = vec![0.0; node_count];
+    for &(from, _, weight) in edges {
+        out_weight[from] += weight;
+    }
+
+    let mut scores = vec![1.0 / n; node_count];
+    for _ in 0..ITERATIONS {
+        let dangling: f64 = scores
+            .iter()
+            .zip(&out_weight)
+            .filter(|(_, weight)| **weight == 0.0)
+            .map(|(score, _)| score)
+            .sum();
+        let base = (1.0 - DAMPING) / n + DAMPING * dangling / n;
+        let mut next = vec![base; node_count];
+        for &(from, to, weight) in edges {
+            if weight > 0.0 {
+                next[to] += DAMPING * scores[from] * weight / out_weight[from];
+            }
+        }
+        scores = next;
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_called_symbol_outranks_isolated_symbol() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.c", "c").unwrap();
+        let target = insert_symbol(&mut db, &file_id, Some("c:@F@log"), None, "log()", "FunctionDecl", true).unwrap();
+        let isolated = insert_symbol(&mut db, &file_id, Some("c:@F@unused"), None, "unused()", "FunctionDecl", true).unwrap();
+        for i in 0..5 {
+            let usr = format!("c:@F@caller{}", i);
+            let name = format!("caller{}()", i);
+            let caller = insert_symbol(&mut db, &file_id, Some(&usr), None, &name, "FunctionDecl", true).unwrap();
+            insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&caller), Some(&target), None, None, "call").unwrap();
+        }
+
+        let ranked = db.compute_pagerank("call").unwrap();
+        assert_eq!(ranked.len(), 7);
+        assert_eq!(ranked[0].0, target);
+        let score = |id: &str| ranked.iter().find(|(sid, _)| sid == id).unwrap().1;
+        assert!(score(&target) > score(&isolated));
+        let total: f64 = ranked.iter().map(|(_, s)| s).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rank_follows_edge_weights() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.c", "c").unwrap();
+        let main = insert_symbol(&mut db, &file_id, Some("c:@F@main"), None, "main()", "FunctionDecl", true).unwrap();
+        let hot = insert_symbol(&mut db, &file_id, Some("c:@F@hot"), None, "hot()", "FunctionDecl", true).unwrap();
+        let cold = insert_symbol(&mut db, &file_id, Some("c:@F@cold"), None, "cold()", "FunctionDecl", true).unwrap();
+        let hot_call = insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main), Some(&hot), None, None, "call").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main), Some(&cold), None, None, "call").unwrap();
+        db.update_edge_weight(&hot_call, 9.0).unwrap();
+
+        let ranked = db.compute_pagerank("call").unwrap();
+        let score = |id: &str| ranked.iter().find(|(sid, _)| sid == id).unwrap().1;
+        assert!(score(&hot) > score(&cold));
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
 
+use crate::{Project, SymgraphDb};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompiledProjectAnnotation {
     pub name: String,
@@ -77,6 +80,87 @@ pub fn analyze_rust_project(root_path: &str, files: &[(String, String, String)])
     })
 }
 
+/// Annotate a project from the stored symbol graph alone, without reading the source tree.
+///
+/// A `main` function makes the project an application; mostly headers and no entry point
+/// make it a library. When `module-import` edges make up most of the graph, the description
+/// calls the project modular.
+pub fn annotate_from_db(db: &SymgraphDb) -> Result<CompiledProjectAnnotation> {
+    // Files are stored under both their path and their ID
+    let mut seen = HashSet::new();
+    let file_infos: Vec<_> = db.list_files()?.into_iter().filter(|f| seen.insert(f.id.clone())).collect();
+    let is_rust = file_infos.iter().filter(|f| f.language == "rust").count() * 2 > file_infos.len();
+    let files: Vec<(String, String, String)> = file_infos
+        .into_iter()
+        .map(|f| (f.path, f.category, f.purpose))
+        .collect();
+
+    let mut entry_points = Vec::new();
+    for main in db.symbols_by_kind("FunctionDecl")? {
+        if main.name == "main" && main.is_definition {
+            if let Some(file) = db.get_file(&main.file_id)? {
+                entry_points.push(file.path);
+            }
+        }
+    }
+    let has_main = !entry_points.is_empty();
+    for (path, _, _) in files.iter().filter(|(_, category, _)| category == "entrypoint") {
+        if !entry_points.contains(path) {
+            entry_points.push(path.clone());
+        }
+    }
+    entry_points.sort();
+    entry_points.dedup();
+
+    let headers = files.iter().filter(|(_, category, _)| category == "header").count();
+    let purpose = if has_main {
+        ProjectPurpose::Application
+    } else if entry_points.is_empty() && headers * 2 > files.len() {
+        ProjectPurpose::Library
+    } else {
+        ProjectPurpose::Unknown
+    };
+
+    let total_edges = db.get_stats()?.edges;
+    let module_imports = db.edges_by_kind("module-import")?.len() as u64;
+    let modular = module_imports * 2 > total_edges;
+
+    let project = db
+        .db
+        .scan_prefix("project:")
+        .values()
+        .filter_map(|value| value.ok())
+        .find_map(|value| serde_json::from_slice::<Project>(&value).ok());
+    let (name, root_path) = match project {
+        Some(project) => (project.name, project.root_path),
+        None => ("project".to_string(), String::new()),
+    };
+
+    let mut description = if is_rust {
+        generate_rust_description(&purpose, files.len())
+    } else {
+        generate_cpp_description(&purpose, files.len())
+    };
+    if modular {
+        description.push_str(&format!(
+            " Module imports make up {} of {} edges, so it is built from separate modules.",
+            module_imports, total_edges
+        ));
+    }
+
+    Ok(CompiledProjectAnnotation {
+        name,
+        root_path,
+        language: if is_rust { "Rust" } else { "C++" }.to_string(),
+        description,
+        test_coverage: calculate_test_coverage(&files),
+        purpose,
+        build_system: if is_rust { BuildSystem::Cargo } else { BuildSystem::Unknown },
+        dependencies: Vec::new(),
+        entry_points,
+    })
+}
+
 fn extract_project_name_cpp(root_path: &str) -> String {
     // Try CMakeLists.txt
     let cmake_path = Path::new(root_path).join("CMakeLists.txt");
@@ -274,4 +358,39 @@ fn generate_rust_description(purpose: &ProjectPurpose, file_count: usize) -> Str
     };
     
     format!("This is {} with {} source files.", purpose_desc, file_count)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, upsert_module, EdgeKindRegistry};
+
+    #[test]
+    fn test_annotate_from_db_finds_application() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let main_cpp = db.ensure_file_with_category("1", "src/main.cpp", "c++", Some("implementation"), None).unwrap();
+        insert_symbol(&mut db, &main_cpp, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+
+        let annotation = annotate_from_db(&db).unwrap();
+        assert!(matches!(annotation.purpose, ProjectPurpose::Application));
+        assert_eq!(annotation.language, "C++");
+        assert_eq!(annotation.entry_points, vec!["src/main.cpp".to_string()]);
+    }
+
+    #[test]
+    fn test_annotate_from_db_finds_modular_library() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        for header in ["include/a.h", "include/b.h"] {
+            db.ensure_file_with_category("1", header, "c++", Some("header"), None).unwrap();
+        }
+        db.ensure_file_with_category("1", "src/a.cpp", "c++", Some("implementation"), None).unwrap();
+        let core = upsert_module(&mut db, "core", "cxx20", "").unwrap();
+        let app = upsert_module(&mut db, "app", "cxx20", "").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), None, None, Some(&app), Some(&core), "module-import").unwrap();
+
+        let annotation = annotate_from_db(&db).unwrap();
+        assert!(matches!(annotation.purpose, ProjectPurpose::Library));
+        assert!(annotation.entry_points.is_empty());
+        assert!(annotation.description.contains("separate modules"), "{}", annotation.description);
+    }
+}
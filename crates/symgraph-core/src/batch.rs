@@ -0,0 +1,367 @@
+//! Atomic multi-record writes
+//!
+//! A [`WriteBatch`] collects the writes of an update that touches many records,
+//! such as merging a scan, and applies them as one sled batch: either all of
+//! them land or none do. Lookups through the batch see its own pending writes;
+//! prefix scans only see committed records, minus those the batch removed.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::database::default_edge_weight;
+use crate::fts::name_tokens;
+use crate::{file_sha256, Edge, EdgeKindRegistry, File, Occurrence, Symbol, SymgraphDb};
+
+/// Pending writes against a [`SymgraphDb`], applied by [`WriteBatch::apply`]
+pub struct WriteBatch<'a> {
+    db: &'a SymgraphDb,
+    batch: sled::Batch,
+    /// Latest pending value per key; `None` marks a removal
+    pending: HashMap<String, Option<Vec<u8>>>,
+}
+
+impl SymgraphDb {
+    /// Start collecting writes that are applied together
+    pub fn write_batch(&self) -> WriteBatch<'_> {
+        WriteBatch {
+            db: self,
+            batch: sled::Batch::default(),
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl WriteBatch<'_> {
+    /// Apply every pending write atomically
+    pub fn apply(self) -> Result<()> {
+        self.db.db.apply_batch(self.batch)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.pending.get(key) {
+            Some(value) => Ok(value.clone()),
+            None => Ok(self.db.db.get(key)?.map(|value| value.to_vec())),
+        }
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key)?
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn insert(&mut self, key: String, value: impl Into<Vec<u8>>) {
+        let value = value.into();
+        self.batch.insert(key.as_bytes(), value.clone());
+        self.pending.insert(key, Some(value));
+    }
+
+    fn remove(&mut self, key: String) {
+        self.batch.remove(key.as_bytes());
+        self.pending.insert(key, None);
+    }
+
+    /// Committed `(key, value)` pairs under `prefix` that this batch has not removed
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut entries = Vec::new();
+        for item in self.db.db.scan_prefix(prefix) {
+            let (key, value) = item?;
+            let key = String::from_utf8_lossy(&key).to_string();
+            match self.pending.get(&key) {
+                Some(Some(value)) => entries.push((key, value.clone())),
+                Some(None) => {}
+                None => entries.push((key, value.to_vec())),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Look up a file by its path
+    pub fn find_file_by_path(&self, path: &str) -> Result<Option<File>> {
+        self.get_json(&format!("file:{}", path))
+    }
+
+    /// Look up a symbol by its database ID
+    pub fn get_symbol(&self, symbol_id: &str) -> Result<Option<Symbol>> {
+        self.get_json(&format!("symbol:{}", symbol_id))
+    }
+
+    /// ID of the symbol with `usr`
+    pub fn find_symbol_by_usr(&self, usr: &str) -> Result<Option<String>> {
+        Ok(self
+            .get(&format!("symbol_by_usr:{}", usr))?
+            .map(|id| String::from_utf8_lossy(&id).to_string()))
+    }
+
+    fn put_file(&mut self, file: &File) -> Result<()> {
+        let value = serde_json::to_vec(file)?;
+        self.insert(format!("file:{}", file.path), value.clone());
+        self.insert(format!("file:{}", file.id), value);
+        Ok(())
+    }
+
+    /// ID of the file at `path`, recording it with its content hash if it is new
+    pub fn ensure_file_with_category(
+        &mut self,
+        project_id: &str,
+        path: &str,
+        lang: &str,
+        category: Option<&str>,
+        purpose: Option<&str>,
+    ) -> Result<String> {
+        if let Some(existing) = self.find_file_by_path(path)? {
+            return Ok(existing.id);
+        }
+        let file = File {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            module_id: None,
+            path: path.to_string(),
+            lang: lang.to_string(),
+            category: category.map(|s| s.to_string()),
+            purpose: purpose.map(|s| s.to_string()),
+            is_header: false,
+            source_hash: file_sha256(path),
+        };
+        self.put_file(&file)?;
+        Ok(file.id)
+    }
+
+    /// Store the current content hash of a known file
+    pub fn update_file_hash(&mut self, path: &str) -> Result<()> {
+        if let Some(mut file) = self.find_file_by_path(path)? {
+            file.source_hash = file_sha256(path);
+            self.put_file(&file)?;
+        }
+        Ok(())
+    }
+
+    /// Flag a file as scanned from a header-only translation unit
+    pub fn mark_file_header(&mut self, file_id: &str) -> Result<()> {
+        if let Some(mut file) = self.get_json::<File>(&format!("file:{}", file_id))? {
+            if !file.is_header {
+                file.is_header = true;
+                self.put_file(&file)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Store `symbol` under a new ID with all of its indexes; returns the ID
+    pub fn insert_symbol(&mut self, symbol: Symbol) -> Result<String> {
+        let symbol = Symbol {
+            id: Uuid::new_v4().to_string(),
+            ..symbol
+        };
+        let id = symbol.id.clone();
+        self.insert(format!("symbol:{}", id), serde_json::to_vec(&symbol)?);
+        if let Some(usr) = &symbol.usr {
+            self.insert(format!("symbol_by_usr:{}", usr), id.as_bytes());
+        }
+        self.insert(format!("symbol_by_kind:{}:{}", symbol.kind, id), id.as_bytes());
+        self.insert(format!("symbol_by_file:{}:{}", symbol.file_id, id), id.as_bytes());
+        for token in name_tokens(&symbol.name) {
+            self.insert(format!("fts:{}:{}", token, id), id.as_bytes());
+        }
+        if let Some(parent) = &symbol.parent_usr {
+            self.insert(format!("symbol_by_parent:{}:{}", parent, id), id.as_bytes());
+        }
+        Ok(id)
+    }
+
+    /// Store an occurrence of a symbol; returns its ID
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_occurrence(
+        &mut self,
+        sym_id: &str,
+        file_id: &str,
+        usage: &str,
+        line: u32,
+        col: u32,
+        end_line: Option<u32>,
+        end_col: Option<u32>,
+    ) -> Result<String> {
+        let occurrence = Occurrence {
+            id: Uuid::new_v4().to_string(),
+            symbol_id: sym_id.to_string(),
+            file_id: file_id.to_string(),
+            usage_kind: usage.to_string(),
+            line,
+            column: col,
+            end_line,
+            end_column: end_col,
+        };
+        let id = occurrence.id.clone();
+        self.insert(format!("occurrence:{}", id), serde_json::to_vec(&occurrence)?);
+        self.insert(format!("occurrence_by_symbol:{}:{}", sym_id, id), id.as_bytes());
+        self.insert(format!("occurrence_by_file:{}:{}", file_id, id), id.as_bytes());
+        Ok(id)
+    }
+
+    /// Store an edge of a registered kind; returns its ID
+    pub fn insert_edge(
+        &mut self,
+        edge_kinds: &EdgeKindRegistry,
+        from_sym: Option<&str>,
+        to_sym: Option<&str>,
+        from_module: Option<&str>,
+        to_module: Option<&str>,
+        kind: &str,
+    ) -> Result<String> {
+        edge_kinds.validate(kind)?;
+        let edge = Edge {
+            id: Uuid::new_v4().to_string(),
+            from_sym: from_sym.map(|s| s.to_string()),
+            to_sym: to_sym.map(|s| s.to_string()),
+            from_module: from_module.map(|s| s.to_string()),
+            to_module: to_module.map(|s| s.to_string()),
+            kind: kind.to_string(),
+            weight: default_edge_weight(),
+        };
+        let id = edge.id.clone();
+        let value = serde_json::to_vec(&edge)?;
+        self.insert(format!("edge:{}", id), value.clone());
+        if let Some(from) = from_sym {
+            self.insert(format!("edges_from:{}:{}:{}", from, kind, id), value);
+        }
+        if let Some(to) = to_sym {
+            self.insert(format!("edges_to:{}:{}", to, id), id.as_bytes());
+        }
+        Ok(id)
+    }
+
+    fn remove_occurrence(&mut self, occurrence: &Occurrence) {
+        self.remove(format!("occurrence:{}", occurrence.id));
+        self.remove(format!("occurrence_by_symbol:{}:{}", occurrence.symbol_id, occurrence.id));
+        self.remove(format!("occurrence_by_file:{}:{}", occurrence.file_id, occurrence.id));
+    }
+
+    fn remove_edge(&mut self, edge: &Edge) {
+        self.remove(format!("edge:{}", edge.id));
+        if let Some(from) = &edge.from_sym {
+            self.remove(format!("edges_from:{}:{}:{}", from, edge.kind, edge.id));
+        }
+        if let Some(to) = &edge.to_sym {
+            self.remove(format!("edges_to:{}:{}", to, edge.id));
+        }
+    }
+
+    /// Remove the symbols declared in a file, their index entries, occurrences and
+    /// edges in both directions, plus every occurrence located in the file. Returns
+    /// the number of removed symbols; the file record itself is kept.
+    pub fn remove_symbols_in_file(&mut self, file_path: &str) -> Result<usize> {
+        let Some(file) = self.find_file_by_path(file_path)? else {
+            return Ok(0);
+        };
+
+        let mut removed = 0;
+        for (key, symbol_id) in self.scan_prefix(&format!("symbol_by_file:{}:", file.id))? {
+            self.remove(key);
+            let symbol_id = String::from_utf8_lossy(&symbol_id).to_string();
+            let Some(symbol) = self.get_symbol(&symbol_id)? else {
+                continue;
+            };
+            if let Some(usr) = &symbol.usr {
+                // Another file may have taken over the USR since
+                let usr_key = format!("symbol_by_usr:{}", usr);
+                if self.get(&usr_key)?.as_deref() == Some(symbol_id.as_bytes()) {
+                    self.remove(usr_key);
+                }
+            }
+            self.remove(format!("symbol_by_kind:{}:{}", symbol.kind, symbol_id));
+            if let Some(parent) = &symbol.parent_usr {
+                self.remove(format!("symbol_by_parent:{}:{}", parent, symbol_id));
+            }
+            for token in name_tokens(&symbol.name) {
+                self.remove(format!("fts:{}:{}", token, symbol_id));
+            }
+            for (key, tag) in self.scan_prefix(&format!("tag_by_symbol:{}:", symbol_id))? {
+                self.remove(key);
+                self.remove(format!("symbol_tag:{}:{}", String::from_utf8_lossy(&tag), symbol_id));
+            }
+            let alias_prefix = format!("alias_by_symbol:{}:", symbol_id);
+            for (key, _) in self.scan_prefix(&alias_prefix)? {
+                self.remove(format!("symbol_alias:{}:{}", &key[alias_prefix.len()..], symbol_id));
+                self.remove(key);
+            }
+            for (_, occ_id) in self.scan_prefix(&format!("occurrence_by_symbol:{}:", symbol_id))? {
+                let key = format!("occurrence:{}", String::from_utf8_lossy(&occ_id));
+                if let Some(occurrence) = self.get_json::<Occurrence>(&key)? {
+                    self.remove_occurrence(&occurrence);
+                }
+            }
+            for (_, value) in self.scan_prefix(&format!("edges_from:{}:", symbol_id))? {
+                self.remove_edge(&serde_json::from_slice(&value)?);
+            }
+            // Edges from other files into the removed symbol would dangle
+            for (_, edge_id) in self.scan_prefix(&format!("edges_to:{}:", symbol_id))? {
+                let key = format!("edge:{}", String::from_utf8_lossy(&edge_id));
+                if let Some(edge) = self.get_json::<Edge>(&key)? {
+                    self.remove_edge(&edge);
+                }
+            }
+            self.remove(format!("symbol:{}", symbol_id));
+            removed += 1;
+        }
+
+        for (_, occ_id) in self.scan_prefix(&format!("occurrence_by_file:{}:", file.id))? {
+            let key = format!("occurrence:{}", String::from_utf8_lossy(&occ_id));
+            if let Some(occurrence) = self.get_json::<Occurrence>(&key)? {
+                self.remove_occurrence(&occurrence);
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(file_id: &str, usr: &str, name: &str) -> Symbol {
+        Symbol {
+            id: String::new(),
+            file_id: file_id.to_string(),
+            usr: Some(usr.to_string()),
+            key: None,
+            name: name.to_string(),
+            kind: "FunctionDecl".to_string(),
+            is_definition: true,
+            cfg: None,
+            metadata: HashMap::new(),
+            template_args: None,
+            def_start_line: None,
+            def_end_line: None,
+            documentation: None,
+            parent_usr: None,
+        }
+    }
+
+    #[test]
+    fn test_write_batch_applies_nothing_until_apply() {
+        let db = SymgraphDb::open_temporary().unwrap();
+        let mut batch = db.write_batch();
+        let file_id = batch.ensure_file_with_category("1", "src/main.cpp", "c++", None, None).unwrap();
+        let main = batch.insert_symbol(function(&file_id, "c:@F@main#", "main")).unwrap();
+
+        // Pending writes are visible through the batch, not in the database
+        assert_eq!(batch.find_symbol_by_usr("c:@F@main#").unwrap(), Some(main));
+        assert_eq!(batch.ensure_file_with_category("1", "src/main.cpp", "c++", None, None).unwrap(), file_id);
+        assert!(db.find_symbol_by_usr("c:@F@main#").unwrap().is_none());
+
+        // A batch dropped without apply leaves the database untouched
+        drop(batch);
+        assert!(db.find_file_by_path("src/main.cpp").unwrap().is_none());
+
+        let mut batch = db.write_batch();
+        let file_id = batch.ensure_file_with_category("1", "src/main.cpp", "c++", None, None).unwrap();
+        let main = batch.insert_symbol(function(&file_id, "c:@F@main#", "main")).unwrap();
+        batch.apply().unwrap();
+        assert_eq!(db.find_symbol_by_usr("c:@F@main#").unwrap(), Some(main));
+        assert_eq!(db.symbols_by_kind("FunctionDecl").unwrap().len(), 1);
+    }
+}
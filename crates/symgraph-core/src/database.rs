@@ -1,8 +1,17 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sled::Db;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::fts::{name_tokens, rank_match, FtsResult};
+use crate::EdgeKindRegistry;
+
+/// How long [`SymgraphDb::open`] waits for another process to release the database
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub id: String,
@@ -33,6 +42,12 @@ pub struct File {
     pub lang: String,
     pub category: Option<String>,
     pub purpose: Option<String>,
+    /// Scanned as a header-only translation unit
+    #[serde(default)]
+    pub is_header: bool,
+    /// SHA-256 of the file contents when it was last scanned
+    #[serde(default)]
+    pub source_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +59,27 @@ pub struct Symbol {
     pub name: String,
     pub kind: String,
     pub is_definition: bool,
+    /// Cargo feature the symbol is gated behind (`#[cfg(feature = "...")]`)
+    #[serde(default)]
+    pub cfg: Option<String>,
+    /// Language-specific facts, e.g. `is_coroutine` (the `symbol_meta` column)
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Template arguments of a template instantiation, e.g. `int`
+    #[serde(default)]
+    pub template_args: Option<String>,
+    /// First line of the definition, including e.g. a leading return type
+    #[serde(default)]
+    pub def_start_line: Option<u32>,
+    /// Last line of the definition, e.g. the closing brace of a function body
+    #[serde(default)]
+    pub def_end_line: Option<u32>,
+    /// Doc comment text without comment markers (Doxygen, `///`)
+    #[serde(default)]
+    pub documentation: Option<String>,
+    /// USR of the enclosing namespace, class or function (the semantic parent)
+    #[serde(default)]
+    pub parent_usr: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +90,11 @@ pub struct Occurrence {
     pub usage_kind: String,
     pub line: u32,
     pub column: u32,
+    /// End of the occurrence range, when the indexer reports one
+    #[serde(default)]
+    pub end_line: Option<u32>,
+    #[serde(default)]
+    pub end_column: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,27 +105,55 @@ pub struct Edge {
     pub from_module: Option<String>,
     pub to_module: Option<String>,
     pub kind: String,
+    /// Relative frequency of the edge, e.g. a call count from profiling; 1.0 by default
+    #[serde(default = "default_edge_weight")]
+    pub weight: f64,
 }
 
+pub(crate) fn default_edge_weight() -> f64 {
+    1.0
+}
+
+/// Handle to the symbol database.
+///
+/// sled lets readers and writers work concurrently within one process, so a
+/// clone can be handed to another thread; other processes are locked out
+/// until the database is dropped.
+#[derive(Clone)]
 pub struct SymgraphDb {
     pub db: Db,
 }
 
 impl SymgraphDb {
+    /// Open the database at `path`, waiting up to [`BUSY_TIMEOUT`] while another process holds it
     pub fn open(path: &str) -> Result<Self> {
-        let db = sled::open(path).map_err(|e| {
+        let db = open_with_busy_timeout(path, BUSY_TIMEOUT).map_err(|e| {
             if e.to_string().contains("already exists") || e.to_string().contains("183") {
                 anyhow::anyhow!("Failed to open database at '{}': Cannot create file when it already exists. This may indicate:\n\
                 1. The database is already open by another process\n\
                 2. Insufficient permissions to access the database directory\n\
                 3. The database path is being used by another application\n\
                 \nTry closing other applications that might be using the database or choose a different path.", path)
+            } else if e.to_string().contains("could not acquire lock") {
+                anyhow::anyhow!(
+                    "Failed to open database at '{}': database is locked by another process (waited {}s)",
+                    path,
+                    BUSY_TIMEOUT.as_secs()
+                )
             } else if e.to_string().contains("IO") {
                 anyhow::anyhow!("Failed to open database at '{}': IO error: {}", path, e)
             } else {
                 anyhow::anyhow!("Failed to open database at '{}': {}", path, e)
             }
         })?;
+        crate::migrations::migrate(&db)?;
+        Ok(Self { db })
+    }
+
+    /// Open a temporary database that is removed when dropped
+    pub fn open_temporary() -> Result<Self> {
+        let db = sled::Config::new().temporary(true).open()?;
+        crate::migrations::migrate(&db)?;
         Ok(Self { db })
     }
 
@@ -131,28 +200,97 @@ impl SymgraphDb {
     }
 
     pub fn ensure_file_with_category(&mut self, project_id: &str, path: &str, lang: &str, category: Option<&str>, purpose: Option<&str>) -> Result<String> {
-        let file_id = Uuid::new_v4().to_string();
-        
-        let file = File {
-            id: file_id.clone(),
-            project_id: project_id.to_string(),
-            module_id: None,
-            path: path.to_string(),
-            lang: lang.to_string(),
-            category: category.map(|s| s.to_string()),
-            purpose: purpose.map(|s| s.to_string()),
-        };
+        let mut batch = self.write_batch();
+        let file_id = batch.ensure_file_with_category(project_id, path, lang, category, purpose)?;
+        batch.apply()?;
+        Ok(file_id)
+    }
 
-        let key = format!("file:{}", path);
-        if let Some(existing) = self.db.get(&key)? {
-            let existing_file: File = serde_json::from_slice(&existing)?;
-            Ok(existing_file.id)
-        } else {
-            let value = serde_json::to_vec(&file)?;
-            self.db.insert(&key, value.clone())?;
-            self.db.insert(format!("file:{}", file.id), value)?;
-            Ok(file_id)
+    /// Store the current content hash of a known file
+    pub fn update_file_hash(&mut self, path: &str) -> Result<()> {
+        let mut batch = self.write_batch();
+        batch.update_file_hash(path)?;
+        batch.apply()
+    }
+
+    /// Whether the file's contents differ from the hash stored at the last scan.
+    ///
+    /// Unknown files, files without a stored hash and unreadable files count as changed.
+    pub fn file_has_changed(&self, path: &str) -> Result<bool> {
+        let stored = self.find_file_by_path(path)?.and_then(|file| file.source_hash);
+        Ok(match (stored, file_sha256(path)) {
+            (Some(stored), Some(current)) => stored != current,
+            _ => true,
+        })
+    }
+
+    /// Flag a file as scanned from a header-only translation unit
+    pub fn mark_file_header(&mut self, file_id: &str) -> Result<()> {
+        let mut batch = self.write_batch();
+        batch.mark_file_header(file_id)?;
+        batch.apply()
+    }
+
+    /// Rewrite stored paths under `from_prefix` to start with `to_prefix`, e.g. after the
+    /// project moved to another directory.
+    ///
+    /// Project roots, file paths and module paths are updated; occurrences and symbols refer
+    /// to files by ID and need no change. A prefix only matches whole path components, so
+    /// `/src/app` does not match `/src/app2`. Returns the number of records updated.
+    pub fn remap_paths(&mut self, from_prefix: &str, to_prefix: &str) -> Result<u64> {
+        let mut batch = sled::Batch::default();
+        let mut updated = 0;
+
+        for item in self.db.scan_prefix("project:") {
+            let (key, value) = item?;
+            let mut project: Project = serde_json::from_slice(&value)?;
+            if key.as_ref() != format!("project:{}", project.id).as_bytes() {
+                continue;
+            }
+            if let Some(root_path) = remap_path(&project.root_path, from_prefix, to_prefix) {
+                batch.remove(format!("project:{}", project.root_path).as_bytes());
+                project.root_path = root_path;
+                let value = serde_json::to_vec(&project)?;
+                batch.insert(format!("project:{}", project.root_path).as_bytes(), value.clone());
+                batch.insert(key, value);
+                updated += 1;
+            }
+        }
+
+        for item in self.db.scan_prefix("file:") {
+            let (key, value) = item?;
+            let mut file: File = serde_json::from_slice(&value)?;
+            if key.as_ref() != format!("file:{}", file.id).as_bytes() {
+                continue;
+            }
+            if let Some(path) = remap_path(&file.path, from_prefix, to_prefix) {
+                batch.remove(format!("file:{}", file.path).as_bytes());
+                file.path = path;
+                let value = serde_json::to_vec(&file)?;
+                batch.insert(format!("file:{}", file.path).as_bytes(), value.clone());
+                batch.insert(key, value);
+                updated += 1;
+            }
         }
+
+        for item in self.db.scan_prefix("module:") {
+            let (key, value) = item?;
+            let Ok(mut module) = serde_json::from_slice::<Module>(&value) else {
+                continue;
+            };
+            let Some(path) = module.path.as_deref().and_then(|p| remap_path(p, from_prefix, to_prefix)) else {
+                continue;
+            };
+            // Both the name and the ID key hold the module; count it once
+            if key.as_ref() == format!("module:{}", module.id).as_bytes() {
+                updated += 1;
+            }
+            module.path = Some(path);
+            batch.insert(key, serde_json::to_vec(&module)?);
+        }
+
+        self.db.apply_batch(batch)?;
+        Ok(updated)
     }
 
     pub fn ensure_file(&mut self, path: &str, lang: &str) -> Result<String> {
@@ -188,6 +326,678 @@ impl SymgraphDb {
         
         Ok(result)
     }
+
+    /// Get a symbol by its database ID
+    pub fn get_symbol(&self, symbol_id: &str) -> Result<Option<Symbol>> {
+        match self.db.get(format!("symbol:{}", symbol_id))? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record the Cargo feature a symbol is gated behind
+    pub fn set_symbol_cfg(&mut self, symbol_id: &str, cfg: Option<&str>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            symbol.cfg = cfg.map(|s| s.to_string());
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Set the template arguments of an instantiation symbol
+    pub fn set_symbol_template_args(&mut self, symbol_id: &str, template_args: Option<&str>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            symbol.template_args = template_args.map(|s| s.to_string());
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Set the lines spanned by a symbol's definition
+    pub fn set_symbol_def_range(&mut self, symbol_id: &str, def_start: Option<u32>, def_end: Option<u32>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            symbol.def_start_line = def_start;
+            symbol.def_end_line = def_end;
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Set the documentation of a symbol
+    pub fn set_symbol_doc(&mut self, symbol_id: &str, doc: Option<&str>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            symbol.documentation = doc.map(|s| s.to_string());
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Get the documentation of a symbol; `None` when the symbol is unknown or undocumented
+    pub fn get_symbol_doc(&self, symbol_id: &str) -> Result<Option<String>> {
+        Ok(self.get_symbol(symbol_id)?.and_then(|symbol| symbol.documentation))
+    }
+
+    /// Set the semantic parent of a symbol and update the `symbol_by_parent:` index
+    pub fn set_symbol_parent(&mut self, symbol_id: &str, parent_usr: Option<&str>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            if let Some(old) = &symbol.parent_usr {
+                self.db.remove(format!("symbol_by_parent:{}:{}", old, symbol_id))?;
+            }
+            if let Some(parent) = parent_usr {
+                self.db.insert(format!("symbol_by_parent:{}:{}", parent, symbol_id), symbol_id.as_bytes())?;
+            }
+            symbol.parent_usr = parent_usr.map(|s| s.to_string());
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Get `(id, name, kind)` of the symbols nested directly in `parent_usr`, sorted by name
+    pub fn query_symbol_children(&self, parent_usr: &str) -> Result<Vec<(String, String, String)>> {
+        let mut children = Vec::new();
+        for item in self.db.scan_prefix(format!("symbol_by_parent:{}:", parent_usr)) {
+            let (_, symbol_id) = item?;
+            if let Some(symbol) = self.get_symbol(&String::from_utf8_lossy(&symbol_id))? {
+                children.push((symbol.id, symbol.name, symbol.kind));
+            }
+        }
+        children.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(children)
+    }
+
+    /// Get the known instantiations of a template via its incoming `instantiates` edges
+    pub fn query_instantiations(&self, template_usr: &str) -> Result<Vec<Symbol>> {
+        let mut instantiations = Vec::new();
+        if let Some(template_id) = self.find_symbol_by_usr(template_usr)? {
+            for edge in self.edges_to(&template_id, "instantiates")? {
+                if let Some(symbol) = edge.from_sym.as_deref().map(|id| self.get_symbol(id)).transpose()?.flatten() {
+                    instantiations.push(symbol);
+                }
+            }
+        }
+        Ok(instantiations)
+    }
+
+    /// Get the sorted names of the constructors of a class via their outgoing `constructs` edges
+    pub fn query_constructors(&self, class_usr: &str) -> Result<Vec<String>> {
+        let mut constructors = BTreeSet::new();
+        if let Some(class_id) = self.find_symbol_by_usr(class_usr)? {
+            for edge in self.edges_to(&class_id, "constructs")? {
+                if let Some(symbol) = edge.from_sym.as_deref().map(|id| self.get_symbol(id)).transpose()?.flatten() {
+                    constructors.insert(symbol.name);
+                }
+            }
+        }
+        Ok(constructors.into_iter().collect())
+    }
+
+    /// Get the sorted names of the functions and classes declared in a namespace.
+    ///
+    /// Members are found through outgoing `namespace-member` edges; for a
+    /// namespace alias, the members of the aliased namespace are returned.
+    pub fn query_namespace_members(&self, namespace_usr: &str) -> Result<Vec<String>> {
+        let Some(mut namespace_id) = self.find_symbol_by_usr(namespace_usr)? else {
+            return Ok(Vec::new());
+        };
+        let alias_prefix = format!("edges_from:{}:namespace-alias:", namespace_id);
+        if let Some(item) = self.db.scan_prefix(&alias_prefix).next() {
+            let (_, value) = item?;
+            let edge: Edge = serde_json::from_slice(&value)?;
+            if let Some(original) = edge.to_sym {
+                namespace_id = original;
+            }
+        }
+
+        let mut members = BTreeSet::new();
+        for item in self.db.scan_prefix(format!("edges_from:{}:namespace-member:", namespace_id)) {
+            let (_, value) = item?;
+            let edge: Edge = serde_json::from_slice(&value)?;
+            if let Some(symbol) = edge.to_sym.as_deref().map(|id| self.get_symbol(id)).transpose()?.flatten() {
+                members.insert(symbol.name);
+            }
+        }
+        Ok(members.into_iter().collect())
+    }
+
+    /// Replace the metadata of a symbol
+    pub fn set_symbol_metadata(&mut self, symbol_id: &str, metadata: &HashMap<String, String>) -> Result<()> {
+        if let Some(mut symbol) = self.get_symbol(symbol_id)? {
+            symbol.metadata = metadata.clone();
+            self.db.insert(format!("symbol:{}", symbol_id), serde_json::to_vec(&symbol)?)?;
+        }
+        Ok(())
+    }
+
+    /// Get all symbols whose metadata has `key` set to `value`
+    pub fn symbols_with_metadata(&self, key: &str, value: &str) -> Result<Vec<Symbol>> {
+        let mut symbols = Vec::new();
+        for item in self.db.scan_prefix("symbol:") {
+            let (_, data) = item?;
+            let symbol: Symbol = serde_json::from_slice(&data)?;
+            if symbol.metadata.get(key).map(String::as_str) == Some(value) {
+                symbols.push(symbol);
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Get all symbols whose metadata has `key` set, whatever its value
+    pub fn symbols_with_metadata_key(&self, key: &str) -> Result<Vec<Symbol>> {
+        let mut symbols = Vec::new();
+        for item in self.db.scan_prefix("symbol:") {
+            let (_, data) = item?;
+            let symbol: Symbol = serde_json::from_slice(&data)?;
+            if symbol.metadata.contains_key(key) {
+                symbols.push(symbol);
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Attach a user-defined label such as `deprecated` to a symbol; tagging twice is a no-op
+    pub fn tag_symbol(&mut self, symbol_id: &str, tag: &str) -> Result<()> {
+        self.db.insert(format!("symbol_tag:{}:{}", tag, symbol_id), symbol_id.as_bytes())?;
+        self.db.insert(format!("tag_by_symbol:{}:{}", symbol_id, tag), tag.as_bytes())?;
+        Ok(())
+    }
+
+    /// Remove a label from a symbol
+    pub fn untag_symbol(&mut self, symbol_id: &str, tag: &str) -> Result<()> {
+        self.db.remove(format!("symbol_tag:{}:{}", tag, symbol_id))?;
+        self.db.remove(format!("tag_by_symbol:{}:{}", symbol_id, tag))?;
+        Ok(())
+    }
+
+    /// Get the IDs of all symbols carrying `tag`
+    pub fn find_symbols_by_tag(&self, tag: &str) -> Result<Vec<String>> {
+        let mut symbol_ids = Vec::new();
+        for item in self.db.scan_prefix(format!("symbol_tag:{}:", tag)) {
+            let (_, symbol_id) = item?;
+            symbol_ids.push(String::from_utf8_lossy(&symbol_id).to_string());
+        }
+        Ok(symbol_ids)
+    }
+
+    /// Get the labels attached to a symbol
+    pub fn symbol_tags(&self, symbol_id: &str) -> Result<Vec<String>> {
+        let mut tags = Vec::new();
+        for item in self.db.scan_prefix(format!("tag_by_symbol:{}:", symbol_id)) {
+            let (_, tag) = item?;
+            tags.push(String::from_utf8_lossy(&tag).to_string());
+        }
+        Ok(tags)
+    }
+
+    /// Record a former name of a symbol, so that it is still found after a rename
+    pub fn add_alias(&mut self, symbol_id: &str, alias: &str) -> Result<()> {
+        let created_at = chrono::Utc::now().to_rfc3339();
+        self.db.insert(format!("symbol_alias:{}:{}", alias, symbol_id), symbol_id.as_bytes())?;
+        self.db.insert(format!("alias_by_symbol:{}:{}", symbol_id, alias), created_at.as_bytes())?;
+        Ok(())
+    }
+
+    /// Get the aliases recorded for a symbol
+    pub fn symbol_aliases(&self, symbol_id: &str) -> Result<Vec<String>> {
+        let prefix = format!("alias_by_symbol:{}:", symbol_id);
+        let mut aliases = Vec::new();
+        for item in self.db.scan_prefix(&prefix) {
+            let (key, _) = item?;
+            aliases.push(String::from_utf8_lossy(&key[prefix.len()..]).to_string());
+        }
+        Ok(aliases)
+    }
+
+    /// Get the IDs of all symbols named `name` or carrying it as an alias.
+    ///
+    /// A name matches with or without its parameter list, so `connect` finds
+    /// `connect(int)`.
+    pub fn resolve_alias(&self, name: &str) -> Result<Vec<String>> {
+        let mut symbol_ids = BTreeSet::new();
+        for item in self.db.scan_prefix(format!("symbol_alias:{}:", name)) {
+            let (_, symbol_id) = item?;
+            symbol_ids.insert(String::from_utf8_lossy(&symbol_id).to_string());
+        }
+
+        // The first name token is the whole lowercased name without parameters
+        let Some(token) = name_tokens(name).into_iter().next() else {
+            return Ok(symbol_ids.into_iter().collect());
+        };
+        for item in self.db.scan_prefix(format!("fts:{}:", token)) {
+            let (_, symbol_id) = item?;
+            let symbol_id = String::from_utf8_lossy(&symbol_id).to_string();
+            if let Some(symbol) = self.get_symbol(&symbol_id)? {
+                let base = symbol.name.split('(').next().unwrap_or(&symbol.name).trim();
+                if symbol.name == name || base == name {
+                    symbol_ids.insert(symbol_id);
+                }
+            }
+        }
+        Ok(symbol_ids.into_iter().collect())
+    }
+
+    /// Names of symbols that are declared but have no definition anywhere in the
+    /// database, such as forward-declared functions implemented elsewhere
+    pub fn find_undefined_symbols(&self) -> Result<Vec<String>> {
+        self.find_undefined_symbols_in_lang(None)
+    }
+
+    /// Like [`find_undefined_symbols`](Self::find_undefined_symbols), limited to
+    /// declarations in files of the given language
+    pub fn find_undefined_symbols_in_lang(&self, lang: Option<&str>) -> Result<Vec<String>> {
+        let mut defined = HashSet::new();
+        let mut declarations = Vec::new();
+        for item in self.db.scan_prefix("symbol:") {
+            let (_, value) = item?;
+            let symbol: Symbol = serde_json::from_slice(&value)?;
+            // Symbols without a USR cannot be matched across translation units
+            let Some(usr) = symbol.usr.clone() else {
+                continue;
+            };
+            if symbol.is_definition {
+                defined.insert(usr);
+            } else {
+                declarations.push(symbol);
+            }
+        }
+
+        let mut file_langs: HashMap<String, Option<String>> = HashMap::new();
+        let mut names = BTreeSet::new();
+        for symbol in declarations {
+            if symbol.usr.as_ref().is_some_and(|usr| defined.contains(usr)) {
+                continue;
+            }
+            if let Some(lang) = lang {
+                if !file_langs.contains_key(&symbol.file_id) {
+                    let file_lang = self.get_file(&symbol.file_id)?.map(|f| f.lang);
+                    file_langs.insert(symbol.file_id.clone(), file_lang);
+                }
+                if file_langs[&symbol.file_id].as_deref() != Some(lang) {
+                    continue;
+                }
+            }
+            names.insert(symbol.name);
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    /// Get all symbols of the given kind via the `symbol_by_kind:` index
+    pub fn symbols_by_kind(&self, kind: &str) -> Result<Vec<Symbol>> {
+        let mut symbols = Vec::new();
+        for item in self.db.scan_prefix(format!("symbol_by_kind:{}:", kind)) {
+            let (_, symbol_id) = item?;
+            if let Some(symbol) = self.get_symbol(&String::from_utf8_lossy(&symbol_id))? {
+                symbols.push(symbol);
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Get `(id, name, kind, is_definition)` of the symbols defined in a file, via the `symbol_by_file:` index
+    pub fn find_symbols_in_file(&self, file_path: &str) -> Result<Vec<(String, String, String, bool)>> {
+        let file = match self.find_file_by_path(file_path)? {
+            Some(file) => file,
+            None => return Ok(Vec::new()),
+        };
+        let mut rows = Vec::new();
+        for item in self.db.scan_prefix(format!("symbol_by_file:{}:", file.id)) {
+            let (_, symbol_id) = item?;
+            if let Some(symbol) = self.get_symbol(&String::from_utf8_lossy(&symbol_id))? {
+                if symbol.is_definition {
+                    rows.push((symbol.id, symbol.name, symbol.kind, symbol.is_definition));
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Get the IDs of the symbols in a file whose definition spans `line`,
+    /// outermost first when definitions are nested
+    pub fn find_symbols_at_line(&self, file_path: &str, line: u32) -> Result<Vec<String>> {
+        let Some(file) = self.find_file_by_path(file_path)? else {
+            return Ok(Vec::new());
+        };
+        let mut matches = Vec::new();
+        for item in self.db.scan_prefix(format!("symbol_by_file:{}:", file.id)) {
+            let (_, symbol_id) = item?;
+            let Some(symbol) = self.get_symbol(&String::from_utf8_lossy(&symbol_id))? else {
+                continue;
+            };
+            if let (Some(start), Some(end)) = (symbol.def_start_line, symbol.def_end_line) {
+                if start <= line && line <= end {
+                    matches.push((start, std::cmp::Reverse(end), symbol.id));
+                }
+            }
+        }
+        matches.sort();
+        Ok(matches.into_iter().map(|(_, _, id)| id).collect())
+    }
+
+    /// Remove the symbols declared in a file, their index entries, occurrences and
+    /// edges in both directions, plus every occurrence located in the file. Returns the number
+    /// of removed symbols; the file record itself is kept.
+    pub fn remove_symbols_in_file(&mut self, file_path: &str) -> Result<usize> {
+        let mut batch = self.write_batch();
+        let removed = batch.remove_symbols_in_file(file_path)?;
+        batch.apply()?;
+        Ok(removed)
+    }
+
+    /// Remove a file record together with its symbols, every occurrence of
+    /// those symbols or located in the file, and every edge from or to those
+    /// symbols. All removals are applied as one atomic batch.
+    pub fn delete_file_and_cascade(&mut self, path: &str) -> Result<DeleteStats> {
+        let mut stats = DeleteStats::default();
+        let file = match self.find_file_by_path(path)? {
+            Some(file) => file,
+            None => return Ok(stats),
+        };
+
+        let mut batch = sled::Batch::default();
+        let mut symbol_ids = std::collections::HashSet::new();
+        for item in self.db.scan_prefix(format!("symbol_by_file:{}:", file.id)) {
+            let (key, symbol_id) = item?;
+            batch.remove(key);
+            let symbol_id = String::from_utf8_lossy(&symbol_id).to_string();
+            let Some(symbol) = self.get_symbol(&symbol_id)? else {
+                continue;
+            };
+            if let Some(usr) = &symbol.usr {
+                let usr_key = format!("symbol_by_usr:{}", usr);
+                if self.db.get(&usr_key)?.as_deref() == Some(symbol_id.as_bytes()) {
+                    batch.remove(usr_key.as_bytes());
+                }
+            }
+            batch.remove(format!("symbol_by_kind:{}:{}", symbol.kind, symbol_id).as_bytes());
+            if let Some(parent) = &symbol.parent_usr {
+                batch.remove(format!("symbol_by_parent:{}:{}", parent, symbol_id).as_bytes());
+            }
+            for token in name_tokens(&symbol.name) {
+                batch.remove(format!("fts:{}:{}", token, symbol_id).as_bytes());
+            }
+            for tag in self.symbol_tags(&symbol_id)? {
+                batch.remove(format!("symbol_tag:{}:{}", tag, symbol_id).as_bytes());
+                batch.remove(format!("tag_by_symbol:{}:{}", symbol_id, tag).as_bytes());
+            }
+            for alias in self.symbol_aliases(&symbol_id)? {
+                batch.remove(format!("symbol_alias:{}:{}", alias, symbol_id).as_bytes());
+                batch.remove(format!("alias_by_symbol:{}:{}", symbol_id, alias).as_bytes());
+            }
+            batch.remove(format!("symbol:{}", symbol_id).as_bytes());
+            symbol_ids.insert(symbol_id);
+            stats.symbols_deleted += 1;
+        }
+
+        for item in self.db.scan_prefix("occurrence:") {
+            let (_, value) = item?;
+            let occurrence: Occurrence = serde_json::from_slice(&value)?;
+            if occurrence.file_id == file.id || symbol_ids.contains(&occurrence.symbol_id) {
+                batch.remove(format!("occurrence:{}", occurrence.id).as_bytes());
+                batch.remove(format!("occurrence_by_symbol:{}:{}", occurrence.symbol_id, occurrence.id).as_bytes());
+                batch.remove(format!("occurrence_by_file:{}:{}", occurrence.file_id, occurrence.id).as_bytes());
+                stats.occurrences_deleted += 1;
+            }
+        }
+
+        let touches = |id: &Option<String>| id.as_ref().is_some_and(|id| symbol_ids.contains(id));
+        for item in self.db.scan_prefix("edge:") {
+            let (_, value) = item?;
+            let edge: Edge = serde_json::from_slice(&value)?;
+            if touches(&edge.from_sym) || touches(&edge.to_sym) {
+                batch.remove(format!("edge:{}", edge.id).as_bytes());
+                if let Some(from) = &edge.from_sym {
+                    batch.remove(format!("edges_from:{}:{}:{}", from, edge.kind, edge.id).as_bytes());
+                }
+                if let Some(to) = &edge.to_sym {
+                    batch.remove(format!("edges_to:{}:{}", to, edge.id).as_bytes());
+                }
+                stats.edges_deleted += 1;
+            }
+        }
+
+        batch.remove(format!("file:{}", file.path).as_bytes());
+        batch.remove(format!("file:{}", file.id).as_bytes());
+        self.db.apply_batch(batch)?;
+        Ok(stats)
+    }
+
+    /// Get a file by its database ID
+    pub fn get_file(&self, file_id: &str) -> Result<Option<File>> {
+        match self.db.get(format!("file:{}", file_id))? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get all occurrences of a symbol via the `occurrence_by_symbol:` index
+    pub fn occurrences_of(&self, symbol_id: &str) -> Result<Vec<Occurrence>> {
+        let mut occurrences = Vec::new();
+        for item in self.db.scan_prefix(format!("occurrence_by_symbol:{}:", symbol_id)) {
+            let (_, occ_id) = item?;
+            if let Some(data) = self.db.get(format!("occurrence:{}", String::from_utf8_lossy(&occ_id)))? {
+                occurrences.push(serde_json::from_slice(&data)?);
+            }
+        }
+        Ok(occurrences)
+    }
+
+    /// Get every occurrence of the symbol with `usr`, ordered by file path, line and column
+    pub fn find_symbol_usages(&self, usr: &str) -> Result<Vec<UsageLocation>> {
+        let Some(symbol_id) = self.find_symbol_by_usr(usr)? else {
+            return Ok(Vec::new());
+        };
+        let mut paths = HashMap::new();
+        let mut usages = Vec::new();
+        for occurrence in self.occurrences_of(&symbol_id)? {
+            if !paths.contains_key(&occurrence.file_id) {
+                let path = self.get_file(&occurrence.file_id)?.map_or_else(|| occurrence.file_id.clone(), |f| f.path);
+                paths.insert(occurrence.file_id.clone(), path);
+            }
+            usages.push(UsageLocation {
+                file_path: paths[&occurrence.file_id].clone(),
+                line: occurrence.line,
+                col: occurrence.column,
+                usage_kind: occurrence.usage_kind,
+            });
+        }
+        usages.sort_by(|a, b| (&a.file_path, a.line, a.col).cmp(&(&b.file_path, b.line, b.col)));
+        Ok(usages)
+    }
+
+    /// Look up a file by its path
+    pub fn find_file_by_path(&self, path: &str) -> Result<Option<File>> {
+        match self.db.get(format!("file:{}", path))? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get outgoing edges of the given kind for a symbol ID
+    pub fn edges_from(&self, symbol_id: &str, kind: &str) -> Result<Vec<Edge>> {
+        let mut edges = Vec::new();
+        let prefix = format!("edges_from:{}:{}:", symbol_id, kind);
+        for item in self.db.scan_prefix(&prefix) {
+            let (_, value) = item?;
+            edges.push(serde_json::from_slice(&value)?);
+        }
+        Ok(edges)
+    }
+
+    /// Get all edges of the given kind
+    pub fn edges_by_kind(&self, kind: &str) -> Result<Vec<Edge>> {
+        let mut edges = Vec::new();
+        for item in self.db.scan_prefix("edge:") {
+            let (_, value) = item?;
+            let edge: Edge = serde_json::from_slice(&value)?;
+            if edge.kind == kind {
+                edges.push(edge);
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Get an edge by its database ID
+    pub fn get_edge(&self, edge_id: &str) -> Result<Option<Edge>> {
+        match self.db.get(format!("edge:{}", edge_id))? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the weight of an edge, e.g. a call count from profiling data
+    pub fn update_edge_weight(&mut self, edge_id: &str, weight: f64) -> Result<()> {
+        if !weight.is_finite() || weight < 0.0 {
+            anyhow::bail!("Invalid edge weight {}", weight);
+        }
+        let Some(mut edge) = self.get_edge(edge_id)? else {
+            anyhow::bail!("Edge not found: {}", edge_id);
+        };
+        edge.weight = weight;
+        let value = serde_json::to_vec(&edge)?;
+        self.db.insert(format!("edge:{}", edge_id), value.clone())?;
+        if let Some(from) = &edge.from_sym {
+            self.db.insert(format!("edges_from:{}:{}:{}", from, edge.kind, edge_id), value)?;
+        }
+        Ok(())
+    }
+
+    /// Get incoming edges of the given kind for a symbol ID
+    pub fn edges_to(&self, symbol_id: &str, kind: &str) -> Result<Vec<Edge>> {
+        Ok(self
+            .edges_by_kind(kind)?
+            .into_iter()
+            .filter(|e| e.to_sym.as_deref() == Some(symbol_id))
+            .collect())
+    }
+
+    /// Walk `inherit` edges from a class, returning it (depth 0) and its ancestors or descendants
+    pub fn query_inheritance_tree(&self, class_usr: &str, direction: InheritDirection) -> Result<Vec<InheritanceNode>> {
+        let mut nodes = Vec::new();
+        let root_id = match self.find_symbol_by_usr(class_usr)? {
+            Some(id) => id,
+            None => return Ok(nodes),
+        };
+
+        // Inherit edges point from the base class to the derived class
+        let mut visited = std::collections::HashSet::new();
+        let mut queue = std::collections::VecDeque::new();
+        visited.insert(root_id.clone());
+        queue.push_back((root_id, 0u8));
+
+        while let Some((sym_id, depth)) = queue.pop_front() {
+            if let Some(symbol) = self.get_symbol(&sym_id)? {
+                nodes.push(InheritanceNode { name: symbol.name, depth });
+            }
+            let next: Vec<String> = match direction {
+                InheritDirection::Descendants => self
+                    .edges_from(&sym_id, "inherit")?
+                    .into_iter()
+                    .filter_map(|e| e.to_sym)
+                    .collect(),
+                InheritDirection::Ancestors => self
+                    .edges_to(&sym_id, "inherit")?
+                    .into_iter()
+                    .filter_map(|e| e.from_sym)
+                    .collect(),
+            };
+            for id in next {
+                if visited.insert(id.clone()) {
+                    queue.push_back((id, depth.saturating_add(1)));
+                }
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Fan-in (callers) and fan-out (callees) over `call` edges for every symbol
+    /// that takes part in at least one call
+    pub fn query_symbol_metrics(&self) -> Result<Vec<SymbolMetrics>> {
+        let mut counts: HashMap<String, (i64, i64)> = HashMap::new();
+        for edge in self.edges_by_kind("call")? {
+            if let Some(to) = edge.to_sym {
+                counts.entry(to).or_default().0 += 1;
+            }
+            if let Some(from) = edge.from_sym {
+                counts.entry(from).or_default().1 += 1;
+            }
+        }
+
+        let mut metrics = Vec::with_capacity(counts.len());
+        for (symbol_id, (fan_in, fan_out)) in counts {
+            if let Some(symbol) = self.get_symbol(&symbol_id)? {
+                metrics.push(SymbolMetrics {
+                    name: symbol.name,
+                    kind: symbol.kind,
+                    fan_in,
+                    fan_out,
+                });
+            }
+        }
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(metrics)
+    }
+
+    /// Get the sorted names of the partitions of a C++20 module via its outgoing `module-partition` edges
+    pub fn query_module_partitions(&self, module: &str) -> Result<Vec<String>> {
+        let Some(value) = self.db.get(format!("module:{}", module))? else {
+            return Ok(Vec::new());
+        };
+        let module: Module = serde_json::from_slice(&value)?;
+        let mut partitions = BTreeSet::new();
+        for edge in self.edges_by_kind("module-partition")? {
+            if edge.from_module.as_deref() != Some(module.id.as_str()) {
+                continue;
+            }
+            if let Some(value) = edge.to_module.map(|id| self.db.get(format!("module:{}", id))).transpose()?.flatten() {
+                partitions.insert(serde_json::from_slice::<Module>(&value)?.name);
+            }
+        }
+        Ok(partitions.into_iter().collect())
+    }
+
+    /// List all modules
+    pub fn list_modules(&self) -> Result<Vec<Module>> {
+        // Modules are stored under both their name and their ID
+        let mut seen = std::collections::HashSet::new();
+        let mut modules = Vec::new();
+        for item in self.db.scan_prefix("module:") {
+            let (_, value) = item?;
+            if let Ok(module) = serde_json::from_slice::<Module>(&value) {
+                if seen.insert(module.id.clone()) {
+                    modules.push(module);
+                }
+            }
+        }
+        Ok(modules)
+    }
+}
+
+/// Open a sled database, retrying while its lock file is held by another process
+fn open_with_busy_timeout(path: &str, timeout: Duration) -> sled::Result<Db> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match sled::open(path) {
+            Err(e) if e.to_string().contains("could not acquire lock") && Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// `path` with `from_prefix` replaced by `to_prefix`, if the prefix ends at a path component
+fn remap_path(path: &str, from_prefix: &str, to_prefix: &str) -> Option<String> {
+    let rest = path.strip_prefix(from_prefix)?;
+    if rest.is_empty() || rest.starts_with('/') || from_prefix.ends_with('/') {
+        Some(format!("{}{}", to_prefix, rest))
+    } else {
+        None
+    }
+}
+
+/// Hex SHA-256 of a file's contents, used to detect changed files; `None` when it cannot be read
+pub fn file_sha256(path: impl AsRef<std::path::Path>) -> Option<String> {
+    std::fs::read(path).ok().map(|data| format!("{:x}", Sha256::digest(&data)))
 }
 
 pub fn insert_symbol(
@@ -199,28 +1009,28 @@ pub fn insert_symbol(
     kind: &str,
     is_def: bool,
 ) -> Result<String> {
-    let symbol_id = Uuid::new_v4().to_string();
-    
-    let symbol = Symbol {
-        id: symbol_id.clone(),
+    let mut batch = db.write_batch();
+    let symbol_id = batch.insert_symbol(Symbol {
+        id: String::new(),
         file_id: file_id.to_string(),
         usr: usr.map(|s| s.to_string()),
         key: key.map(|s| s.to_string()),
         name: name.to_string(),
         kind: kind.to_string(),
         is_definition: is_def,
-    };
-
-    let value = serde_json::to_vec(&symbol)?;
-    db.db.insert(format!("symbol:{}", symbol_id), value.clone())?;
-    
-    if let Some(usr_val) = usr {
-        db.db.insert(format!("symbol_by_usr:{}", usr_val), symbol_id.as_bytes())?;
-    }
-    
+        cfg: None,
+        metadata: HashMap::new(),
+        template_args: None,
+        def_start_line: None,
+        def_end_line: None,
+        documentation: None,
+        parent_usr: None,
+    })?;
+    batch.apply()?;
     Ok(symbol_id)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn insert_occurrence(
     db: &mut SymgraphDb,
     sym_id: &str,
@@ -228,50 +1038,27 @@ pub fn insert_occurrence(
     usage: &str,
     line: u32,
     col: u32,
+    end_line: Option<u32>,
+    end_col: Option<u32>,
 ) -> Result<String> {
-    let occ_id = Uuid::new_v4().to_string();
-    
-    let occurrence = Occurrence {
-        id: occ_id.clone(),
-        symbol_id: sym_id.to_string(),
-        file_id: file_id.to_string(),
-        usage_kind: usage.to_string(),
-        line,
-        column: col,
-    };
-
-    let value = serde_json::to_vec(&occurrence)?;
-    db.db.insert(format!("occurrence:{}", occ_id), value)?;
-    
+    let mut batch = db.write_batch();
+    let occ_id = batch.insert_occurrence(sym_id, file_id, usage, line, col, end_line, end_col)?;
+    batch.apply()?;
     Ok(occ_id)
 }
 
 pub fn insert_edge(
     db: &mut SymgraphDb,
+    edge_kinds: &EdgeKindRegistry,
     from_sym: Option<&str>,
     to_sym: Option<&str>,
     from_module: Option<&str>,
     to_module: Option<&str>,
     kind: &str,
 ) -> Result<String> {
-    let edge_id = Uuid::new_v4().to_string();
-    
-    let edge = Edge {
-        id: edge_id.clone(),
-        from_sym: from_sym.map(|s| s.to_string()),
-        to_sym: to_sym.map(|s| s.to_string()),
-        from_module: from_module.map(|s| s.to_string()),
-        to_module: to_module.map(|s| s.to_string()),
-        kind: kind.to_string(),
-    };
-
-    let value = serde_json::to_vec(&edge)?;
-    db.db.insert(format!("edge:{}", edge_id), value.clone())?;
-    
-    if let Some(from) = from_sym {
-        db.db.insert(format!("edges_from:{}:{}:{}", from, kind, edge_id), value)?;
-    }
-    
+    let mut batch = db.write_batch();
+    let edge_id = batch.insert_edge(edge_kinds, from_sym, to_sym, from_module, to_module, kind)?;
+    batch.apply()?;
     Ok(edge_id)
 }
 
@@ -390,10 +1177,15 @@ impl SymgraphDb {
 
     /// List all files
     pub fn list_files(&self) -> Result<Vec<FileInfo>> {
+        // Files are stored under both their path and their ID
+        let mut seen = HashSet::new();
         let mut files = Vec::new();
         for item in self.db.scan_prefix("file:") {
             let (_, value) = item?;
             if let Ok(file) = serde_json::from_slice::<File>(&value) {
+                if !seen.insert(file.id.clone()) {
+                    continue;
+                }
                 files.push(FileInfo {
                     id: file.id,
                     path: file.path,
@@ -433,17 +1225,114 @@ impl SymgraphDb {
         Ok(symbols)
     }
 
-    /// Search symbols by name
+    /// Full-text search on symbol names via the `fts:` token index.
+    ///
+    /// Every whitespace-separated term must match the start of a word of the
+    /// name (case-insensitive). Results are ordered by their worst term rank,
+    /// then by name length and name.
+    pub fn fts_search_symbols(&self, query: &str) -> Result<Vec<FtsResult>> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let Some(first) = terms.first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut candidates = std::collections::BTreeSet::new();
+        for item in self.db.scan_prefix(format!("fts:{}", first)) {
+            let (_, symbol_id) = item?;
+            candidates.insert(String::from_utf8_lossy(&symbol_id).to_string());
+        }
+
+        let mut results = Vec::new();
+        for symbol_id in candidates {
+            let Some(symbol) = self.get_symbol(&symbol_id)? else {
+                continue;
+            };
+            let ranks: Option<Vec<u32>> = terms.iter().map(|term| rank_match(&symbol.name, term)).collect();
+            let Some(rank) = ranks.and_then(|ranks| ranks.into_iter().max()) else {
+                continue;
+            };
+            let file_path = self.get_file(&symbol.file_id)?.map(|f| f.path);
+            results.push(FtsResult {
+                symbol_id,
+                name: symbol.name,
+                kind: symbol.kind,
+                file_path,
+                rank,
+            });
+        }
+        results.sort_by(|a, b| (a.rank, a.name.len(), &a.name).cmp(&(b.rank, b.name.len(), &b.name)));
+        Ok(results)
+    }
+
+    /// Search symbols by name or alias
     pub fn search_symbols(&self, query: &str) -> Result<Vec<SymbolInfo>> {
         let all_symbols = self.list_symbols()?;
         let query_lower = query.to_lowercase();
+
+        let mut aliased = HashSet::new();
+        for item in self.db.scan_prefix("symbol_alias:") {
+            let (key, symbol_id) = item?;
+            let key = String::from_utf8_lossy(&key);
+            let symbol_id = String::from_utf8_lossy(&symbol_id);
+            // Key layout: symbol_alias:{alias}:{symbol_id}
+            let alias = &key["symbol_alias:".len()..key.len() - symbol_id.len() - 1];
+            if alias.to_lowercase().contains(&query_lower) {
+                aliased.insert(symbol_id.to_string());
+            }
+        }
+
         Ok(all_symbols
             .into_iter()
-            .filter(|s| s.name.to_lowercase().contains(&query_lower))
+            .filter(|s| s.name.to_lowercase().contains(&query_lower) || aliased.contains(&s.id))
             .collect())
     }
 }
 
+/// Direction of an inheritance hierarchy query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InheritDirection {
+    /// Base classes, transitively
+    Ancestors,
+    /// Derived classes, transitively
+    Descendants,
+}
+
+/// What [`SymgraphDb::delete_file_and_cascade`] removed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteStats {
+    pub symbols_deleted: usize,
+    pub occurrences_deleted: usize,
+    pub edges_deleted: usize,
+}
+
+/// Coupling of a symbol in the call graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolMetrics {
+    pub name: String,
+    pub kind: String,
+    /// Number of incoming `call` edges
+    pub fan_in: i64,
+    /// Number of outgoing `call` edges
+    pub fan_out: i64,
+}
+
+/// A class in an inheritance hierarchy and its distance from the queried class
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InheritanceNode {
+    pub name: String,
+    pub depth: u8,
+}
+
+/// A place where a symbol is used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageLocation {
+    pub file_path: String,
+    pub line: u32,
+    pub col: u32,
+    /// How the symbol is used there, e.g. `call` or `type_ref`
+    pub usage_kind: String,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct DatabaseStats {
     pub files: u64,
@@ -0,0 +1,146 @@
+//! Comparison of two symbol graphs
+//!
+//! Symbols are matched by USR (falling back to name and kind when no USR is
+//! recorded), edges by `(from name, to name, kind)`, so the two databases do
+//! not need to share internal IDs.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::SymgraphDb;
+
+/// Differences between two databases
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DbDiff {
+    pub added_symbols: Vec<String>,
+    pub removed_symbols: Vec<String>,
+    pub added_edges: usize,
+    pub removed_edges: usize,
+}
+
+impl DbDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_symbols.is_empty()
+            && self.removed_symbols.is_empty()
+            && self.added_edges == 0
+            && self.removed_edges == 0
+    }
+}
+
+/// Compare `before` and `after`.
+///
+/// When `kind` is given, only symbols and edges of that kind are compared
+/// (e.g. `FunctionDecl` for symbols or `call` for edges).
+pub fn diff_databases(before: &SymgraphDb, after: &SymgraphDb, kind: Option<&str>) -> Result<DbDiff> {
+    let before_symbols = symbol_keys(before, kind)?;
+    let after_symbols = symbol_keys(after, kind)?;
+
+    let added_symbols = after_symbols
+        .iter()
+        .filter(|(key, _)| !before_symbols.contains_key(*key))
+        .map(|(_, name)| name.clone())
+        .collect();
+    let removed_symbols = before_symbols
+        .iter()
+        .filter(|(key, _)| !after_symbols.contains_key(*key))
+        .map(|(_, name)| name.clone())
+        .collect();
+
+    let before_edges = edge_keys(before, kind)?;
+    let after_edges = edge_keys(after, kind)?;
+
+    Ok(DbDiff {
+        added_symbols,
+        removed_symbols,
+        added_edges: after_edges.difference(&before_edges).count(),
+        removed_edges: before_edges.difference(&after_edges).count(),
+    })
+}
+
+/// Map of symbol identity key to display name, sorted by key
+fn symbol_keys(db: &SymgraphDb, kind: Option<&str>) -> Result<BTreeMap<String, String>> {
+    let mut keys = BTreeMap::new();
+    for item in db.db.scan_prefix("symbol:") {
+        let (_, value) = item?;
+        let symbol: crate::Symbol = serde_json::from_slice(&value)?;
+        if kind.is_some_and(|k| k != symbol.kind) {
+            continue;
+        }
+        let key = match &symbol.usr {
+            Some(usr) => usr.clone(),
+            None => format!("{}:{}", symbol.kind, symbol.name),
+        };
+        keys.insert(key, symbol.name);
+    }
+    Ok(keys)
+}
+
+/// Set of `(from name, to name, kind)` for symbol-to-symbol edges
+fn edge_keys(db: &SymgraphDb, kind: Option<&str>) -> Result<HashSet<(String, String, String)>> {
+    let mut names: HashMap<String, Option<String>> = HashMap::new();
+    let mut name_of = |id: &str| -> Result<Option<String>> {
+        if let Some(name) = names.get(id) {
+            return Ok(name.clone());
+        }
+        let name = db.get_symbol(id)?.map(|s| s.name);
+        names.insert(id.to_string(), name.clone());
+        Ok(name)
+    };
+
+    let mut keys = HashSet::new();
+    for item in db.db.scan_prefix("edge:") {
+        let (_, value) = item?;
+        let edge: crate::Edge = serde_json::from_slice(&value)?;
+        if kind.is_some_and(|k| k != edge.kind) {
+            continue;
+        }
+        if let (Some(from), Some(to)) = (&edge.from_sym, &edge.to_sym) {
+            if let (Some(from_name), Some(to_name)) = (name_of(from)?, name_of(to)?) {
+                keys.insert((from_name, to_name, edge.kind));
+            }
+        }
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    fn seed(db: &mut SymgraphDb, with_helper: bool) {
+        let file_id = db.ensure_file("app.cpp", "c++").unwrap();
+        let main_id = insert_symbol(db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        let run_id = insert_symbol(db, &file_id, Some("c:@F@run#"), None, "run", "FunctionDecl", true).unwrap();
+        insert_edge(db, &EdgeKindRegistry::new(), Some(&main_id), Some(&run_id), None, None, "call").unwrap();
+        if with_helper {
+            let helper_id = insert_symbol(db, &file_id, Some("c:@F@helper#"), None, "helper", "FunctionDecl", true).unwrap();
+            insert_edge(db, &EdgeKindRegistry::new(), Some(&run_id), Some(&helper_id), None, None, "call").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_diff_databases_one_added_symbol() {
+        let mut before = SymgraphDb::open_temporary().unwrap();
+        let mut after = SymgraphDb::open_temporary().unwrap();
+        seed(&mut before, false);
+        seed(&mut after, true);
+
+        let diff = diff_databases(&before, &after, None).unwrap();
+        assert_eq!(diff.added_symbols, vec!["helper".to_string()]);
+        assert!(diff.removed_symbols.is_empty());
+        assert_eq!(diff.added_edges, 1);
+        assert_eq!(diff.removed_edges, 0);
+
+        let reverse = diff_databases(&after, &before, None).unwrap();
+        assert_eq!(reverse.removed_symbols, vec!["helper".to_string()]);
+        assert_eq!(reverse.removed_edges, 1);
+
+        // Kind filter restricts the comparison
+        let classes_only = diff_databases(&before, &after, Some("ClassDecl")).unwrap();
+        assert!(classes_only.is_empty());
+
+        let same = diff_databases(&after, &after, None).unwrap();
+        assert!(same.is_empty());
+    }
+}
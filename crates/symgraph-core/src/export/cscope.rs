@@ -0,0 +1,168 @@
+//! cscope cross-reference export
+//!
+//! Writes a `cscope.out` in the uncompressed (`-c`) cscope 15 format so that
+//! editors and scripts built around cscope can query a symgraph database.
+//! Function definitions (`$`) and calls (`` ` ``) come from `definition` and
+//! `call` occurrences. The database does not record where a function body
+//! ends, so each function ends (`}`) where the next one in the file begins.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+
+use crate::{File, Occurrence, Symbol, SymgraphDb};
+
+/// Format version written in the `cscope.out` header
+const CSCOPE_VERSION: u32 = 15;
+
+/// A function cross-reference mark on a source line, in the order cscope expects them
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Mark {
+    End,
+    Definition(String),
+    Call(String),
+}
+
+/// Write `cscope.out` for the database into `output_dir`.
+pub fn export_cscope(db: &SymgraphDb, output_dir: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    for item in db.db.scan_prefix("file:") {
+        let (key, value) = item?;
+        let Ok(file) = serde_json::from_slice::<File>(&value) else {
+            continue;
+        };
+        // Files are stored under both their path and their ID
+        if key.as_ref() == format!("file:{}", file.id).as_bytes() {
+            files.push(file);
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut occurrences_by_file: HashMap<String, Vec<Occurrence>> = HashMap::new();
+    for item in db.db.scan_prefix("occurrence:") {
+        let (_, value) = item?;
+        if let Ok(occurrence) = serde_json::from_slice::<Occurrence>(&value) {
+            occurrences_by_file.entry(occurrence.file_id.clone()).or_default().push(occurrence);
+        }
+    }
+
+    let mut symbols: HashMap<String, Option<Symbol>> = HashMap::new();
+    let mut body = Vec::new();
+    for file in &files {
+        let mut lines: BTreeMap<u32, Vec<Mark>> = BTreeMap::new();
+        for occurrence in occurrences_by_file.remove(&file.id).unwrap_or_default() {
+            if !symbols.contains_key(&occurrence.symbol_id) {
+                symbols.insert(occurrence.symbol_id.clone(), db.get_symbol(&occurrence.symbol_id)?);
+            }
+            let Some(symbol) = symbols[&occurrence.symbol_id].as_ref().filter(|s| is_function_kind(&s.kind)) else {
+                continue;
+            };
+            let mark = match occurrence.usage_kind.as_str() {
+                "definition" => Mark::Definition(identifier(&symbol.name)),
+                "call" => Mark::Call(identifier(&symbol.name)),
+                _ => continue,
+            };
+            lines.entry(occurrence.line).or_default().push(mark);
+        }
+        close_functions(&mut lines);
+
+        writeln!(body, "\t@{}", file.path)?;
+        writeln!(body)?;
+        for (line, mut marks) in lines {
+            marks.sort();
+            marks.dedup();
+            writeln!(body, "{} ", line)?;
+            for mark in marks {
+                match mark {
+                    Mark::End => writeln!(body, "\t}}")?,
+                    Mark::Definition(name) => writeln!(body, "\t${}", name)?,
+                    Mark::Call(name) => writeln!(body, "\t`{}", name)?,
+                }
+            }
+            writeln!(body)?;
+        }
+    }
+    writeln!(body, "\t@")?;
+
+    let directory = std::fs::canonicalize(output_dir).unwrap_or_else(|_| output_dir.to_path_buf());
+    let header_len = format!("cscope {} {} -c {:010}\n", CSCOPE_VERSION, directory.display(), 0).len();
+    let trailer_offset = header_len + body.len();
+
+    let mut output = std::io::BufWriter::new(std::fs::File::create(output_dir.join("cscope.out"))?);
+    writeln!(output, "cscope {} {} -c {:010}", CSCOPE_VERSION, directory.display(), trailer_offset)?;
+    output.write_all(&body)?;
+
+    // Trailer: source directories, include directories, then the file list
+    writeln!(output, "1\n.\n0")?;
+    writeln!(output, "{}", files.len())?;
+    writeln!(output, "{}", files.iter().map(|f| f.path.len() + 1).sum::<usize>())?;
+    for file in &files {
+        writeln!(output, "{}", file.path)?;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// End every function on the line where the next definition starts, and the
+/// last one after the final marked line
+fn close_functions(lines: &mut BTreeMap<u32, Vec<Mark>>) {
+    let definitions: Vec<u32> = lines
+        .iter()
+        .filter(|(_, marks)| marks.iter().any(|m| matches!(m, Mark::Definition(_))))
+        .map(|(line, _)| *line)
+        .collect();
+    let Some(last_line) = lines.keys().next_back().copied() else {
+        return;
+    };
+    for next in definitions.iter().skip(1) {
+        lines.entry(*next).or_default().push(Mark::End);
+    }
+    if !definitions.is_empty() {
+        lines.entry(last_line + 1).or_default().push(Mark::End);
+    }
+}
+
+fn is_function_kind(kind: &str) -> bool {
+    let kind = kind.to_ascii_lowercase();
+    kind.contains("function") || kind.contains("method") || kind == "constructor" || kind == "destructor"
+}
+
+/// Bare identifier of a display name such as `resize(int, int)`
+fn identifier(name: &str) -> String {
+    name.split('(').next().unwrap_or(name).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_occurrence, insert_symbol};
+
+    #[test]
+    fn test_export_cscope_function_definitions_and_calls() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.c", "c").unwrap();
+        let helper = insert_symbol(&mut db, &file_id, Some("c:@F@helper"), None, "helper(int)", "FunctionDecl", true).unwrap();
+        let main = insert_symbol(&mut db, &file_id, Some("c:@F@main"), None, "main()", "FunctionDecl", true).unwrap();
+        insert_occurrence(&mut db, &helper, &file_id, "definition", 1, 5, None, None).unwrap();
+        insert_occurrence(&mut db, &main, &file_id, "definition", 5, 5, None, None).unwrap();
+        insert_occurrence(&mut db, &helper, &file_id, "call", 6, 12, None, None).unwrap();
+
+        let output_dir = std::env::temp_dir().join(format!("symgraph_cscope_{}", std::process::id()));
+        std::fs::create_dir_all(&output_dir).unwrap();
+        export_cscope(&db, &output_dir).unwrap();
+        let cscope = std::fs::read_to_string(output_dir.join("cscope.out")).unwrap();
+        std::fs::remove_dir_all(&output_dir).ok();
+
+        assert!(cscope.starts_with("cscope 15 "));
+        assert!(cscope.contains("\t@src/main.c\n"));
+        assert!(cscope.contains("1 \n\t$helper\n\n"));
+        assert!(cscope.contains("5 \n\t}\n\t$main\n\n"));
+        assert!(cscope.contains("6 \n\t`helper\n\n"));
+        assert!(cscope.contains("7 \n\t}\n\n"));
+
+        // The header points at the trailer with the file list
+        let offset: usize = cscope.lines().next().unwrap().rsplit(' ').next().unwrap().parse().unwrap();
+        assert_eq!(&cscope[offset..], "1\n.\n0\n1\n11\nsrc/main.c\n");
+    }
+}
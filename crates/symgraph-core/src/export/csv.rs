@@ -0,0 +1,245 @@
+//! CSV table export
+//!
+//! Writes one collection as a CSV table for spreadsheet tools, with a header
+//! row naming the columns. Missing optional values become empty cells and
+//! symbol metadata is written as a JSON object in a single cell.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+
+use crate::{Edge, File, Module, Occurrence, Symbol, SymgraphDb};
+
+/// Collection exported by [`SymgraphDb::export_csv`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvTable {
+    Symbols,
+    Files,
+    Edges,
+    Occurrences,
+    Modules,
+}
+
+impl CsvTable {
+    /// Column names in the order they are written
+    pub fn headers(self) -> &'static [&'static str] {
+        match self {
+            CsvTable::Symbols => &[
+                "id",
+                "file_id",
+                "usr",
+                "key",
+                "name",
+                "kind",
+                "is_definition",
+                "cfg",
+                "metadata",
+                "template_args",
+                "def_start_line",
+                "def_end_line",
+                "documentation",
+                "parent_usr",
+            ],
+            CsvTable::Files => &[
+                "id",
+                "project_id",
+                "module_id",
+                "path",
+                "lang",
+                "category",
+                "purpose",
+                "is_header",
+                "source_hash",
+            ],
+            CsvTable::Edges => &["id", "from_sym", "to_sym", "from_module", "to_module", "kind", "weight"],
+            CsvTable::Occurrences => &[
+                "id",
+                "symbol_id",
+                "file_id",
+                "usage_kind",
+                "line",
+                "column",
+                "end_line",
+                "end_column",
+            ],
+            CsvTable::Modules => &["id", "project_id", "name", "kind", "path"],
+        }
+    }
+}
+
+/// Row filters of a CSV export; each only applies to its own table
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvFilter<'a> {
+    /// Keep files in this language (`Files`)
+    pub lang: Option<&'a str>,
+    /// Keep edges of this kind (`Edges`)
+    pub kind: Option<&'a str>,
+}
+
+impl SymgraphDb {
+    /// Write `table` as CSV with a header row; returns the number of data rows.
+    pub fn export_csv(&self, table: CsvTable, filter: CsvFilter, output: &mut dyn Write) -> Result<usize> {
+        let mut writer = csv::Writer::from_writer(output);
+        writer.write_record(table.headers())?;
+
+        let mut count = 0;
+        match table {
+            CsvTable::Symbols => {
+                for s in self.rows::<Symbol>("symbol:", |s| &s.id) {
+                    let s = s?;
+                    let metadata = if s.metadata.is_empty() {
+                        String::new()
+                    } else {
+                        let sorted: std::collections::BTreeMap<_, _> = s.metadata.iter().collect();
+                        serde_json::to_string(&sorted)?
+                    };
+                    writer.write_record([
+                        s.id,
+                        s.file_id,
+                        cell(s.usr),
+                        cell(s.key),
+                        s.name,
+                        s.kind,
+                        s.is_definition.to_string(),
+                        cell(s.cfg),
+                        metadata,
+                        cell(s.template_args),
+                        cell(s.def_start_line),
+                        cell(s.def_end_line),
+                        cell(s.documentation),
+                        cell(s.parent_usr),
+                    ])?;
+                    count += 1;
+                }
+            }
+            CsvTable::Files => {
+                for f in self.rows::<File>("file:", |f| &f.id) {
+                    let f = f?;
+                    if filter.lang.is_some_and(|lang| f.lang != lang) {
+                        continue;
+                    }
+                    writer.write_record([
+                        f.id,
+                        f.project_id,
+                        cell(f.module_id),
+                        f.path,
+                        f.lang,
+                        cell(f.category),
+                        cell(f.purpose),
+                        f.is_header.to_string(),
+                        cell(f.source_hash),
+                    ])?;
+                    count += 1;
+                }
+            }
+            CsvTable::Edges => {
+                for e in self.rows::<Edge>("edge:", |e| &e.id) {
+                    let e = e?;
+                    if filter.kind.is_some_and(|kind| e.kind != kind) {
+                        continue;
+                    }
+                    writer.write_record([
+                        e.id,
+                        cell(e.from_sym),
+                        cell(e.to_sym),
+                        cell(e.from_module),
+                        cell(e.to_module),
+                        e.kind,
+                        e.weight.to_string(),
+                    ])?;
+                    count += 1;
+                }
+            }
+            CsvTable::Occurrences => {
+                for o in self.rows::<Occurrence>("occurrence:", |o| &o.id) {
+                    let o = o?;
+                    writer.write_record([
+                        o.id,
+                        o.symbol_id,
+                        o.file_id,
+                        o.usage_kind,
+                        o.line.to_string(),
+                        o.column.to_string(),
+                        cell(o.end_line),
+                        cell(o.end_column),
+                    ])?;
+                    count += 1;
+                }
+            }
+            CsvTable::Modules => {
+                for m in self.rows::<Module>("module:", |m| &m.id) {
+                    let m = m?;
+                    writer.write_record([m.id, m.project_id, m.name, m.kind, cell(m.path)])?;
+                    count += 1;
+                }
+            }
+        }
+
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// Rows under `prefix`, skipping the name/path copies of files and modules
+    fn rows<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        prefix: &'static str,
+        id_of: fn(&T) -> &String,
+    ) -> impl Iterator<Item = Result<T>> + 'a {
+        self.db.scan_prefix(prefix).filter_map(move |item| {
+            let (key, value) = match item {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let row: T = serde_json::from_slice(&value).ok()?;
+            (key.as_ref()[prefix.len()..] == *id_of(&row).as_bytes()).then_some(Ok(row))
+        })
+    }
+}
+
+/// Optional value as a CSV cell; `None` becomes an empty string
+fn cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_export_csv_symbol_parent() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/shape.cpp", "c++").unwrap();
+        let area = insert_symbol(&mut db, &file_id, Some("c:@S@Shape@F@area#"), None, "area()", "Method", true).unwrap();
+        db.set_symbol_parent(&area, Some("c:@S@Shape")).unwrap();
+
+        let mut out = Vec::new();
+        db.export_csv(CsvTable::Symbols, CsvFilter::default(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].ends_with(",documentation,parent_usr"), "{}", lines[0]);
+        assert!(lines[1].ends_with(",c:@S@Shape"), "{}", lines[1]);
+    }
+
+    #[test]
+    fn test_export_csv_edges_filtered_by_kind() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let main_id = insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        let run_id = insert_symbol(&mut db, &file_id, Some("c:@F@run#"), None, "run", "FunctionDecl", true).unwrap();
+        let registry = EdgeKindRegistry::new();
+        insert_edge(&mut db, &registry, Some(&main_id), Some(&run_id), None, None, "call").unwrap();
+        insert_edge(&mut db, &registry, Some(&run_id), Some(&main_id), None, None, "type_ref").unwrap();
+
+        let mut out = Vec::new();
+        let filter = CsvFilter { kind: Some("call"), ..Default::default() };
+        let count = db.export_csv(CsvTable::Edges, filter, &mut out).unwrap();
+        assert_eq!(count, 1);
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "id,from_sym,to_sym,from_module,to_module,kind,weight");
+        // Missing modules are empty cells
+        assert!(lines[1].ends_with(&format!("{},{},,,call,1", main_id, run_id)), "{}", lines[1]);
+    }
+}
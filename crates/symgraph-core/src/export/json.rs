@@ -0,0 +1,115 @@
+//! JSON snapshot export
+//!
+//! Writes the whole database as a single JSON object with one array per
+//! collection. Rows are streamed from sled straight into the writer, so memory
+//! use does not grow with the size of the database.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::io::Write;
+use std::marker::PhantomData;
+
+use crate::{Edge, File, Module, Occurrence, Symbol, SymgraphDb};
+
+impl SymgraphDb {
+    /// Serialize files, modules, symbols, occurrences and edges as one JSON object.
+    pub fn export_json(&self, output: &mut dyn Write) -> Result<()> {
+        let mut serializer = serde_json::Serializer::new(output);
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("files", &Rows::<File>::new(self, "file:", |f| &f.id))?;
+        map.serialize_entry("modules", &Rows::<Module>::new(self, "module:", |m| &m.id))?;
+        map.serialize_entry("symbols", &Rows::<Symbol>::new(self, "symbol:", |s| &s.id))?;
+        map.serialize_entry("occurrences", &Rows::<Occurrence>::new(self, "occurrence:", |o| &o.id))?;
+        map.serialize_entry("edges", &Rows::<Edge>::new(self, "edge:", |e| &e.id))?;
+        SerializeMap::end(map)?;
+        Ok(())
+    }
+}
+
+/// Lazily serialized rows under a key prefix.
+///
+/// Files and modules are stored under both their ID and their name/path;
+/// only the `prefix + id` copy is emitted so each row appears once.
+struct Rows<'a, T> {
+    db: &'a SymgraphDb,
+    prefix: &'static str,
+    id_of: fn(&T) -> &String,
+    _row: PhantomData<T>,
+}
+
+impl<'a, T> Rows<'a, T> {
+    fn new(db: &'a SymgraphDb, prefix: &'static str, id_of: fn(&T) -> &String) -> Self {
+        Self {
+            db,
+            prefix,
+            id_of,
+            _row: PhantomData,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Serialize for Rows<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for item in self.db.db.scan_prefix(self.prefix) {
+            let (key, value) = item.map_err(S::Error::custom)?;
+            let row: T = match serde_json::from_slice(&value) {
+                Ok(row) => row,
+                Err(_) => continue,
+            };
+            if key.as_ref()[self.prefix.len()..] != *(self.id_of)(&row).as_bytes() {
+                continue;
+            }
+            seq.serialize_element(&row)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_occurrence, insert_symbol, upsert_module, EdgeKindRegistry};
+
+    #[derive(serde::Deserialize)]
+    struct Snapshot {
+        files: Vec<File>,
+        modules: Vec<Module>,
+        symbols: Vec<Symbol>,
+        occurrences: Vec<Occurrence>,
+        edges: Vec<Edge>,
+    }
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let module_id = upsert_module(&mut db, "app", "cpp20-module", "src/app.cppm").unwrap();
+        let main_id = insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        let run_id = insert_symbol(&mut db, &file_id, Some("c:@F@run#"), None, "run", "FunctionDecl", true).unwrap();
+        insert_occurrence(&mut db, &run_id, &file_id, "call", 3, 5, None, None).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main_id), Some(&run_id), None, None, "call").unwrap();
+
+        let mut out = Vec::new();
+        db.export_json(&mut out).unwrap();
+        let snapshot: Snapshot = serde_json::from_slice(&out).unwrap();
+
+        // Files and modules are stored twice but exported once
+        assert_eq!(snapshot.files.len(), 1);
+        assert_eq!(snapshot.files[0].id, file_id);
+        assert_eq!(snapshot.files[0].path, "src/main.cpp");
+        assert_eq!(snapshot.modules.len(), 1);
+        assert_eq!(snapshot.modules[0].id, module_id);
+
+        let mut names: Vec<_> = snapshot.symbols.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["main", "run"]);
+        assert_eq!(snapshot.occurrences.len(), 1);
+        assert_eq!(snapshot.occurrences[0].symbol_id, run_id);
+        assert_eq!(snapshot.edges.len(), 1);
+        assert_eq!(snapshot.edges[0].from_sym.as_deref(), Some(main_id.as_str()));
+        assert_eq!(snapshot.edges[0].to_sym.as_deref(), Some(run_id.as_str()));
+    }
+}
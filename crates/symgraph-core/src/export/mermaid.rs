@@ -0,0 +1,143 @@
+//! Mermaid flowchart export
+//!
+//! Emits edges of a single kind (e.g. the call graph) as a `graph TD` flowchart
+//! that renders natively in GitHub Markdown and most wikis.
+
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+
+use crate::SymgraphDb;
+
+/// Export edges of `kind` as a Mermaid flowchart.
+///
+/// When `root_usr` is given, only the subgraph reachable from that symbol within
+/// `max_depth` hops is exported; otherwise every edge of that kind is written.
+pub fn export_mermaid(
+    db: &SymgraphDb,
+    kind: &str,
+    root_usr: Option<&str>,
+    max_depth: u8,
+    output: &mut dyn Write,
+) -> Result<()> {
+    let pairs = match root_usr {
+        Some(usr) => collect_subgraph(db, kind, usr, max_depth)?,
+        None => db
+            .edges_by_kind(kind)?
+            .into_iter()
+            .filter_map(|e| Some((e.from_sym?, e.to_sym?)))
+            .collect(),
+    };
+
+    writeln!(output, "graph TD")?;
+    for (from_id, to_id) in pairs {
+        if let (Some(from), Some(to)) = (db.get_symbol(&from_id)?, db.get_symbol(&to_id)?) {
+            writeln!(
+                output,
+                "    {}[\"{}\"] --> {}[\"{}\"]",
+                node_id(&from.name),
+                escape_label(&from.name),
+                node_id(&to.name),
+                escape_label(&to.name)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Breadth-first walk from `root_usr`, returning `(from_id, to_id)` pairs
+fn collect_subgraph(
+    db: &SymgraphDb,
+    kind: &str,
+    root_usr: &str,
+    max_depth: u8,
+) -> Result<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+
+    let root_id = match db.find_symbol_by_usr(root_usr)? {
+        Some(id) => id,
+        None => return Ok(pairs),
+    };
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(root_id.clone());
+    queue.push_back((root_id, 0u8));
+
+    while let Some((sym_id, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        for edge in db.edges_from(&sym_id, kind)? {
+            if let Some(to_id) = edge.to_sym {
+                pairs.push((sym_id.clone(), to_id.clone()));
+                if visited.insert(to_id.clone()) {
+                    queue.push_back((to_id, depth + 1));
+                }
+            }
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// Turn a symbol name into a valid Mermaid node ID
+fn node_id(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if id.is_empty() {
+        "_".to_string()
+    } else {
+        id
+    }
+}
+
+/// Escape a symbol name for use inside a quoted Mermaid label
+fn escape_label(name: &str) -> String {
+    name.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_export_mermaid_call_graph() {
+        let mut db = SymgraphDb::open("test_db_mermaid").unwrap();
+        let file_id = db.ensure_file("app.cpp", "c++").unwrap();
+
+        let main_id = insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        let foo_id = insert_symbol(&mut db, &file_id, Some("c:@F@foo#"), None, "foo", "FunctionDecl", true).unwrap();
+        let bar_id = insert_symbol(&mut db, &file_id, Some("c:@F@bar#"), None, "bar", "FunctionDecl", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main_id), Some(&foo_id), None, None, "call").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&foo_id), Some(&bar_id), None, None, "call").unwrap();
+
+        let mut out = Vec::new();
+        export_mermaid(&db, "call", Some("c:@F@main#"), 3, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("graph TD"));
+        assert!(text.contains("main[\"main\"] --> foo[\"foo\"]"));
+        assert!(text.contains("foo[\"foo\"] --> bar[\"bar\"]"));
+
+        // Depth limit cuts the graph after the first hop
+        let mut out = Vec::new();
+        export_mermaid(&db, "call", Some("c:@F@main#"), 1, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("main[\"main\"] --> foo[\"foo\"]"));
+        assert!(!text.contains("--> bar"));
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_mermaid").ok();
+    }
+
+    #[test]
+    fn test_node_id_sanitization() {
+        assert_eq!(node_id("ns::func"), "ns__func");
+        assert_eq!(node_id("operator<<"), "operator__");
+        assert_eq!(escape_label("say \"hi\""), "say #quot;hi#quot;");
+    }
+}
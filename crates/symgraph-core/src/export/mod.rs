@@ -0,0 +1,14 @@
+//! Exporters that render the symbol graph in external diagram and database formats.
+
+pub mod cscope;
+pub mod csv;
+pub mod json;
+pub mod mermaid;
+pub mod plantuml;
+pub mod sequence;
+
+pub use cscope::export_cscope;
+pub use self::csv::{CsvFilter, CsvTable};
+pub use mermaid::export_mermaid;
+pub use plantuml::export_plantuml;
+pub use sequence::export_sequence;
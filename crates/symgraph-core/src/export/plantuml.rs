@@ -0,0 +1,90 @@
+//! PlantUML class diagram export
+//!
+//! Renders classes with their `member` edges as class bodies and `inherit`
+//! edges as generalization arrows.
+
+use anyhow::Result;
+use std::io::Write;
+
+use crate::SymgraphDb;
+
+/// Symbol kinds rendered as classes in the diagram
+const CLASS_KINDS: &[&str] = &["ClassDecl", "StructDecl"];
+
+/// Export classes, their members and inheritance relations as a PlantUML class diagram.
+pub fn export_plantuml(db: &SymgraphDb, output: &mut dyn Write) -> Result<()> {
+    writeln!(output, "@startuml")?;
+
+    let mut classes: Vec<_> = db
+        .list_symbols()?
+        .into_iter()
+        .filter(|s| CLASS_KINDS.contains(&s.kind.as_str()))
+        .collect();
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for class in &classes {
+        writeln!(output, "class {} {{", class.name)?;
+        for edge in db.edges_from(&class.id, "member")? {
+            let member = match edge.to_sym {
+                Some(id) => db.get_symbol(&id)?,
+                None => None,
+            };
+            if let Some(member) = member {
+                if member.kind == "Method" {
+                    writeln!(output, "  {}()", member.name)?;
+                } else {
+                    writeln!(output, "  {}", member.name)?;
+                }
+            }
+        }
+        writeln!(output, "}}")?;
+    }
+
+    // Inherit edges point from the base class to the derived class
+    for edge in db.edges_by_kind("inherit")? {
+        if let (Some(base_id), Some(derived_id)) = (&edge.from_sym, &edge.to_sym) {
+            if let (Some(base), Some(derived)) = (db.get_symbol(base_id)?, db.get_symbol(derived_id)?) {
+                writeln!(output, "{} <|-- {}", base.name, derived.name)?;
+            }
+        }
+    }
+
+    writeln!(output, "@enduml")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_export_plantuml_inheritance() {
+        let mut db = SymgraphDb::open("test_db_plantuml").unwrap();
+        let file_id = db.ensure_file("shapes.cpp", "c++").unwrap();
+
+        let shape_id = insert_symbol(&mut db, &file_id, Some("c:@S@Shape"), None, "Shape", "ClassDecl", true).unwrap();
+        let circle_id = insert_symbol(&mut db, &file_id, Some("c:@S@Circle"), None, "Circle", "ClassDecl", true).unwrap();
+        let radius_id = insert_symbol(&mut db, &file_id, Some("c:@S@Circle@FI@radius"), None, "radius", "FieldDecl", true).unwrap();
+        let area_id = insert_symbol(&mut db, &file_id, Some("c:@S@Circle@F@area#"), None, "area", "Method", true).unwrap();
+
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&shape_id), Some(&circle_id), None, None, "inherit").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&circle_id), Some(&radius_id), None, None, "member").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&circle_id), Some(&area_id), None, None, "member").unwrap();
+
+        let mut out = Vec::new();
+        export_plantuml(&db, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.starts_with("@startuml"));
+        assert!(text.trim_end().ends_with("@enduml"));
+        assert!(text.contains("class Shape {"));
+        assert!(text.contains("class Circle {"));
+        assert!(text.contains("  radius\n"));
+        assert!(text.contains("  area()\n"));
+        assert!(text.contains("Shape <|-- Circle"));
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_plantuml").ok();
+    }
+}
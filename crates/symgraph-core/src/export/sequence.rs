@@ -0,0 +1,135 @@
+//! PlantUML sequence diagram export
+//!
+//! Linearizes the static call graph below one function into a sequence
+//! diagram: a depth-first walk over `call` edges emits one message per call.
+//! A call back into a function that is still on the walk is drawn once with
+//! a `note` marking the recursion instead of being followed again.
+
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::SymgraphDb;
+
+/// Export the call chain below `start_usr` as a PlantUML sequence diagram.
+///
+/// Calls are followed at most `max_depth` levels below the start function.
+pub fn export_sequence(db: &SymgraphDb, start_usr: &str, max_depth: u8, output: &mut dyn Write) -> Result<()> {
+    let Some(start_id) = db.find_symbol_by_usr(start_usr)? else {
+        bail!("Symbol with USR '{}' not found", start_usr);
+    };
+
+    let mut walk = SequenceWalk::default();
+    let start = walk.participant(db, &start_id)?;
+    walk.visit(db, &start_id, start, max_depth)?;
+
+    writeln!(output, "@startuml")?;
+    for (alias, name) in &walk.participants {
+        writeln!(output, "participant \"{}\" as {}", name.replace('"', "'"), alias)?;
+    }
+    for line in &walk.messages {
+        writeln!(output, "{}", line)?;
+    }
+    writeln!(output, "@enduml")?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct SequenceWalk {
+    /// `(alias, display name)` in order of first appearance
+    participants: Vec<(String, String)>,
+    aliases: HashMap<String, usize>,
+    /// Symbol IDs on the current call chain
+    stack: Vec<String>,
+    messages: Vec<String>,
+}
+
+impl SequenceWalk {
+    /// Index of the participant for `symbol_id`, declaring it on first use
+    fn participant(&mut self, db: &SymgraphDb, symbol_id: &str) -> Result<usize> {
+        if let Some(index) = self.aliases.get(symbol_id) {
+            return Ok(*index);
+        }
+        let name = db
+            .get_symbol(symbol_id)?
+            .map(|s| s.name)
+            .unwrap_or_else(|| symbol_id.to_string());
+        let index = self.participants.len();
+        self.participants.push((format!("P{}", index), name));
+        self.aliases.insert(symbol_id.to_string(), index);
+        Ok(index)
+    }
+
+    fn visit(&mut self, db: &SymgraphDb, symbol_id: &str, caller: usize, depth: u8) -> Result<()> {
+        if depth == 0 {
+            return Ok(());
+        }
+        self.stack.push(symbol_id.to_string());
+
+        let mut callees: Vec<String> = db.edges_from(symbol_id, "call")?.into_iter().filter_map(|e| e.to_sym).collect();
+        callees.dedup();
+        for callee_id in callees {
+            let callee = self.participant(db, &callee_id)?;
+            let (from, (to, name)) = (&self.participants[caller].0, &self.participants[callee]);
+            self.messages.push(format!("{} -> {}: {}", from, to, label(name)));
+            if self.stack.contains(&callee_id) {
+                self.messages.push(format!("note right of {}: recursive call to {}", to, label(name)));
+                continue;
+            }
+            self.visit(db, &callee_id, callee, depth - 1)?;
+        }
+
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// Message label: the function name without its parameter list
+fn label(name: &str) -> &str {
+    name.split('(').next().unwrap_or(name).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, EdgeKindRegistry};
+
+    #[test]
+    fn test_export_sequence_linear_chain_and_recursion() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let file_id = db.ensure_file("app.cpp", "c++").unwrap();
+
+        let names = ["main", "load", "parse", "tokenize"];
+        let ids: Vec<String> = names
+            .iter()
+            .map(|n| insert_symbol(&mut db, &file_id, Some(&format!("c:@F@{}#", n)), None, n, "FunctionDecl", true).unwrap())
+            .collect();
+        for pair in ids.windows(2) {
+            insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&pair[0]), Some(&pair[1]), None, None, "call").unwrap();
+        }
+
+        let mut out = Vec::new();
+        export_sequence(&db, "c:@F@main#", 3, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("@startuml"));
+        assert!(text.trim_end().ends_with("@enduml"));
+        for (index, name) in names.iter().enumerate() {
+            assert!(text.contains(&format!("participant \"{}\" as P{}", name, index)));
+        }
+        assert!(text.contains("P0 -> P1: load"));
+        assert!(text.contains("P2 -> P3: tokenize"));
+
+        // Depth limit stops before the last call
+        let mut out = Vec::new();
+        export_sequence(&db, "c:@F@main#", 2, &mut out).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("tokenize"));
+
+        // A call back to main is noted instead of followed
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&ids[3]), Some(&ids[0]), None, None, "call").unwrap();
+        let mut out = Vec::new();
+        export_sequence(&db, "c:@F@main#", 10, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("P3 -> P0: main\nnote right of P0: recursive call to main"));
+        assert_eq!(text.matches("P0 -> P1").count(), 1);
+    }
+}
@@ -0,0 +1,78 @@
+//! Token index for full-text search on symbol names
+//!
+//! Every symbol name is split into lowercase tokens that are stored under
+//! `fts:{token}:{symbol_id}`, so a term is looked up by a prefix scan instead
+//! of reading every symbol. `getHttpClient` yields `gethttpclient`, `get`,
+//! `http` and `client`; `net::tcp_connect(int)` yields `net::tcp_connect`,
+//! `net`, `tcp` and `connect`.
+
+use serde::{Deserialize, Serialize};
+
+/// A symbol matching a full-text query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtsResult {
+    pub symbol_id: String,
+    pub name: String,
+    pub kind: String,
+    pub file_path: Option<String>,
+    /// Lower is better: 0 exact name, 1 name prefix, 2 whole word, 3 word prefix
+    pub rank: u32,
+}
+
+/// Lowercase search tokens of a symbol name
+pub fn name_tokens(name: &str) -> Vec<String> {
+    let base = name.split('(').next().unwrap_or(name).trim();
+    let mut tokens = vec![base.to_lowercase()];
+
+    let mut word = String::new();
+    let mut previous: Option<char> = None;
+    for c in base.chars() {
+        if !c.is_alphanumeric() {
+            push_token(&mut tokens, &mut word);
+        } else {
+            // camelCase and HTTPServer boundaries
+            if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+                push_token(&mut tokens, &mut word);
+            }
+            word.push(c);
+        }
+        previous = Some(c);
+    }
+    push_token(&mut tokens, &mut word);
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<String>, word: &mut String) {
+    let token = std::mem::take(word).to_lowercase();
+    if !token.is_empty() && !tokens.contains(&token) {
+        tokens.push(token);
+    }
+}
+
+/// Rank of `name` for one lowercase query term, if it matches
+pub(crate) fn rank_match(name: &str, term: &str) -> Option<u32> {
+    let tokens = name_tokens(name);
+    if tokens[0] == term {
+        Some(0)
+    } else if tokens[0].starts_with(term) {
+        Some(1)
+    } else if tokens.iter().any(|t| t == term) {
+        Some(2)
+    } else if tokens.iter().any(|t| t.starts_with(term)) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_tokens() {
+        assert_eq!(name_tokens("getHttpClient"), ["gethttpclient", "get", "http", "client"]);
+        assert_eq!(name_tokens("net::tcp_connect(int)"), ["net::tcp_connect", "net", "tcp", "connect"]);
+        assert_eq!(name_tokens("Config"), ["config"]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Stored edges as a `petgraph` graph
+//!
+//! [`to_petgraph_digraph`] loads the edges of one kind so `petgraph`'s
+//! algorithms can run on them. Each symbol or module at either end of an
+//! edge becomes one node, weighted with its name.
+
+use anyhow::Result;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::HashMap;
+
+use crate::{Module, SymgraphDb};
+
+/// Directed graph of the edges of `kind`; node weights are symbol or module names
+pub fn to_petgraph_digraph(db: &SymgraphDb, kind: &str) -> Result<DiGraph<String, ()>> {
+    let mut graph = DiGraph::new();
+    let mut nodes: HashMap<String, NodeIndex> = HashMap::new();
+    let mut node = |graph: &mut DiGraph<String, ()>, id: String, is_symbol: bool| -> Result<NodeIndex> {
+        if let Some(&index) = nodes.get(&id) {
+            return Ok(index);
+        }
+        let name = if is_symbol {
+            db.get_symbol(&id)?.map(|s| s.name)
+        } else {
+            db.db
+                .get(format!("module:{}", id))?
+                .map(|value| serde_json::from_slice::<Module>(&value).map(|m| m.name))
+                .transpose()?
+        };
+        let index = graph.add_node(name.unwrap_or_else(|| id.clone()));
+        nodes.insert(id, index);
+        Ok(index)
+    };
+
+    for edge in db.edges_by_kind(kind)? {
+        let ends = match (edge.from_sym, edge.to_sym, edge.from_module, edge.to_module) {
+            (Some(from), Some(to), _, _) => (from, to, true),
+            (_, _, Some(from), Some(to)) => (from, to, false),
+            _ => continue,
+        };
+        let from = node(&mut graph, ends.0, ends.2)?;
+        let to = node(&mut graph, ends.1, ends.2)?;
+        graph.add_edge(from, to, ());
+    }
+    Ok(graph)
+}
+
+impl SymgraphDb {
+    /// Groups of symbols or modules that reach each other over edges of `kind`.
+    ///
+    /// Found with Tarjan's strongly connected components; a single node counts
+    /// only when it has an edge to itself (direct recursion). Names are sorted
+    /// within each cycle, and cycles by their first name.
+    pub fn find_cycles(&self, kind: &str) -> Result<Vec<Vec<String>>> {
+        let graph = to_petgraph_digraph(self, kind)?;
+        let mut cycles: Vec<Vec<String>> = petgraph::algo::tarjan_scc(&graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || graph.contains_edge(scc[0], scc[0]))
+            .map(|scc| {
+                let mut names: Vec<String> = scc.into_iter().map(|index| graph[index].clone()).collect();
+                names.sort();
+                names
+            })
+            .collect();
+        cycles.sort();
+        Ok(cycles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{insert_edge, insert_symbol, upsert_module, EdgeKindRegistry};
+
+    #[test]
+    fn test_to_petgraph_digraph_round_trip() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let edge_kinds = EdgeKindRegistry::new();
+        let file_id = db.ensure_file("src/main.c", "c").unwrap();
+        let mut ids = HashMap::new();
+        for name in ["main", "parse", "lex", "unused"] {
+            let usr = format!("c:@F@{}", name);
+            ids.insert(name, insert_symbol(&mut db, &file_id, Some(&usr), None, name, "FunctionDecl", true).unwrap());
+        }
+        for (from, to) in [("main", "parse"), ("parse", "lex"), ("main", "lex")] {
+            insert_edge(&mut db, &edge_kinds, Some(&ids[from]), Some(&ids[to]), None, None, "call").unwrap();
+        }
+        insert_edge(&mut db, &edge_kinds, Some(&ids["unused"]), Some(&ids["main"]), None, None, "reference").unwrap();
+
+        let graph = to_petgraph_digraph(&db, "call").unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        let mut names: Vec<_> = graph.node_weights().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["lex", "main", "parse"]);
+        assert!(db.find_cycles("call").unwrap().is_empty());
+
+        // lex -> parse closes parse <-> lex; lex also recurses into itself
+        insert_edge(&mut db, &edge_kinds, Some(&ids["lex"]), Some(&ids["parse"]), None, None, "call").unwrap();
+        insert_edge(&mut db, &edge_kinds, Some(&ids["main"]), Some(&ids["main"]), None, None, "call").unwrap();
+        assert_eq!(db.find_cycles("call").unwrap(), vec![vec!["lex", "parse"], vec!["main"]]);
+    }
+
+    #[test]
+    fn test_module_graph_uses_module_names() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+        let app = upsert_module(&mut db, "app", "cpp20-module", "").unwrap();
+        let core = upsert_module(&mut db, "core", "cpp20-module", "").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), None, None, Some(&app), Some(&core), "module-import").unwrap();
+
+        let graph = to_petgraph_digraph(&db, "module-import").unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.node_weights().any(|name| name == "core"));
+    }
+}
@@ -1,19 +1,219 @@
 pub mod annotations;
+pub mod batch;
 pub mod scip;
+pub mod usr;
+pub mod fts;
 pub mod database;
+pub mod diff;
+pub mod lsif;
+pub mod migrations;
+pub mod export;
+pub mod algorithms;
+pub mod graph;
+pub mod maintenance;
+#[cfg(feature = "pool")]
+pub mod pool;
 
 // Re-export database types and functions for easier access
 pub use database::{
-    SymgraphDb, Project, Module, File, Symbol, Occurrence, Edge,
-    insert_symbol, insert_occurrence, insert_edge, upsert_module
+    SymgraphDb, BUSY_TIMEOUT, Project, Module, File, Symbol, Occurrence, Edge,
+    InheritDirection, InheritanceNode, SymbolMetrics, DeleteStats, UsageLocation,
+    insert_symbol, insert_occurrence, insert_edge, upsert_module, file_sha256
 };
 
+// Re-export atomic multi-record writes
+pub use batch::WriteBatch;
+
 // Re-export SCIP functions for easier access
 pub use scip::{parse_scip_file, parse_scip_bytes, load_scip_to_database};
 
+// Re-export full-text search results
+pub use fts::FtsResult;
+
+// Re-export USR formatting
+pub use usr::usr_to_display_name;
+
+// Re-export LSIF import
+pub use lsif::{import_lsif, import_lsif_file, LsifImportStats};
+
+// Re-export schema versioning
+pub use migrations::{schema_version, SCHEMA_VERSION};
+
+// Re-export database comparison
+pub use diff::{diff_databases, DbDiff};
+
+// Re-export graph exporters
+pub use export::{export_cscope, export_mermaid, export_plantuml, export_sequence, CsvFilter, CsvTable};
+
+// Re-export graph algorithm results
+pub use algorithms::WeightedPath;
+
+// Re-export petgraph conversion
+pub use graph::to_petgraph_digraph;
+
+// Re-export on-disk maintenance
+pub use maintenance::{vacuum_database, VacuumStats};
+
+// Re-export the handle pool
+#[cfg(feature = "pool")]
+pub use pool::{DbPool, PooledDb};
+
 // Legacy type alias for backward compatibility
 pub type Db = SymgraphDb;
 
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+impl SymgraphDb {
+    /// Return module names in build order: every module comes after the modules it imports.
+    ///
+    /// Uses Kahn's algorithm over `module-import` edges; ties are broken by module name
+    /// so the order is deterministic.
+    pub fn topological_sort_modules(&self) -> Result<Vec<String>> {
+        let names: HashMap<String, String> = self
+            .list_modules()?
+            .into_iter()
+            .map(|m| (m.id, m.name))
+            .collect();
+
+        // Edges point from the importer to the imported module, so the imported
+        // module must be built first: dependents[dep] lists its importers.
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut in_degree: HashMap<&str, usize> = names.keys().map(|id| (id.as_str(), 0)).collect();
+        let edges = self.edges_by_kind("module-import")?;
+        for edge in &edges {
+            if let (Some(from), Some(to)) = (edge.from_module.as_deref(), edge.to_module.as_deref()) {
+                if !names.contains_key(from) || !names.contains_key(to) {
+                    continue;
+                }
+                dependents.entry(to).or_default().push(from);
+                *in_degree.entry(from).or_default() += 1;
+            }
+        }
+
+        let mut ready: BTreeSet<(&str, &str)> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| (names[*id].as_str(), *id))
+            .collect();
+
+        let mut order = Vec::with_capacity(names.len());
+        while let Some((name, id)) = ready.pop_first() {
+            order.push(name.to_string());
+            for dependent in dependents.get(id).into_iter().flatten() {
+                let degree = in_degree.get_mut(dependent).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert((names[*dependent].as_str(), dependent));
+                }
+            }
+        }
+
+        if order.len() < names.len() {
+            // Name the modules on the cycle rather than everything stuck behind it
+            let cycle = self.find_cycles("module-import")?.into_iter().next().unwrap_or_default();
+            return Err(anyhow!("Cycle involving module {}", cycle.join(", ")));
+        }
+
+        Ok(order)
+    }
+
+    /// Number of symbols of each kind, most common first (ties by kind)
+    pub fn symbol_kind_distribution(&self) -> Result<Vec<(String, i64)>> {
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        for item in self.db.scan_prefix("symbol:") {
+            let (_, value) = item?;
+            let symbol: Symbol = serde_json::from_slice(&value)?;
+            *counts.entry(symbol.kind).or_default() += 1;
+        }
+        Ok(by_count(counts))
+    }
+
+    /// Number of files in each language, most common first (ties by language)
+    pub fn file_lang_distribution(&self) -> Result<Vec<(String, i64)>> {
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        for item in self.db.scan_prefix("file:") {
+            let (key, value) = item?;
+            let file: File = serde_json::from_slice(&value)?;
+            // Files are stored under both their path and their ID
+            if key.as_ref() == format!("file:{}", file.id).as_bytes() {
+                *counts.entry(file.lang).or_default() += 1;
+            }
+        }
+        Ok(by_count(counts))
+    }
+}
+
+fn by_count(counts: HashMap<String, i64>) -> Vec<(String, i64)> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Edge kinds [`insert_edge`] accepts.
+///
+/// Scanners write edge kinds as plain strings, so a typo would otherwise
+/// create a kind no query looks for.
+#[derive(Debug, Clone)]
+pub struct EdgeKindRegistry {
+    allowed: HashSet<String>,
+}
+
+impl EdgeKindRegistry {
+    /// Registry with every kind the bundled scanners produce
+    pub fn new() -> Self {
+        let allowed = [
+            "call",
+            "inherit",
+            "member",
+            "module-import",
+            "module-partition",
+            "type_ref",
+            "impl_trait",
+            "namespace-member",
+            "override",
+            "include",
+            "conan-dep",
+            "cargo-dep",
+            // C++ scanner
+            "instantiates",
+            "namespace-alias",
+            "objc-category",
+            "constructs",
+            "destructs",
+            // SCIP relationships
+            "reference",
+            "implementation",
+            "type_definition",
+            "definition",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+        Self { allowed }
+    }
+
+    /// Allow a kind that is not built in
+    pub fn register(&mut self, kind: &str) {
+        self.allowed.insert(kind.to_string());
+    }
+
+    /// Fail if `kind` has not been registered
+    pub fn validate(&self, kind: &str) -> Result<()> {
+        if self.allowed.contains(kind) {
+            Ok(())
+        } else {
+            Err(anyhow!("Unknown edge kind '{}'", kind))
+        }
+    }
+}
+
+impl Default for EdgeKindRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,9 +330,9 @@ mod tests {
         .unwrap();
 
         // Добавляем несколько мест использования
-        let occ1 = insert_occurrence(&mut db, &sym_id, &file_id, "call", 10, 5).unwrap();
-        let occ2 = insert_occurrence(&mut db, &sym_id, &file_id, "call", 25, 8).unwrap();
-        let occ3 = insert_occurrence(&mut db, &sym_id, &file_id, "reference", 42, 12).unwrap();
+        let occ1 = insert_occurrence(&mut db, &sym_id, &file_id, "call", 10, 5, None, None).unwrap();
+        let occ2 = insert_occurrence(&mut db, &sym_id, &file_id, "call", 25, 8, None, None).unwrap();
+        let occ3 = insert_occurrence(&mut db, &sym_id, &file_id, "reference", 42, 12, None, None).unwrap();
 
         assert!(!occ1.is_empty());
         assert!(!occ2.is_empty());
@@ -142,6 +342,25 @@ mod tests {
         std::fs::remove_dir_all("test_db_5").ok();
     }
 
+    /// Демонстрация: место использования с полным диапазоном
+    #[test]
+    fn test_occurrence_with_range() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("main.cpp", "c++").unwrap();
+        let sym_id = insert_symbol(&mut db, &file_id, Some("c:@F@print#"), None, "print", "FunctionDecl", true).unwrap();
+
+        insert_occurrence(&mut db, &sym_id, &file_id, "call", 10, 5, Some(10), Some(10)).unwrap();
+        insert_occurrence(&mut db, &sym_id, &file_id, "reference", 12, 3, None, None).unwrap();
+
+        let mut occurrences = db.occurrences_of(&sym_id).unwrap();
+        occurrences.sort_by_key(|o| o.line);
+        assert_eq!(
+            (occurrences[0].line, occurrences[0].column, occurrences[0].end_line, occurrences[0].end_column),
+            (10, 5, Some(10), Some(10))
+        );
+        assert_eq!((occurrences[1].end_line, occurrences[1].end_column), (None, None));
+    }
+
     /// Демонстрация: создание графа вызовов (call graph)
     #[test]
     fn test_call_graph() {
@@ -193,6 +412,7 @@ mod tests {
         // main() вызывает foo() и bar()
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&main_id),
             Some(&foo_id),
             None,
@@ -202,6 +422,7 @@ mod tests {
         .unwrap();
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&main_id),
             Some(&bar_id),
             None,
@@ -211,7 +432,7 @@ mod tests {
         .unwrap();
 
         // foo() вызывает baz()
-        insert_edge(&mut db, Some(&foo_id), Some(&baz_id), None, None, "call").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&foo_id), Some(&baz_id), None, None, "call").unwrap();
 
         // Запрос: кого вызывает main?
         let callees = db.query_edges_by_kind_from("call", "c:@F@main#").unwrap();
@@ -268,6 +489,7 @@ mod tests {
         // Derived наследует от Base
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&base_id),
             Some(&derived_id),
             None,
@@ -278,6 +500,7 @@ mod tests {
         // Child наследует от Derived
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&derived_id),
             Some(&child_id),
             None,
@@ -349,6 +572,7 @@ mod tests {
         // Связи членства
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&person_id),
             Some(&name_id),
             None,
@@ -358,6 +582,7 @@ mod tests {
         .unwrap();
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&person_id),
             Some(&age_id),
             None,
@@ -367,6 +592,7 @@ mod tests {
         .unwrap();
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             Some(&person_id),
             Some(&get_name_id),
             None,
@@ -401,6 +627,7 @@ mod tests {
         // main импортирует foo и bar
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             None,
             None,
             Some(&main_mod),
@@ -410,6 +637,7 @@ mod tests {
         .unwrap();
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             None,
             None,
             Some(&main_mod),
@@ -421,6 +649,7 @@ mod tests {
         // bar импортирует foo
         insert_edge(
             &mut db,
+            &EdgeKindRegistry::new(),
             None,
             None,
             Some(&bar_mod),
@@ -472,4 +701,654 @@ mod tests {
         drop(db);
         std::fs::remove_dir_all("test_db_10").ok();
     }
+
+    /// Демонстрация: порядок сборки модулей (топологическая сортировка)
+    #[test]
+    fn test_topological_sort_modules() {
+        let mut db = Db::open("test_db_topo").unwrap();
+
+        let core = upsert_module(&mut db, "core", "cpp20-module", "src/core.cppm").unwrap();
+        let util = upsert_module(&mut db, "util", "cpp20-module", "src/util.cppm").unwrap();
+        let net = upsert_module(&mut db, "net", "cpp20-module", "src/net.cppm").unwrap();
+        let app = upsert_module(&mut db, "app", "cpp20-module", "src/app.cpp").unwrap();
+
+        // app -> net -> util -> core, app -> core
+        for (from, to) in [(&app, &net), (&net, &util), (&util, &core), (&app, &core)] {
+            insert_edge(&mut db, &EdgeKindRegistry::new(), None, None, Some(from), Some(to), "module-import").unwrap();
+        }
+
+        let order = db.topological_sort_modules().unwrap();
+        let pos = |name: &str| order.iter().position(|n| n == name).unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(pos("core") < pos("util"));
+        assert!(pos("util") < pos("net"));
+        assert!(pos("net") < pos("app"));
+
+        // core импортирует app: цикл
+        insert_edge(&mut db, &EdgeKindRegistry::new(), None, None, Some(&core), Some(&app), "module-import").unwrap();
+        let err = db.topological_sort_modules().unwrap_err();
+        assert!(err.to_string().starts_with("Cycle involving module"));
+        assert_eq!(err.to_string(), "Cycle involving module app, core, net, util");
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_topo").ok();
+    }
+
+    /// Демонстрация: выборка символов по виду (например, макросов)
+    #[test]
+    fn test_symbols_by_kind() {
+        let mut db = Db::open("test_db_by_kind").unwrap();
+        let file_id = db.ensure_file("config.h", "c").unwrap();
+
+        insert_symbol(&mut db, &file_id, Some("c:config.h@8@macro@MAX_SIZE"), None, "MAX_SIZE", "MacroDefinition", true).unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@F@init#"), None, "init", "FunctionDecl", true).unwrap();
+
+        let macros = db.symbols_by_kind("MacroDefinition").unwrap();
+        assert_eq!(macros.len(), 1);
+        assert_eq!(macros[0].name, "MAX_SIZE");
+
+        let file = db.find_file_by_path("config.h").unwrap().unwrap();
+        assert_eq!(file.id, file_id);
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_by_kind").ok();
+    }
+
+    /// Демонстрация: иерархия наследования (предки и потомки)
+    #[test]
+    fn test_query_inheritance_tree() {
+        let mut db = Db::open("test_db_inherit_tree").unwrap();
+        let file_id = db.ensure_file("shapes.h", "c++").unwrap();
+
+        let shape = insert_symbol(&mut db, &file_id, Some("c:@S@Shape"), None, "Shape", "ClassDecl", true).unwrap();
+        let polygon = insert_symbol(&mut db, &file_id, Some("c:@S@Polygon"), None, "Polygon", "ClassDecl", true).unwrap();
+        let square = insert_symbol(&mut db, &file_id, Some("c:@S@Square"), None, "Square", "ClassDecl", true).unwrap();
+
+        // Рёбра inherit направлены от базового класса к производному
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&shape), Some(&polygon), None, None, "inherit").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&polygon), Some(&square), None, None, "inherit").unwrap();
+
+        let node = |name: &str, depth: u8| InheritanceNode { name: name.to_string(), depth };
+
+        let descendants = db.query_inheritance_tree("c:@S@Shape", InheritDirection::Descendants).unwrap();
+        assert_eq!(descendants, vec![node("Shape", 0), node("Polygon", 1), node("Square", 2)]);
+
+        let ancestors = db.query_inheritance_tree("c:@S@Square", InheritDirection::Ancestors).unwrap();
+        assert_eq!(ancestors, vec![node("Square", 0), node("Polygon", 1), node("Shape", 2)]);
+
+        assert!(db.query_inheritance_tree("c:@S@Missing", InheritDirection::Ancestors).unwrap().is_empty());
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_inherit_tree").ok();
+    }
+
+    /// Демонстрация: определения символов в конкретном файле
+    #[test]
+    fn test_find_symbols_in_file() {
+        let mut db = Db::open("test_db_symbols_in_file").unwrap();
+        let main_cpp = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let util_cpp = db.ensure_file("src/util.cpp", "c++").unwrap();
+
+        let main_id = insert_symbol(&mut db, &main_cpp, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        insert_symbol(&mut db, &main_cpp, Some("c:@F@helper#"), None, "helper", "FunctionDecl", false).unwrap();
+        insert_symbol(&mut db, &util_cpp, Some("c:@F@helper#"), None, "helper", "FunctionDecl", true).unwrap();
+
+        // Объявления и символы других файлов не попадают в выборку
+        let rows = db.find_symbols_in_file("src/main.cpp").unwrap();
+        assert_eq!(rows, vec![(main_id, "main".to_string(), "FunctionDecl".to_string(), true)]);
+        assert!(db.find_symbols_in_file("src/missing.cpp").unwrap().is_empty());
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_symbols_in_file").ok();
+    }
+
+    /// Демонстрация: распределение символов по видам и файлов по языкам
+    #[test]
+    fn test_kind_and_lang_distribution() {
+        let mut db = Db::open_temporary().unwrap();
+        let main_cpp = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let util_cpp = db.ensure_file("src/util.cpp", "c++").unwrap();
+        let lib_rs = db.ensure_file("src/lib.rs", "rust").unwrap();
+        insert_symbol(&mut db, &main_cpp, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        insert_symbol(&mut db, &util_cpp, Some("c:@F@helper#"), None, "helper", "FunctionDecl", true).unwrap();
+        insert_symbol(&mut db, &util_cpp, Some("c:@S@Point"), None, "Point", "StructDecl", true).unwrap();
+        insert_symbol(&mut db, &lib_rs, None, None, "parse", "function", true).unwrap();
+
+        assert_eq!(
+            db.symbol_kind_distribution().unwrap(),
+            vec![
+                ("FunctionDecl".to_string(), 2),
+                ("StructDecl".to_string(), 1),
+                ("function".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            db.file_lang_distribution().unwrap(),
+            vec![("c++".to_string(), 2), ("rust".to_string(), 1)]
+        );
+    }
+
+    /// Демонстрация: перенос проекта в другой каталог
+    #[test]
+    fn test_remap_paths() {
+        let mut db = Db::open("test_db_remap_paths").unwrap();
+        let main_cpp = db.ensure_file("/home/alice/project/src/main.cpp", "c++").unwrap();
+        db.ensure_file("/home/alice/project2/main.cpp", "c++").unwrap();
+        upsert_module(&mut db, "core", "cmake", "/home/alice/project/core").unwrap();
+        let main_id = insert_symbol(&mut db, &main_cpp, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+
+        // Соседний каталог с тем же префиксом не затрагивается
+        assert_eq!(db.remap_paths("/home/alice/project", "/home/bob/project").unwrap(), 2);
+
+        let rows = db.find_symbols_in_file("/home/bob/project/src/main.cpp").unwrap();
+        assert_eq!(rows, vec![(main_id, "main".to_string(), "FunctionDecl".to_string(), true)]);
+        assert!(db.find_symbols_in_file("/home/alice/project/src/main.cpp").unwrap().is_empty());
+        assert!(db.find_file_by_path("/home/alice/project2/main.cpp").unwrap().is_some());
+        let module = db.list_modules().unwrap().into_iter().find(|m| m.name == "core").unwrap();
+        assert_eq!(module.path.as_deref(), Some("/home/bob/project/core"));
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_remap_paths").ok();
+    }
+
+    /// Демонстрация: вхождения символа и файл, в котором они находятся
+    #[test]
+    fn test_occurrences_of() {
+        let mut db = Db::open("test_db_occurrences_of").unwrap();
+        let file_id = db.ensure_file("src/shapes.cpp", "c++").unwrap();
+        let concept = insert_symbol(&mut db, &file_id, Some("c:@CT@Printable"), None, "Printable", "ConceptDecl", true).unwrap();
+        let other = insert_symbol(&mut db, &file_id, Some("c:@F@draw#"), None, "draw", "FunctionDecl", true).unwrap();
+
+        insert_occurrence(&mut db, &concept, &file_id, "concept_use", 3, 31, None, None).unwrap();
+        insert_occurrence(&mut db, &other, &file_id, "call", 7, 5, None, None).unwrap();
+
+        let occurrences = db.occurrences_of(&concept).unwrap();
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].usage_kind, "concept_use");
+        assert_eq!((occurrences[0].line, occurrences[0].column), (3, 31));
+
+        let file = db.get_file(&occurrences[0].file_id).unwrap().unwrap();
+        assert_eq!(file.path, "src/shapes.cpp");
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_occurrences_of").ok();
+    }
+
+    /// Демонстрация: удаление символов файла перед повторным сканированием
+    #[test]
+    fn test_remove_symbols_in_file() {
+        let mut db = Db::open("test_db_remove_symbols").unwrap();
+        let header = db.ensure_file("include/util.h", "c++").unwrap();
+        let source = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let helper = insert_symbol(&mut db, &header, Some("c:@F@helper#"), None, "helper", "FunctionDecl", true).unwrap();
+        let main = insert_symbol(&mut db, &source, Some("c:@F@main#"), None, "main", "FunctionDecl", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&main), Some(&helper), None, None, "call").unwrap();
+        insert_occurrence(&mut db, &helper, &source, "call", 4, 5, None, None).unwrap();
+        insert_occurrence(&mut db, &helper, &header, "definition", 1, 6, None, None).unwrap();
+
+        assert_eq!(db.remove_symbols_in_file("src/main.cpp").unwrap(), 1);
+
+        // Символы и вызовы из main.cpp удалены, символы заголовка остались
+        assert!(db.find_symbol_by_usr("c:@F@main#").unwrap().is_none());
+        assert!(db.find_symbols_in_file("src/main.cpp").unwrap().is_empty());
+        assert!(db.edges_by_kind("call").unwrap().is_empty());
+        let occurrences = db.occurrences_of(&helper).unwrap();
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].usage_kind, "definition");
+        assert_eq!(db.find_symbol_by_usr("c:@F@helper#").unwrap(), Some(helper));
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_remove_symbols").ok();
+    }
+
+    /// Демонстрация: при повторном сканировании вызываемого файла входящие рёбра не повисают
+    #[test]
+    fn test_remove_symbols_in_file_drops_incoming_edges() {
+        let mut db = Db::open_temporary().unwrap();
+        let caller_file = db.ensure_file("src/a.cpp", "c++").unwrap();
+        let callee_file = db.ensure_file("src/b.cpp", "c++").unwrap();
+        let caller = insert_symbol(&mut db, &caller_file, Some("c:@F@run#"), None, "run", "FunctionDecl", true).unwrap();
+        let callee = insert_symbol(&mut db, &callee_file, Some("c:@F@work#"), None, "work", "FunctionDecl", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&caller), Some(&callee), None, None, "call").unwrap();
+        insert_occurrence(&mut db, &callee, &callee_file, "definition", 1, 6, None, None).unwrap();
+
+        // b.cpp сканируется заново: старый символ удаляется, вместе с ним и вызов из a.cpp
+        assert_eq!(db.remove_symbols_in_file("src/b.cpp").unwrap(), 1);
+        assert!(db.edges_by_kind("call").unwrap().is_empty());
+        assert!(db.edges_from(&caller, "call").unwrap().is_empty());
+        assert_eq!(db.db.scan_prefix("edges_to:").count(), 0);
+        assert_eq!(db.db.scan_prefix("occurrence").count(), 0);
+        assert!(db.find_symbol_by_usr("c:@F@run#").unwrap().is_some());
+    }
+
+    /// Демонстрация: метаданные символа (например, признак корутины)
+    #[test]
+    fn test_symbols_with_metadata() {
+        let mut db = Db::open("test_db_symbol_metadata").unwrap();
+        let file_id = db.ensure_file("src/tasks.cppm", "c++").unwrap();
+        let fetch = insert_symbol(&mut db, &file_id, Some("module:tasks:fetch"), None, "fetch", "function", true).unwrap();
+        insert_symbol(&mut db, &file_id, Some("module:tasks:plain"), None, "plain", "function", true).unwrap();
+
+        let metadata = HashMap::from([("is_coroutine".to_string(), "true".to_string())]);
+        db.set_symbol_metadata(&fetch, &metadata).unwrap();
+
+        let coroutines = db.symbols_with_metadata("is_coroutine", "true").unwrap();
+        assert_eq!(coroutines.len(), 1);
+        assert_eq!(coroutines[0].name, "fetch");
+        assert_eq!(db.get_symbol(&fetch).unwrap().unwrap().metadata, metadata);
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_symbol_metadata").ok();
+    }
+
+    /// Демонстрация: fan-in и fan-out в графе вызовов
+    #[test]
+    fn test_query_symbol_metrics() {
+        let mut db = Db::open("test_db_symbol_metrics").unwrap();
+        let file_id = db.ensure_file("src/star.cpp", "c++").unwrap();
+        let hub = insert_symbol(&mut db, &file_id, Some("c:@F@log#"), None, "log", "FunctionDecl", true).unwrap();
+
+        // Звезда: пять функций вызывают одну и ту же функцию log
+        for i in 0..5 {
+            let name = format!("caller{}", i);
+            let usr = format!("c:@F@{}#", name);
+            let caller = insert_symbol(&mut db, &file_id, Some(&usr), None, &name, "FunctionDecl", true).unwrap();
+            insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&caller), Some(&hub), None, None, "call").unwrap();
+        }
+
+        let metrics = db.query_symbol_metrics().unwrap();
+        assert_eq!(metrics.len(), 6);
+        let log = metrics.iter().find(|m| m.name == "log").unwrap();
+        assert_eq!((log.fan_in, log.fan_out), (5, 0));
+        let caller = metrics.iter().find(|m| m.name == "caller3").unwrap();
+        assert_eq!((caller.fan_in, caller.fan_out), (0, 1));
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_symbol_metrics").ok();
+    }
+
+    /// Демонстрация: чтение из одного потока во время записи из другого
+    #[test]
+    fn test_concurrent_reader_and_writer() {
+        let mut writer = Db::open("test_db_concurrent").unwrap();
+        let reader = writer.clone();
+        let file_id = writer.ensure_file("src/big.cpp", "c++").unwrap();
+
+        let writer_thread = std::thread::spawn(move || -> Result<()> {
+            for i in 0..200 {
+                let name = format!("f{}", i);
+                let usr = format!("c:@F@{}#", name);
+                insert_symbol(&mut writer, &file_id, Some(&usr), None, &name, "FunctionDecl", true)?;
+            }
+            Ok(())
+        });
+        let reader_thread = std::thread::spawn(move || -> Result<usize> {
+            let mut seen = 0;
+            while seen < 200 {
+                seen = reader.find_symbols_in_file("src/big.cpp")?.len();
+            }
+            Ok(seen)
+        });
+
+        writer_thread.join().unwrap().expect("writer failed");
+        assert_eq!(reader_thread.join().unwrap().expect("reader failed"), 200);
+
+        std::fs::remove_dir_all("test_db_concurrent").ok();
+    }
+
+    /// Демонстрация: повторное открытие ждёт, пока база занята
+    #[test]
+    fn test_open_waits_for_busy_database() {
+        let path = "test_db_busy";
+        std::fs::remove_dir_all(path).ok();
+        let holder = Db::open(path).unwrap();
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            drop(holder);
+        });
+
+        let db = Db::open(path).expect("open should wait for the lock");
+        releaser.join().unwrap();
+
+        drop(db);
+        std::fs::remove_dir_all(path).ok();
+    }
+
+    /// Демонстрация: обнаружение изменений по хешу содержимого
+    #[test]
+    fn test_file_has_changed() {
+        let source = "test_db_source_hash.cpp";
+        std::fs::write(source, "int main() { return 0; }\n").unwrap();
+        let mut db = Db::open("test_db_source_hash").unwrap();
+
+        db.ensure_file(source, "c++").unwrap();
+        assert!(!db.file_has_changed(source).unwrap());
+
+        // Та же длина, другое содержимое
+        std::fs::write(source, "int main() { return 1; }\n").unwrap();
+        assert!(db.file_has_changed(source).unwrap());
+
+        db.update_file_hash(source).unwrap();
+        assert!(!db.file_has_changed(source).unwrap());
+        assert!(db.file_has_changed("test_db_unknown.cpp").unwrap());
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_source_hash").ok();
+        std::fs::remove_file(source).ok();
+    }
+
+    /// Демонстрация: экземпляры шаблона через рёбра instantiates
+    #[test]
+    fn test_query_instantiations() {
+        let mut db = Db::open("test_db_instantiations").unwrap();
+        let file_id = db.ensure_file("include/vector", "c++").unwrap();
+        let template = "c:@N@std@ST>2#T#T@vector";
+        let vector = insert_symbol(&mut db, &file_id, Some(template), None, "vector", "ClassTemplate", true).unwrap();
+        let ints = insert_symbol(&mut db, &file_id, Some("c:@N@std@S@vector>#I"), None, "std::vector<int>", "ClassTemplateInstantiation", false).unwrap();
+        db.set_symbol_template_args(&ints, Some("int")).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&ints), Some(&vector), None, None, "instantiates").unwrap();
+
+        let instantiations = db.query_instantiations(template).unwrap();
+        assert_eq!(instantiations.len(), 1);
+        assert_eq!(instantiations[0].name, "std::vector<int>");
+        assert_eq!(instantiations[0].template_args.as_deref(), Some("int"));
+        assert!(db.query_instantiations("c:@ST>1#T@missing").unwrap().is_empty());
+
+        drop(db);
+        std::fs::remove_dir_all("test_db_instantiations").ok();
+    }
+
+    /// Демонстрация: удаление файла вместе с символами, вхождениями и рёбрами
+    #[test]
+    fn test_delete_file_and_cascade() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/gone.cpp", "c++").unwrap();
+        let caller = insert_symbol(&mut db, &file_id, Some("c:@F@caller#"), None, "caller", "FunctionDecl", true).unwrap();
+        let callee = insert_symbol(&mut db, &file_id, Some("c:@F@callee#"), None, "callee", "FunctionDecl", true).unwrap();
+        insert_occurrence(&mut db, &callee, &file_id, "call", 3, 5, None, None).unwrap();
+        insert_occurrence(&mut db, &caller, &file_id, "definition", 2, 6, None, None).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&caller), Some(&callee), None, None, "call").unwrap();
+        db.tag_symbol(&callee, "deprecated").unwrap();
+
+        let stats = db.delete_file_and_cascade("src/gone.cpp").unwrap();
+        assert_eq!(stats, DeleteStats { symbols_deleted: 2, occurrences_deleted: 2, edges_deleted: 1 });
+
+        // Остаётся только служебная версия схемы
+        let keys: Vec<String> = db.db.iter().keys().map(|k| String::from_utf8_lossy(&k.unwrap()).to_string()).collect();
+        assert_eq!(keys, vec!["meta:schema_version"]);
+        assert_eq!(db.delete_file_and_cascade("src/gone.cpp").unwrap(), DeleteStats::default());
+    }
+
+    /// Демонстрация: полнотекстовый поиск по именам символов с ранжированием
+    #[test]
+    fn test_fts_search_symbols() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/app.cpp", "c++").unwrap();
+        for (name, kind) in [
+            ("Config", "ClassDecl"),
+            ("ConfigLoader", "ClassDecl"),
+            ("AppConfig", "ClassDecl"),
+            ("load_config(const char *)", "FunctionDecl"),
+            ("reconfigure", "FunctionDecl"),
+            ("Parser", "ClassDecl"),
+            ("parse_args", "FunctionDecl"),
+            ("HttpClient", "ClassDecl"),
+            ("main", "FunctionDecl"),
+            ("conf", "VarDecl"),
+        ] {
+            insert_symbol(&mut db, &file_id, None, None, name, kind, true).unwrap();
+        }
+
+        let results = db.fts_search_symbols("conf").unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        // Точное имя, затем префиксы имени, затем префиксы слов; "reconfigure" не совпадает
+        assert_eq!(names, ["conf", "Config", "ConfigLoader", "AppConfig", "load_config(const char *)"]);
+        assert_eq!(results[0].rank, 0);
+        assert_eq!(results[0].file_path.as_deref(), Some("src/app.cpp"));
+
+        // Все слова запроса должны совпасть
+        let results = db.fts_search_symbols("load CONFIG").unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["load_config(const char *)", "ConfigLoader"]);
+        assert!(db.fts_search_symbols("client").unwrap().iter().any(|r| r.name == "HttpClient"));
+        assert!(db.fts_search_symbols("").unwrap().is_empty());
+
+        // Индекс очищается вместе с символами файла
+        db.remove_symbols_in_file("src/app.cpp").unwrap();
+        assert!(db.fts_search_symbols("conf").unwrap().is_empty());
+    }
+
+    /// Демонстрация: пользовательские метки символов
+    #[test]
+    fn test_symbol_tags() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/api.cpp", "c++").unwrap();
+        let old = insert_symbol(&mut db, &file_id, Some("c:@F@old_api#"), None, "old_api", "FunctionDecl", true).unwrap();
+        let new = insert_symbol(&mut db, &file_id, Some("c:@F@new_api#"), None, "new_api", "FunctionDecl", true).unwrap();
+
+        db.tag_symbol(&old, "deprecated").unwrap();
+        db.tag_symbol(&old, "deprecated").unwrap();
+        db.tag_symbol(&old, "public-api").unwrap();
+        db.tag_symbol(&new, "public-api").unwrap();
+        assert_eq!(db.find_symbols_by_tag("deprecated").unwrap(), vec![old.clone()]);
+        assert_eq!(db.find_symbols_by_tag("public-api").unwrap().len(), 2);
+
+        db.untag_symbol(&old, "public-api").unwrap();
+        assert_eq!(db.find_symbols_by_tag("public-api").unwrap(), vec![new.clone()]);
+        assert_eq!(db.symbol_tags(&old).unwrap(), vec!["deprecated"]);
+
+        // Метки удаляются вместе с символами файла
+        db.remove_symbols_in_file("src/api.cpp").unwrap();
+        assert!(db.find_symbols_by_tag("deprecated").unwrap().is_empty());
+    }
+
+    /// Демонстрация: функции, объявленные, но нигде не определённые
+    #[test]
+    fn test_find_undefined_symbols() {
+        let mut db = Db::open_temporary().unwrap();
+        let header = db.ensure_file("include/net.h", "c++").unwrap();
+        let source = db.ensure_file("src/net.cpp", "c++").unwrap();
+        let script = db.ensure_file("tools/net.py", "python").unwrap();
+
+        // `connect` объявлена в заголовке и определена в другой единице трансляции
+        insert_symbol(&mut db, &header, Some("c:@F@connect#"), None, "connect", "FunctionDecl", false).unwrap();
+        insert_symbol(&mut db, &source, Some("c:@F@connect#"), None, "connect", "FunctionDecl", true).unwrap();
+        // `shutdown` только объявлена
+        insert_symbol(&mut db, &header, Some("c:@F@shutdown#"), None, "shutdown", "FunctionDecl", false).unwrap();
+        insert_symbol(&mut db, &source, Some("c:@F@shutdown#"), None, "shutdown", "FunctionDecl", false).unwrap();
+        insert_symbol(&mut db, &script, Some("py:net.reconnect"), None, "reconnect", "Function", false).unwrap();
+
+        assert_eq!(db.find_undefined_symbols().unwrap(), vec!["reconnect", "shutdown"]);
+        assert_eq!(db.find_undefined_symbols_in_lang(Some("c++")).unwrap(), vec!["shutdown"]);
+    }
+
+    /// Демонстрация: старое имя переименованной функции продолжает находиться
+    #[test]
+    fn test_symbol_aliases_after_rename() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/net.cpp", "c++").unwrap();
+        // `connectTo(int)` раньше называлась `open_connection`
+        let connect = insert_symbol(&mut db, &file_id, Some("c:@F@connectTo#I#"), None, "connectTo(int)", "FunctionDecl", true).unwrap();
+        let other = insert_symbol(&mut db, &file_id, Some("c:@F@close#"), None, "close()", "FunctionDecl", true).unwrap();
+        db.add_alias(&connect, "open_connection").unwrap();
+
+        assert_eq!(db.resolve_alias("open_connection").unwrap(), vec![connect.clone()]);
+        assert_eq!(db.resolve_alias("connectTo").unwrap(), vec![connect.clone()]);
+        assert_eq!(db.resolve_alias("close()").unwrap(), vec![other]);
+        assert!(db.resolve_alias("connect").unwrap().is_empty());
+
+        let found: Vec<String> = db.search_symbols("open_conn").unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(found, vec!["connectTo(int)"]);
+        assert_eq!(db.symbol_aliases(&connect).unwrap(), vec!["open_connection"]);
+
+        // Псевдонимы удаляются вместе с символами файла
+        db.remove_symbols_in_file("src/net.cpp").unwrap();
+        assert!(db.resolve_alias("open_connection").unwrap().is_empty());
+    }
+
+    /// Демонстрация: поиск определений, охватывающих строку файла
+    #[test]
+    fn test_find_symbols_at_line() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/parser.cpp", "c++").unwrap();
+        let class = insert_symbol(&mut db, &file_id, Some("c:@S@Parser"), None, "Parser", "ClassDecl", true).unwrap();
+        let parse = insert_symbol(&mut db, &file_id, Some("c:@S@Parser@F@parse#"), None, "parse()", "Method", true).unwrap();
+        let decl = insert_symbol(&mut db, &file_id, Some("c:@F@helper#"), None, "helper()", "FunctionDecl", false).unwrap();
+        db.set_symbol_def_range(&class, Some(5), Some(40)).unwrap();
+        db.set_symbol_def_range(&parse, Some(10), Some(25)).unwrap();
+
+        assert_eq!(db.find_symbols_at_line("src/parser.cpp", 10).unwrap(), vec![class.clone(), parse.clone()]);
+        assert_eq!(db.find_symbols_at_line("src/parser.cpp", 25).unwrap(), vec![class.clone(), parse]);
+        assert_eq!(db.find_symbols_at_line("src/parser.cpp", 26).unwrap(), vec![class]);
+        assert!(db.find_symbols_at_line("src/parser.cpp", 41).unwrap().is_empty());
+        assert!(db.find_symbols_at_line("src/other.cpp", 10).unwrap().is_empty());
+        assert_eq!(db.get_symbol(&decl).unwrap().unwrap().def_start_line, None);
+    }
+
+    /// Демонстрация: документация символа из Doxygen-комментария
+    #[test]
+    fn test_symbol_documentation() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/math.cpp", "c++").unwrap();
+        let clamp = insert_symbol(&mut db, &file_id, Some("c:@F@clamp#I#I#I#"), None, "clamp(int, int, int)", "FunctionDecl", true).unwrap();
+        let other = insert_symbol(&mut db, &file_id, Some("c:@F@lerp#"), None, "lerp()", "FunctionDecl", true).unwrap();
+
+        db.set_symbol_doc(&clamp, Some("@brief Limit a value to a range.\n@param value Value to limit")).unwrap();
+        let doc = db.get_symbol_doc(&clamp).unwrap().unwrap();
+        assert!(doc.starts_with("@brief Limit a value to a range."));
+        assert_eq!(db.get_symbol_doc(&other).unwrap(), None);
+        assert_eq!(db.get_symbol_doc("missing").unwrap(), None);
+    }
+
+    /// Демонстрация: члены пространства имён и псевдонимы
+    #[test]
+    fn test_namespace_members() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/geometry.cpp", "c++").unwrap();
+        let geo = insert_symbol(&mut db, &file_id, Some("c:@N@geo"), None, "geo", "Namespace", true).unwrap();
+        let alias = insert_symbol(&mut db, &file_id, Some("c:@NA@g"), None, "g", "NamespaceAlias", true).unwrap();
+        let perimeter = insert_symbol(&mut db, &file_id, Some("c:@N@geo@F@perimeter#I#I#"), None, "perimeter(int, int)", "FunctionDecl", true).unwrap();
+        let area = insert_symbol(&mut db, &file_id, Some("c:@N@geo@F@area#I#I#"), None, "area(int, int)", "FunctionDecl", true).unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@F@outside#"), None, "outside()", "FunctionDecl", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&geo), Some(&perimeter), None, None, "namespace-member").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&geo), Some(&area), None, None, "namespace-member").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&alias), Some(&geo), None, None, "namespace-alias").unwrap();
+
+        let expected = vec!["area(int, int)".to_string(), "perimeter(int, int)".to_string()];
+        assert_eq!(db.query_namespace_members("c:@N@geo").unwrap(), expected);
+        assert_eq!(db.query_namespace_members("c:@NA@g").unwrap(), expected);
+        assert!(db.query_namespace_members("c:@N@missing").unwrap().is_empty());
+    }
+
+    /// Демонстрация: вложенность символов (пространство имён → класс → метод)
+    #[test]
+    fn test_query_symbol_children() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/geometry.cpp", "c++").unwrap();
+        insert_symbol(&mut db, &file_id, Some("c:@N@geo"), None, "geo", "Namespace", true).unwrap();
+        let shape = insert_symbol(&mut db, &file_id, Some("c:@N@geo@S@Shape"), None, "Shape", "ClassDecl", true).unwrap();
+        let area = insert_symbol(&mut db, &file_id, Some("c:@N@geo@S@Shape@F@area#"), None, "area()", "Method", true).unwrap();
+        let helper = insert_symbol(&mut db, &file_id, Some("c:@N@geo@F@helper#"), None, "helper()", "FunctionDecl", true).unwrap();
+        db.set_symbol_parent(&shape, Some("c:@N@geo")).unwrap();
+        db.set_symbol_parent(&helper, Some("c:@N@geo")).unwrap();
+        db.set_symbol_parent(&area, Some("c:@N@geo@S@Shape")).unwrap();
+
+        let names = |db: &Db, usr: &str| -> Vec<(String, String)> {
+            db.query_symbol_children(usr).unwrap().into_iter().map(|(_, name, kind)| (name, kind)).collect()
+        };
+        assert_eq!(
+            names(&db, "c:@N@geo"),
+            [("Shape".to_string(), "ClassDecl".to_string()), ("helper()".to_string(), "FunctionDecl".to_string())]
+        );
+        assert_eq!(names(&db, "c:@N@geo@S@Shape"), [("area()".to_string(), "Method".to_string())]);
+        assert!(names(&db, "c:@N@geo@S@Shape@F@area#").is_empty());
+
+        // Removing the file drops the index entries
+        db.remove_symbols_in_file("src/geometry.cpp").unwrap();
+        assert!(names(&db, "c:@N@geo").is_empty());
+    }
+
+    /// Демонстрация: конструкторы класса
+    #[test]
+    fn test_query_constructors() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/point.cpp", "c++").unwrap();
+        let point = insert_symbol(&mut db, &file_id, Some("c:@S@Point"), None, "Point", "ClassDecl", true).unwrap();
+        let with_args = insert_symbol(&mut db, &file_id, Some("c:@S@Point@F@Point#I#I#"), None, "Point(int, int)", "Constructor", true).unwrap();
+        let default = insert_symbol(&mut db, &file_id, Some("c:@S@Point@F@Point#"), None, "Point()", "Constructor", true).unwrap();
+        let destructor = insert_symbol(&mut db, &file_id, Some("c:@S@Point@F@~Point#"), None, "~Point()", "Destructor", true).unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&with_args), Some(&point), None, None, "constructs").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&default), Some(&point), None, None, "constructs").unwrap();
+        insert_edge(&mut db, &EdgeKindRegistry::new(), Some(&destructor), Some(&point), None, None, "destructs").unwrap();
+
+        assert_eq!(db.query_constructors("c:@S@Point").unwrap(), vec!["Point()".to_string(), "Point(int, int)".to_string()]);
+        assert!(db.query_constructors("c:@S@Missing").unwrap().is_empty());
+    }
+
+    /// Демонстрация: неизвестные типы рёбер отклоняются
+    #[test]
+    fn test_edge_kind_registry() {
+        let mut db = Db::open_temporary().unwrap();
+        let file_id = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let main = insert_symbol(&mut db, &file_id, Some("c:@F@main#"), None, "main()", "FunctionDecl", true).unwrap();
+        let run = insert_symbol(&mut db, &file_id, Some("c:@F@run#"), None, "run()", "FunctionDecl", true).unwrap();
+
+        let mut edge_kinds = EdgeKindRegistry::new();
+        let err = insert_edge(&mut db, &edge_kinds, Some(&main), Some(&run), None, None, "cal").unwrap_err();
+        assert!(err.to_string().contains("'cal'"));
+        assert!(db.edges_by_kind("cal").unwrap().is_empty());
+
+        edge_kinds.register("my-custom");
+        insert_edge(&mut db, &edge_kinds, Some(&main), Some(&run), None, None, "my-custom").unwrap();
+        assert_eq!(db.edges_by_kind("my-custom").unwrap().len(), 1);
+    }
+
+    /// Демонстрация: разделы модуля C++20
+    #[test]
+    fn test_module_partitions() {
+        let mut db = Db::open_temporary().unwrap();
+        let edge_kinds = EdgeKindRegistry::new();
+        let geometry = upsert_module(&mut db, "geometry", "cpp20-module", "geometry.cppm").unwrap();
+        let shapes = upsert_module(&mut db, "geometry:shapes", "cpp20-module", "geometry-shapes.cppm").unwrap();
+        let math = upsert_module(&mut db, "geometry:math", "cpp20-module", "").unwrap();
+        let std = upsert_module(&mut db, "std", "cpp20-module", "").unwrap();
+        insert_edge(&mut db, &edge_kinds, None, None, Some(&geometry), Some(&shapes), "module-partition").unwrap();
+        insert_edge(&mut db, &edge_kinds, None, None, Some(&geometry), Some(&math), "module-partition").unwrap();
+        insert_edge(&mut db, &edge_kinds, None, None, Some(&geometry), Some(&math), "module-partition").unwrap();
+        insert_edge(&mut db, &edge_kinds, None, None, Some(&geometry), Some(&std), "module-import").unwrap();
+
+        assert_eq!(db.query_module_partitions("geometry").unwrap(), vec!["geometry:math", "geometry:shapes"]);
+        assert!(db.query_module_partitions("geometry:shapes").unwrap().is_empty());
+        assert!(db.query_module_partitions("missing").unwrap().is_empty());
+    }
+
+    /// Демонстрация: использования символа в нескольких файлах
+    #[test]
+    fn test_find_symbol_usages() {
+        let mut db = Db::open_temporary().unwrap();
+        let util = db.ensure_file("src/util.cpp", "c++").unwrap();
+        let main = db.ensure_file("src/main.cpp", "c++").unwrap();
+        let app = db.ensure_file("src/app.cpp", "c++").unwrap();
+        let foo = insert_symbol(&mut db, &util, Some("c:@F@foo#"), None, "foo()", "FunctionDecl", true).unwrap();
+        insert_occurrence(&mut db, &foo, &util, "definition", 3, 6, None, None).unwrap();
+        insert_occurrence(&mut db, &foo, &main, "call", 12, 5, None, None).unwrap();
+        insert_occurrence(&mut db, &foo, &main, "call", 4, 9, None, None).unwrap();
+        insert_occurrence(&mut db, &foo, &app, "reference", 7, 14, None, None).unwrap();
+
+        let usages = db.find_symbol_usages("c:@F@foo#").unwrap();
+        let locations: Vec<_> = usages
+            .iter()
+            .map(|u| format!("{}:{}:{} {}", u.file_path, u.line, u.col, u.usage_kind))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                "src/app.cpp:7:14 reference",
+                "src/main.cpp:4:9 call",
+                "src/main.cpp:12:5 call",
+                "src/util.cpp:3:6 definition",
+            ]
+        );
+        assert!(db.find_symbol_usages("c:@F@missing#").unwrap().is_empty());
+    }
 }
@@ -0,0 +1,373 @@
+//! LSIF (Language Server Index Format) import
+//!
+//! Reads the line-delimited JSON dump produced by `rust-analyzer lsif` and
+//! similar tools. Definitions become symbols whose USR is the moniker
+//! identifier of their result set; references become occurrences of the
+//! matching symbol, whether it is defined in the same dump or already exists
+//! in the database.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::{insert_occurrence, insert_symbol, SymgraphDb};
+
+/// Counters reported after an LSIF import
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LsifImportStats {
+    pub documents: usize,
+    pub definitions: usize,
+    pub references: usize,
+    pub skipped_references: usize,
+}
+
+/// One vertex or edge of the dump; only the fields symgraph reads
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Element {
+    id: Value,
+    #[serde(rename = "type")]
+    element_type: String,
+    label: String,
+    uri: Option<String>,
+    language_id: Option<String>,
+    project_root: Option<String>,
+    start: Option<Position>,
+    tag: Option<Tag>,
+    identifier: Option<String>,
+    out_v: Option<Value>,
+    in_v: Option<Value>,
+    #[serde(default)]
+    in_vs: Vec<Value>,
+    document: Option<Value>,
+    property: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: u32,
+    character: u32,
+}
+
+/// Range tag emitted by some indexers (LSIF 0.4)
+#[derive(Debug, Deserialize)]
+struct Tag {
+    text: Option<String>,
+    kind: Option<u32>,
+}
+
+struct Range {
+    line: u32,
+    character: u32,
+    name: Option<String>,
+    kind: Option<u32>,
+}
+
+/// A range listed by an `item` edge
+struct Item {
+    result_set: String,
+    range: String,
+    document: String,
+}
+
+/// Import an LSIF dump from a file; see [`import_lsif`].
+pub fn import_lsif_file(
+    db: &mut SymgraphDb,
+    path: &Path,
+    project_name: &str,
+    skip_missing_usrs: bool,
+) -> Result<LsifImportStats> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open LSIF file '{}'", path.display()))?;
+    import_lsif(db, std::io::BufReader::new(file), project_name, skip_missing_usrs)
+}
+
+/// Import a line-delimited LSIF dump into `db` under `project_name`.
+///
+/// A reference whose result set is neither defined in the dump nor has a
+/// moniker matching an existing symbol USR is an error, unless
+/// `skip_missing_usrs` is set, in which case it is counted and dropped.
+/// Nothing is written when the import fails. Lines are stored 1-based.
+pub fn import_lsif<R: BufRead>(
+    db: &mut SymgraphDb,
+    reader: R,
+    project_name: &str,
+    skip_missing_usrs: bool,
+) -> Result<LsifImportStats> {
+    let mut project_root = String::new();
+    let mut documents: HashMap<String, (String, String)> = HashMap::new();
+    let mut ranges: HashMap<String, Range> = HashMap::new();
+    let mut monikers: HashMap<String, String> = HashMap::new();
+    let mut next: HashMap<String, String> = HashMap::new();
+    let mut moniker_of: HashMap<String, String> = HashMap::new();
+    let mut definition_results: HashMap<String, String> = HashMap::new();
+    let mut reference_results: HashMap<String, String> = HashMap::new();
+    let mut item_edges: Vec<Element> = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let element: Element = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid LSIF element on line {}", number + 1))?;
+        let id = id_string(&element.id);
+        let out_v = element.out_v.as_ref().map(id_string);
+        let in_v = element.in_v.as_ref().map(id_string);
+
+        match (element.element_type.as_str(), element.label.as_str()) {
+            ("vertex", "metaData") => project_root = element.project_root.unwrap_or_default(),
+            ("vertex", "document") => {
+                let uri = element.uri.unwrap_or_default();
+                documents.insert(id, (uri, element.language_id.unwrap_or_default()));
+            }
+            ("vertex", "range") => {
+                if let Some(start) = element.start {
+                    ranges.insert(
+                        id,
+                        Range {
+                            line: start.line,
+                            character: start.character,
+                            name: element.tag.as_ref().and_then(|t| t.text.clone()),
+                            kind: element.tag.as_ref().and_then(|t| t.kind),
+                        },
+                    );
+                }
+            }
+            ("vertex", "moniker") => {
+                if let Some(identifier) = element.identifier {
+                    monikers.insert(id, identifier);
+                }
+            }
+            ("edge", "next") => {
+                if let (Some(out_v), Some(in_v)) = (out_v, in_v) {
+                    next.insert(out_v, in_v);
+                }
+            }
+            ("edge", "moniker") => {
+                if let (Some(out_v), Some(in_v)) = (out_v, in_v) {
+                    moniker_of.insert(out_v, in_v);
+                }
+            }
+            ("edge", "textDocument/definition") => {
+                if let (Some(out_v), Some(in_v)) = (out_v, in_v) {
+                    definition_results.insert(in_v, out_v);
+                }
+            }
+            ("edge", "textDocument/references") => {
+                if let (Some(out_v), Some(in_v)) = (out_v, in_v) {
+                    reference_results.insert(in_v, out_v);
+                }
+            }
+            ("edge", "item") => item_edges.push(element),
+            _ => {}
+        }
+    }
+
+    // Split item edges into definition and reference ranges
+    let mut definitions = Vec::new();
+    let mut references = Vec::new();
+    for edge in &item_edges {
+        let (Some(out_v), Some(document)) = (edge.out_v.as_ref(), edge.document.as_ref()) else {
+            continue;
+        };
+        let out_v = id_string(out_v);
+        let document = id_string(document);
+        let (result_set, is_definition) = if let Some(rs) = definition_results.get(&out_v) {
+            (rs, true)
+        } else if let Some(rs) = reference_results.get(&out_v) {
+            (rs, edge.property.as_deref() == Some("definitions"))
+        } else {
+            continue;
+        };
+        for range in &edge.in_vs {
+            let item = Item {
+                result_set: result_set.clone(),
+                range: id_string(range),
+                document: document.clone(),
+            };
+            if is_definition {
+                definitions.push(item);
+            } else {
+                references.push(item);
+            }
+        }
+    }
+
+    let usr_of = |result_set: &str| -> Option<String> {
+        let mut current = result_set.to_string();
+        let mut seen = HashSet::new();
+        loop {
+            if let Some(moniker) = moniker_of.get(&current) {
+                return monikers.get(moniker).cloned();
+            }
+            if !seen.insert(current.clone()) {
+                return None;
+            }
+            current = next.get(&current)?.clone();
+        }
+    };
+
+    // Resolve references before writing anything
+    let defined: HashSet<&str> = definitions.iter().map(|d| d.result_set.as_str()).collect();
+    let mut resolved_references = Vec::new();
+    let mut stats = LsifImportStats::default();
+    for item in &references {
+        if defined.contains(item.result_set.as_str()) {
+            resolved_references.push((item, None));
+            continue;
+        }
+        let usr = usr_of(&item.result_set);
+        match usr.as_deref().map(|u| db.find_symbol_by_usr(u)).transpose()?.flatten() {
+            Some(symbol_id) => resolved_references.push((item, Some(symbol_id))),
+            None if skip_missing_usrs => stats.skipped_references += 1,
+            None => {
+                let range = ranges.get(&item.range);
+                bail!(
+                    "LSIF reference at {}:{} points to unknown USR '{}'",
+                    documents.get(&item.document).map_or("?", |(uri, _)| uri.as_str()),
+                    range.map_or(0, |r| r.line + 1),
+                    usr.unwrap_or_default()
+                );
+            }
+        }
+    }
+
+    let project_id = db.ensure_project(project_name, &project_root)?;
+    let mut file_ids: HashMap<String, String> = HashMap::new();
+    let mut file_id_for = |db: &mut SymgraphDb, document: &str| -> Result<Option<String>> {
+        if let Some(file_id) = file_ids.get(document) {
+            return Ok(Some(file_id.clone()));
+        }
+        let Some((uri, language)) = documents.get(document) else {
+            return Ok(None);
+        };
+        let path = relative_path(uri, &project_root);
+        let file_id = db.ensure_file_with_category(
+            &project_id,
+            &path,
+            language,
+            Some("lsif_imported"),
+            Some("LSIF imported file"),
+        )?;
+        file_ids.insert(document.to_string(), file_id.clone());
+        Ok(Some(file_id))
+    };
+
+    let mut symbol_ids: HashMap<String, String> = HashMap::new();
+    for item in &definitions {
+        let (Some(range), Some(file_id)) = (ranges.get(&item.range), file_id_for(db, &item.document)?) else {
+            continue;
+        };
+        let symbol_id = match symbol_ids.get(&item.result_set) {
+            Some(id) => id.clone(),
+            None => {
+                let usr = usr_of(&item.result_set);
+                let name = range
+                    .name
+                    .clone()
+                    .or_else(|| usr.as_deref().map(short_name))
+                    .unwrap_or_else(|| format!("lsif:{}", item.result_set));
+                let kind = range.kind.map_or("symbol", lsp_kind_name);
+                let id = insert_symbol(db, &file_id, usr.as_deref(), None, &name, kind, true)?;
+                symbol_ids.insert(item.result_set.clone(), id.clone());
+                stats.definitions += 1;
+                id
+            }
+        };
+        insert_occurrence(db, &symbol_id, &file_id, "definition", range.line + 1, range.character + 1, None, None)?;
+    }
+
+    for (item, existing) in resolved_references {
+        let symbol_id = match existing.or_else(|| symbol_ids.get(&item.result_set).cloned()) {
+            Some(id) => id,
+            None => continue,
+        };
+        let (Some(range), Some(file_id)) = (ranges.get(&item.range), file_id_for(db, &item.document)?) else {
+            continue;
+        };
+        insert_occurrence(db, &symbol_id, &file_id, "reference", range.line + 1, range.character + 1, None, None)?;
+        stats.references += 1;
+    }
+
+    stats.documents = file_ids.len();
+    Ok(stats)
+}
+
+/// LSIF ids may be numbers or strings
+fn id_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Document URI relative to the project root when it lies inside it
+fn relative_path(uri: &str, project_root: &str) -> String {
+    let root = project_root.trim_end_matches('/');
+    match uri.strip_prefix(root) {
+        Some(rest) if !root.is_empty() => rest.trim_start_matches('/').to_string(),
+        _ => uri.strip_prefix("file://").unwrap_or(uri).to_string(),
+    }
+}
+
+/// Last path segment of a moniker identifier such as `crate::module::name`
+fn short_name(identifier: &str) -> String {
+    identifier.rsplit("::").next().unwrap_or(identifier).to_string()
+}
+
+/// symgraph kind for an LSP `SymbolKind`
+fn lsp_kind_name(kind: u32) -> &'static str {
+    match kind {
+        2 => "module",
+        3 => "namespace",
+        5 => "class",
+        6 => "method",
+        8 => "field",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        22 => "enum_member",
+        23 => "struct",
+        26 => "type_parameter",
+        _ => "symbol",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/simple.lsif");
+
+    #[test]
+    fn test_import_lsif_definition_and_reference() {
+        let mut db = SymgraphDb::open_temporary().unwrap();
+
+        // `println` is referenced but never defined and not in the database
+        assert!(import_lsif(&mut db, FIXTURE.as_bytes(), "demo", false).is_err());
+        assert!(db.find_symbol_by_usr("test_project::main").unwrap().is_none());
+
+        let stats = import_lsif(&mut db, FIXTURE.as_bytes(), "demo", true).unwrap();
+        assert_eq!(
+            stats,
+            LsifImportStats {
+                documents: 1,
+                definitions: 1,
+                references: 1,
+                skipped_references: 1,
+            }
+        );
+
+        let main_id = db.find_symbol_by_usr("test_project::main").unwrap().unwrap();
+        let main = db.get_symbol(&main_id).unwrap().unwrap();
+        assert_eq!(main.name, "main");
+        assert_eq!(main.kind, "function");
+        assert!(db.find_file_by_path("src/main.rs").unwrap().is_some());
+    }
+}
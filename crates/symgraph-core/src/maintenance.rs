@@ -0,0 +1,103 @@
+//! On-disk maintenance
+//!
+//! sled appends every write to its log and reclaims the space of overwritten
+//! or removed entries only lazily, so a database that has been rescanned many
+//! times keeps growing. [`vacuum_database`] rebuilds it from a snapshot of the
+//! live entries, the sled counterpart of SQLite's `VACUUM`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::SymgraphDb;
+
+/// Database size before and after a vacuum, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VacuumStats {
+    pub before: u64,
+    pub after: u64,
+}
+
+/// Rewrite the database at `path` so it only holds its live entries.
+///
+/// Every tree is copied into a fresh database next to `path` in one batch,
+/// which then replaces the original directory. The database must not be open
+/// elsewhere.
+pub fn vacuum_database(path: &str) -> Result<VacuumStats> {
+    let dir = Path::new(path);
+    let before = dir_size(dir)?;
+    let fresh = format!("{}.vacuum", path.trim_end_matches('/'));
+    let stale = format!("{}.vacuum-old", path.trim_end_matches('/'));
+    fs::remove_dir_all(&fresh).ok();
+    fs::remove_dir_all(&stale).ok();
+
+    {
+        let source = SymgraphDb::open(path)?;
+        let target = sled::Config::new()
+            .path(&fresh)
+            .flush_every_ms(None)
+            .open()
+            .with_context(|| format!("Failed to create {}", fresh))?;
+        for name in source.db.tree_names() {
+            let mut batch = sled::Batch::default();
+            for entry in source.db.open_tree(&name)?.iter() {
+                let (key, value) = entry?;
+                batch.insert(key, value);
+            }
+            target.open_tree(&name)?.apply_batch(batch)?;
+        }
+        target.flush()?;
+    }
+
+    fs::rename(dir, &stale).with_context(|| format!("Failed to move {} aside", path))?;
+    fs::rename(&fresh, dir).with_context(|| format!("Failed to move {} into place", fresh))?;
+    fs::remove_dir_all(&stale)?;
+
+    Ok(VacuumStats { before, after: dir_size(dir)? })
+}
+
+/// Total size of the files under `dir`
+fn dir_size(dir: &Path) -> Result<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        total += if meta.is_dir() { dir_size(&entry.path())? } else { meta.len() };
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::insert_symbol;
+
+    #[test]
+    fn test_vacuum_does_not_grow_database() {
+        let path = "test_db_vacuum";
+        fs::remove_dir_all(path).ok();
+        {
+            let mut db = SymgraphDb::open(path).unwrap();
+            let file_id = db.ensure_file("src/big.cpp", "c++").unwrap();
+            // Rescanning the same file leaves the replaced entries in the log
+            for _ in 0..3 {
+                db.remove_symbols_in_file("src/big.cpp").unwrap();
+                for i in 0..100 {
+                    let name = format!("f{}", i);
+                    let usr = format!("c:@F@{}#", name);
+                    insert_symbol(&mut db, &file_id, Some(&usr), None, &name, "FunctionDecl", true).unwrap();
+                }
+            }
+            db.db.flush().unwrap();
+        }
+
+        let stats = vacuum_database(path).unwrap();
+        assert!(stats.after <= stats.before, "{:?}", stats);
+
+        let db = SymgraphDb::open(path).unwrap();
+        assert_eq!(db.find_symbols_in_file("src/big.cpp").unwrap().len(), 100);
+
+        drop(db);
+        fs::remove_dir_all(path).ok();
+    }
+}
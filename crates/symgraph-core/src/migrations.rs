@@ -0,0 +1,301 @@
+//! Schema versioning and upgrades
+//!
+//! The database records its schema version under `meta:schema_version`; a
+//! missing key means version 0. [`SymgraphDb::open`](crate::SymgraphDb::open)
+//! runs every migration above the stored version in order. A migration reads
+//! the current data and returns a batch of writes that is applied atomically
+//! together with the version bump, so a failing migration leaves the database
+//! at the previous version.
+
+use anyhow::{bail, Context, Result};
+use sled::{Batch, Db};
+use std::collections::HashMap;
+
+use crate::fts::name_tokens;
+use crate::{file_sha256, Edge, File, Occurrence, Symbol};
+
+/// Schema version written by this build
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+const VERSION_KEY: &str = "meta:schema_version";
+
+/// Builds the writes that upgrade the database by one version
+pub(crate) type Migration = fn(&Db) -> Result<Batch>;
+
+/// `MIGRATIONS[n]` upgrades version `n` to `n + 1`
+const MIGRATIONS: &[Migration] = &[
+    migration_001_file_and_occurrence_indexes,
+    migration_002_file_source_hashes,
+    migration_003_symbol_name_tokens,
+    migration_004_edge_weights,
+    migration_005_symbol_parents,
+    migration_006_edge_target_and_file_occurrence_indexes,
+    migration_007_symbol_kind_index,
+];
+
+/// Schema version stored in the database; 0 when none is recorded
+pub fn schema_version(db: &Db) -> Result<u32> {
+    match db.get(VERSION_KEY)? {
+        Some(value) => String::from_utf8_lossy(&value)
+            .parse()
+            .with_context(|| format!("Invalid schema version '{}'", String::from_utf8_lossy(&value))),
+        None => Ok(0),
+    }
+}
+
+/// Bring the database up to [`SCHEMA_VERSION`]
+pub(crate) fn migrate(db: &Db) -> Result<()> {
+    run_migrations(db, MIGRATIONS)
+}
+
+pub(crate) fn run_migrations(db: &Db, migrations: &[Migration]) -> Result<()> {
+    let current = schema_version(db)?;
+    if current as usize > migrations.len() {
+        bail!(
+            "Database schema version {} is newer than the supported version {}",
+            current,
+            migrations.len()
+        );
+    }
+
+    for (index, migration) in migrations.iter().enumerate().skip(current as usize) {
+        let number = index + 1;
+        let mut batch = migration(db).with_context(|| format!("Migration {:03} failed", number))?;
+        batch.insert(VERSION_KEY, number.to_string().as_bytes());
+        db.apply_batch(batch)
+            .with_context(|| format!("Migration {:03} failed", number))?;
+    }
+    Ok(())
+}
+
+/// Backfill the `symbol_by_file:` and `occurrence_by_symbol:` indexes
+fn migration_001_file_and_occurrence_indexes(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("symbol:") {
+        let (_, value) = item?;
+        let symbol: Symbol = serde_json::from_slice(&value)?;
+        batch.insert(
+            format!("symbol_by_file:{}:{}", symbol.file_id, symbol.id).as_bytes(),
+            symbol.id.as_bytes(),
+        );
+    }
+    for item in db.scan_prefix("occurrence:") {
+        let (_, value) = item?;
+        let occurrence: Occurrence = serde_json::from_slice(&value)?;
+        batch.insert(
+            format!("occurrence_by_symbol:{}:{}", occurrence.symbol_id, occurrence.id).as_bytes(),
+            occurrence.id.as_bytes(),
+        );
+    }
+    Ok(batch)
+}
+
+/// Record the content hash of every file that is still readable
+fn migration_002_file_source_hashes(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("file:") {
+        let (key, value) = item?;
+        let mut file: File = serde_json::from_slice(&value)?;
+        // Every file is stored under both its path and its ID; visit it once
+        if key.as_ref() != format!("file:{}", file.path).as_bytes() {
+            continue;
+        }
+        file.source_hash = file_sha256(&file.path);
+        let value = serde_json::to_vec(&file)?;
+        batch.insert(format!("file:{}", file.path).as_bytes(), value.clone());
+        batch.insert(format!("file:{}", file.id).as_bytes(), value);
+    }
+    Ok(batch)
+}
+
+/// Backfill the `fts:` token index of symbol names
+fn migration_003_symbol_name_tokens(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("symbol:") {
+        let (_, value) = item?;
+        let symbol: Symbol = serde_json::from_slice(&value)?;
+        for token in name_tokens(&symbol.name) {
+            batch.insert(format!("fts:{}:{}", token, symbol.id).as_bytes(), symbol.id.as_bytes());
+        }
+    }
+    Ok(batch)
+}
+
+/// Store the default weight on every edge written before edges had one
+fn migration_004_edge_weights(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("edge:") {
+        let (_, value) = item?;
+        // Edges without a stored weight deserialize with the default
+        let edge: Edge = serde_json::from_slice(&value)?;
+        let value = serde_json::to_vec(&edge)?;
+        batch.insert(format!("edge:{}", edge.id).as_bytes(), value.clone());
+        if let Some(from) = &edge.from_sym {
+            batch.insert(format!("edges_from:{}:{}:{}", from, edge.kind, edge.id).as_bytes(), value);
+        }
+    }
+    Ok(batch)
+}
+
+/// Derive `parent_usr` from `member` and `namespace-member` edges and index it
+fn migration_005_symbol_parents(db: &Db) -> Result<Batch> {
+    let symbol = |id: &str| -> Result<Option<Symbol>> {
+        db.get(format!("symbol:{}", id))?
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(Into::into)
+    };
+
+    let mut parents = HashMap::new();
+    for item in db.scan_prefix("edge:") {
+        let (_, value) = item?;
+        let edge: Edge = serde_json::from_slice(&value)?;
+        if edge.kind != "member" && edge.kind != "namespace-member" {
+            continue;
+        }
+        let (Some(parent_id), Some(child_id)) = (edge.from_sym, edge.to_sym) else {
+            continue;
+        };
+        if let Some(parent_usr) = symbol(&parent_id)?.and_then(|parent| parent.usr) {
+            parents.insert(child_id, parent_usr);
+        }
+    }
+
+    let mut batch = Batch::default();
+    for (child_id, parent_usr) in parents {
+        let Some(mut child) = symbol(&child_id)? else {
+            continue;
+        };
+        if child.parent_usr.is_some() {
+            continue;
+        }
+        batch.insert(format!("symbol_by_parent:{}:{}", parent_usr, child_id).as_bytes(), child_id.as_bytes());
+        child.parent_usr = Some(parent_usr);
+        batch.insert(format!("symbol:{}", child_id).as_bytes(), serde_json::to_vec(&child)?);
+    }
+    Ok(batch)
+}
+
+/// Backfill the `edges_to:` and `occurrence_by_file:` indexes
+fn migration_006_edge_target_and_file_occurrence_indexes(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("edge:") {
+        let (_, value) = item?;
+        let edge: Edge = serde_json::from_slice(&value)?;
+        if let Some(to) = &edge.to_sym {
+            batch.insert(format!("edges_to:{}:{}", to, edge.id).as_bytes(), edge.id.as_bytes());
+        }
+    }
+    for item in db.scan_prefix("occurrence:") {
+        let (_, value) = item?;
+        let occurrence: Occurrence = serde_json::from_slice(&value)?;
+        batch.insert(
+            format!("occurrence_by_file:{}:{}", occurrence.file_id, occurrence.id).as_bytes(),
+            occurrence.id.as_bytes(),
+        );
+    }
+    Ok(batch)
+}
+
+/// Backfill the `symbol_by_kind:` index
+fn migration_007_symbol_kind_index(db: &Db) -> Result<Batch> {
+    let mut batch = Batch::default();
+    for item in db.scan_prefix("symbol:") {
+        let (_, value) = item?;
+        let symbol: Symbol = serde_json::from_slice(&value)?;
+        batch.insert(format!("symbol_by_kind:{}:{}", symbol.kind, symbol.id).as_bytes(), symbol.id.as_bytes());
+    }
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymgraphDb;
+
+    #[test]
+    fn test_open_migrates_version_0_database() {
+        let path = "test_db_migrate_v0";
+        std::fs::remove_dir_all(path).ok();
+        {
+            // A database written before schema versioning: no version, no file index
+            let db = sled::open(path).unwrap();
+            let symbol = serde_json::json!({
+                "id": "s1", "file_id": "f1", "usr": "c:@F@main#", "key": null,
+                "name": "main", "kind": "FunctionDecl", "is_definition": true,
+            });
+            let file = serde_json::json!({
+                "id": "f1", "project_id": "1", "path": "src/main.cpp", "lang": "c++",
+                "category": null, "purpose": null,
+            });
+            db.insert("symbol:s1", serde_json::to_vec(&symbol).unwrap()).unwrap();
+            db.insert("file:f1", serde_json::to_vec(&file).unwrap()).unwrap();
+            db.insert("file:src/main.cpp", serde_json::to_vec(&file).unwrap()).unwrap();
+            let edge = serde_json::json!({
+                "id": "e1", "from_sym": "s1", "to_sym": "s1", "from_module": null,
+                "to_module": null, "kind": "call",
+            });
+            db.insert("edge:e1", serde_json::to_vec(&edge).unwrap()).unwrap();
+            let occurrence = serde_json::json!({
+                "id": "o1", "symbol_id": "s1", "file_id": "f1", "usage_kind": "call",
+                "line": 3, "column": 5,
+            });
+            db.insert("occurrence:o1", serde_json::to_vec(&occurrence).unwrap()).unwrap();
+            // A class member, linked to its class only by a `member` edge
+            let records = [
+                ("s2", "c:@S@Widget", "Widget", "ClassDecl"),
+                ("s3", "c:@S@Widget@FI@size", "size", "FieldDecl"),
+            ];
+            for (id, usr, name, kind) in records {
+                let symbol = serde_json::json!({
+                    "id": id, "file_id": "f2", "usr": usr, "key": null,
+                    "name": name, "kind": kind, "is_definition": true,
+                });
+                db.insert(format!("symbol:{}", id), serde_json::to_vec(&symbol).unwrap()).unwrap();
+            }
+            let member = serde_json::json!({
+                "id": "e2", "from_sym": "s2", "to_sym": "s3", "from_module": null,
+                "to_module": null, "kind": "member",
+            });
+            db.insert("edge:e2", serde_json::to_vec(&member).unwrap()).unwrap();
+            assert_eq!(schema_version(&db).unwrap(), 0);
+        }
+
+        let db = SymgraphDb::open(path).unwrap();
+        assert_eq!(schema_version(&db.db).unwrap(), SCHEMA_VERSION);
+        let symbols = db.find_symbols_in_file("src/main.cpp").unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].1, "main");
+        let edge: serde_json::Value = serde_json::from_slice(&db.db.get("edge:e1").unwrap().unwrap()).unwrap();
+        assert_eq!(edge["weight"], 1.0);
+        assert!(db.db.get("edges_to:s1:e1").unwrap().is_some());
+        assert!(db.db.get("occurrence_by_file:f1:o1").unwrap().is_some());
+        let fields = db.symbols_by_kind("FieldDecl").unwrap();
+        assert_eq!(fields.len(), 1);
+        let children = db.query_symbol_children("c:@S@Widget").unwrap();
+        assert_eq!(children, [("s3".to_string(), "size".to_string(), "FieldDecl".to_string())]);
+
+        drop(db);
+        std::fs::remove_dir_all(path).ok();
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_version() {
+        fn add_marker(_: &Db) -> Result<Batch> {
+            let mut batch = Batch::default();
+            batch.insert("marker", "1");
+            Ok(batch)
+        }
+        fn broken(_: &Db) -> Result<Batch> {
+            bail!("boom")
+        }
+
+        let db = SymgraphDb::open_temporary().unwrap();
+        db.db.remove(VERSION_KEY).unwrap();
+
+        let error = run_migrations(&db.db, &[add_marker, broken]).unwrap_err();
+        assert!(format!("{:#}", error).contains("Migration 002 failed"));
+        assert_eq!(schema_version(&db.db).unwrap(), 1);
+        assert!(db.db.get("marker").unwrap().is_some());
+    }
+}
@@ -0,0 +1,111 @@
+//! Pool of database handles for concurrent requests
+//!
+//! [`SymgraphDb`] remains the single-handle API. Servers that answer several
+//! requests at once use a [`DbPool`] instead: every request checks out its own
+//! handle, and the pool caps how many are in use at the same time. sled lets
+//! handles share one open database, so a pooled handle is a clone of it.
+
+use anyhow::Result;
+use std::ops::Deref;
+
+use crate::SymgraphDb;
+
+/// Hands out clones of an already opened database
+#[derive(Clone)]
+pub struct SymgraphDbManager {
+    db: SymgraphDb,
+}
+
+impl r2d2::ManageConnection for SymgraphDbManager {
+    type Connection = SymgraphDb;
+    type Error = sled::Error;
+
+    fn connect(&self) -> Result<SymgraphDb, sled::Error> {
+        Ok(self.db.clone())
+    }
+
+    fn is_valid(&self, _conn: &mut SymgraphDb) -> Result<(), sled::Error> {
+        Ok(())
+    }
+
+    fn has_broken(&self, _conn: &mut SymgraphDb) -> bool {
+        false
+    }
+}
+
+/// A database handle checked out of a [`DbPool`]
+pub type PooledDb = r2d2::PooledConnection<SymgraphDbManager>;
+
+/// Bounded pool of [`SymgraphDb`] handles.
+///
+/// Query methods can be called on the pool directly; each call is served by
+/// the shared database, while [`DbPool::get`] checks out a handle of its own.
+#[derive(Clone)]
+pub struct DbPool {
+    pool: r2d2::Pool<SymgraphDbManager>,
+    db: SymgraphDb,
+}
+
+impl DbPool {
+    /// Open the database at `path` with at most `size` handles in use at once
+    pub fn open(path: &str, size: u32) -> Result<Self> {
+        Self::from_db(SymgraphDb::open(path)?, size)
+    }
+
+    /// Pool handles to an already opened database
+    pub fn from_db(db: SymgraphDb, size: u32) -> Result<Self> {
+        let manager = SymgraphDbManager { db: db.clone() };
+        let pool = r2d2::Pool::builder().max_size(size).build(manager)?;
+        Ok(Self { pool, db })
+    }
+
+    /// Check out a handle, waiting while all of them are in use
+    pub fn get(&self) -> Result<PooledDb> {
+        Ok(self.pool.get()?)
+    }
+
+    /// Maximum number of handles in use at once
+    pub fn size(&self) -> u32 {
+        self.pool.max_size()
+    }
+}
+
+impl Deref for DbPool {
+    type Target = SymgraphDb;
+
+    fn deref(&self) -> &SymgraphDb {
+        &self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+
+    #[test]
+    fn test_pool_serves_ten_threads() {
+        let pool = DbPool::from_db(SymgraphDb::open_temporary().unwrap(), 10).unwrap();
+        let barrier = Arc::new(Barrier::new(10));
+
+        let threads: Vec<_> = (0..10)
+            .map(|i| {
+                let pool = pool.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    let mut db = pool.get().unwrap();
+                    // All ten handles are checked out at the same time
+                    barrier.wait();
+                    db.ensure_file(&format!("src/file{}.cpp", i), "c++").unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        for i in 0..10 {
+            assert!(pool.get_file(&format!("src/file{}.cpp", i)).unwrap().is_some());
+        }
+    }
+}
@@ -10,6 +10,9 @@ use std::path::Path;
 use crate::SymgraphDb;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "scip-proto")]
+mod proto;
+
 /// SCIP document metadata stored in database
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScipDocumentInfo {
@@ -109,71 +112,187 @@ pub struct ScipRange {
 }
 
 /// Main SCIP parser
-pub struct ScipParser;
+///
+/// Decoding the protobuf index requires the `scip-proto` feature.
+pub struct ScipParser {
+    /// SCIP symbol string -> symgraph kind, filled while reading symbol information
+    #[cfg_attr(not(feature = "scip-proto"), allow(dead_code))]
+    symbol_cache: HashMap<String, String>,
+}
 
 impl ScipParser {
     /// Create a new SCIP parser
     pub fn new() -> Self {
-        Self
+        Self {
+            symbol_cache: HashMap::new(),
+        }
     }
 
     /// Parse a SCIP file from disk
-    pub fn parse_file<P: AsRef<Path>>(file_path: P) -> Result<ScipParsedData> {
+    pub fn parse_file<P: AsRef<Path>>(&mut self, file_path: P) -> Result<ScipParsedData> {
         let content = fs::read(file_path)
             .context("Failed to read SCIP file")?;
         
-        Self::parse_bytes(&content)
+        self.parse_bytes(&content)
     }
 
-    /// Parse SCIP data from bytes
-    pub fn parse_bytes(data: &[u8]) -> Result<ScipParsedData> {
-        // For now, we'll create a simple mock implementation
-        // TODO: Implement full protobuf parsing when protoc is available
-        Self::parse_mock_data(data)
-    }
+    /// Parse a binary `scip.Index` message
+    #[cfg(feature = "scip-proto")]
+    pub fn parse_bytes(&mut self, data: &[u8]) -> Result<ScipParsedData> {
+        use prost::Message;
+
+        let index = proto::Index::decode(data).context("Failed to decode SCIP protobuf")?;
+
+        let metadata = index.metadata.unwrap_or_default();
+        let tool_info = metadata.tool_info.unwrap_or_default();
+        let metadata = ScipMetadata {
+            version: metadata.version.to_string(),
+            tool_name: tool_info.name,
+            tool_version: tool_info.version,
+            project_roots: vec![metadata.project_root],
+        };
+
+        // Kinds first, so occurrences can be labelled regardless of document order
+        for info in index.documents.iter().flat_map(|d| &d.symbols).chain(&index.external_symbols) {
+            let kind = self.symbol_kind(info);
+            self.symbol_cache.insert(info.symbol.clone(), kind);
+        }
+
+        let mut documents = Vec::new();
+        let mut symbols = Vec::new();
+        let mut occurrences = Vec::new();
+
+        for document in index.documents {
+            documents.push(ScipDocument {
+                relative_path: document.relative_path.clone(),
+                language: document.language.to_lowercase(),
+                symbol_count: document.symbols.len(),
+                occurrence_count: document.occurrences.len(),
+            });
+
+            for occurrence in document.occurrences {
+                let Some(range) = convert_range(&occurrence.range) else {
+                    continue;
+                };
+                let roles = proto::SYMBOL_ROLES
+                    .iter()
+                    .filter(|(bit, _)| occurrence.symbol_roles & bit != 0)
+                    .map(|(_, name)| name.to_string())
+                    .collect();
+                let syntax_kind = match self.symbol_cache.get(&occurrence.symbol) {
+                    Some(kind) => kind.clone(),
+                    None => self.infer_symbol_kind(&occurrence.symbol).to_string(),
+                };
+                occurrences.push(ScipOccurrence {
+                    document_path: document.relative_path.clone(),
+                    symbol: occurrence.symbol,
+                    range,
+                    roles,
+                    syntax_kind,
+                });
+            }
+
+            symbols.extend(document.symbols.into_iter().map(|info| self.convert_symbol(info)));
+        }
+
+        symbols.extend(index.external_symbols.into_iter().map(|info| self.convert_symbol(info)));
 
-    /// Mock implementation for testing without protoc
-    fn parse_mock_data(_data: &[u8]) -> Result<ScipParsedData> {
         Ok(ScipParsedData {
-            metadata: ScipMetadata {
-                version: "0.1.0".to_string(),
-                tool_name: "rust-analyzer".to_string(),
-                tool_version: "1.92.0".to_string(),
-                project_roots: vec!["file:///project".to_string()],
-            },
-            documents: vec![
-                ScipDocument {
-                    relative_path: "src/main.rs".to_string(),
-                    language: "rust".to_string(),
-                    symbol_count: 1,
-                    occurrence_count: 2,
-                }
-            ],
-            symbols: vec![
-                ScipSymbol {
-                    symbol: "rust-analyzer cargo test_project 0.1.0 main()".to_string(),
-                    documentation: Some("Main function".to_string()),
-                    display_name: Some("main".to_string()),
-                    symbol_kind: "function".to_string(),
-                    relationships: vec![],
-                }
-            ],
-            occurrences: vec![
-                ScipOccurrence {
-                    document_path: "src/main.rs".to_string(),
-                    symbol: "rust-analyzer cargo test_project 0.1.0 main()".to_string(),
-                    range: ScipRange {
-                        start_line: 1,
-                        start_character: 0,
-                        end_line: 1,
-                        end_character: 10,
-                    },
-                    roles: vec!["definition".to_string()],
-                    syntax_kind: "function".to_string(),
-                }
-            ],
+            metadata,
+            documents,
+            symbols,
+            occurrences,
         })
     }
+
+    /// Without the `scip-proto` feature SCIP indexes cannot be decoded
+    #[cfg(not(feature = "scip-proto"))]
+    pub fn parse_bytes(&mut self, _data: &[u8]) -> Result<ScipParsedData> {
+        anyhow::bail!("SCIP support is disabled; rebuild symgraph-core with the `scip-proto` feature")
+    }
+
+    /// Guess a symbol kind from the shape of its SCIP symbol string
+    ///
+    /// Used when the indexer leaves `SymbolInformation.kind` unspecified.
+    pub fn infer_symbol_kind(&self, symbol: &str) -> &'static str {
+        let descriptor = symbol.trim_end_matches('.');
+        if descriptor.ends_with(')') {
+            return "function";
+        }
+        if descriptor.ends_with('#') {
+            return "type";
+        }
+        if descriptor.ends_with('/') || descriptor.contains("::") {
+            return "module";
+        }
+        let name = descriptor.rsplit(['/', '#', '.', ' ']).next().unwrap_or(descriptor);
+        if name.starts_with(|c: char| c.is_uppercase()) {
+            "type"
+        } else {
+            "variable"
+        }
+    }
+
+    #[cfg(feature = "scip-proto")]
+    fn symbol_kind(&self, info: &proto::SymbolInformation) -> String {
+        proto::kind_name(info.kind)
+            .unwrap_or_else(|| self.infer_symbol_kind(&info.symbol))
+            .to_string()
+    }
+
+    #[cfg(feature = "scip-proto")]
+    fn convert_symbol(&self, info: proto::SymbolInformation) -> ScipSymbol {
+        let symbol_kind = match self.symbol_cache.get(&info.symbol) {
+            Some(kind) => kind.clone(),
+            None => self.symbol_kind(&info),
+        };
+        let relationships = info
+            .relationships
+            .into_iter()
+            .flat_map(|rel| {
+                [
+                    (rel.is_reference, "reference"),
+                    (rel.is_implementation, "implementation"),
+                    (rel.is_type_definition, "type_definition"),
+                    (rel.is_definition, "definition"),
+                ]
+                .into_iter()
+                .filter(|(set, _)| *set)
+                .map(move |(_, kind)| ScipRelationship {
+                    kind: kind.to_string(),
+                    target_symbol: rel.symbol.clone(),
+                })
+            })
+            .collect();
+
+        ScipSymbol {
+            documentation: (!info.documentation.is_empty()).then(|| info.documentation.join("\n\n")),
+            display_name: (!info.display_name.is_empty()).then_some(info.display_name),
+            symbol: info.symbol,
+            symbol_kind,
+            relationships,
+        }
+    }
+}
+
+/// Convert a 3- or 4-element SCIP range; anything else is malformed
+#[cfg(feature = "scip-proto")]
+fn convert_range(range: &[i32]) -> Option<ScipRange> {
+    match *range {
+        [start_line, start_character, end_character] => Some(ScipRange {
+            start_line,
+            start_character,
+            end_line: start_line,
+            end_character,
+        }),
+        [start_line, start_character, end_line, end_character] => Some(ScipRange {
+            start_line,
+            start_character,
+            end_line,
+            end_character,
+        }),
+        _ => None,
+    }
 }
 
 impl Default for ScipParser {
@@ -184,12 +303,12 @@ impl Default for ScipParser {
 
 /// Utility function to quickly parse a SCIP file
 pub fn parse_scip_file<P: AsRef<Path>>(file_path: P) -> Result<ScipParsedData> {
-    ScipParser::parse_file(file_path)
+    ScipParser::new().parse_file(file_path)
 }
 
 /// Utility function to quickly parse SCIP bytes
 pub fn parse_scip_bytes(data: &[u8]) -> Result<ScipParsedData> {
-    ScipParser::parse_bytes(data)
+    ScipParser::new().parse_bytes(data)
 }
 
 /// Load SCIP data into symgraph database with complete information preservation
@@ -289,6 +408,8 @@ pub fn load_scip_to_database(db: &mut SymgraphDb, scip_data: &ScipParsedData, pr
                 &occurrence.roles.join(","),
                 occurrence.range.start_line as u32,
                 occurrence.range.start_character as u32,
+                Some(occurrence.range.end_line as u32),
+                Some(occurrence.range.end_character as u32),
             )?;
 
             // Store complete SCIP occurrence info
@@ -306,12 +427,14 @@ pub fn load_scip_to_database(db: &mut SymgraphDb, scip_data: &ScipParsedData, pr
     }
 
     // Fourth pass: Create symbol relationships based on SCIP relationships
+    let edge_kinds = crate::EdgeKindRegistry::new();
     for symbol in &scip_data.symbols {
         if let Some(from_scip_id) = symbol_ids.get(&symbol.symbol) {
             for relationship in &symbol.relationships {
                 if let Some(to_scip_id) = symbol_ids.get(&relationship.target_symbol) {
                     crate::insert_edge(
                         db,
+                        &edge_kinds,
                         Some(from_scip_id),
                         Some(to_scip_id),
                         None,
@@ -346,15 +469,40 @@ mod tests {
         assert_eq!(parser.infer_symbol_kind("module::submodule"), "module");
     }
 
+    /// Index produced by `rust-analyzer scip` for the `test_scip_project` hello-world crate
+    #[cfg(feature = "scip-proto")]
     #[test]
-    fn test_parse_mock_data() {
+    fn test_parse_rust_analyzer_index() {
         let mut parser = ScipParser::new();
-        let data = b"mock scip data";
+        let data = include_bytes!("../tests/fixtures/test_project.scip");
         let result = parser.parse_bytes(data).unwrap();
         
         assert_eq!(result.metadata.tool_name, "rust-analyzer");
         assert_eq!(result.documents.len(), 1);
-        assert_eq!(result.symbols.len(), 1);
-        assert_eq!(result.occurrences.len(), 1);
+        assert_eq!(result.documents[0].language, "rust");
+        assert!(result.documents[0].relative_path.ends_with("main.rs"));
+
+        let main = result
+            .symbols
+            .iter()
+            .find(|s| s.display_name.as_deref() == Some("main"))
+            .expect("main symbol");
+        assert_eq!(main.symbol, "rust-analyzer cargo test_project 0.1.0 main().");
+        assert_eq!(main.symbol_kind, "function");
+
+        let definition = result
+            .occurrences
+            .iter()
+            .find(|o| o.symbol == main.symbol)
+            .expect("main occurrence");
+        assert_eq!(definition.roles, vec!["definition".to_string()]);
+        assert_eq!((definition.range.start_line, definition.range.start_character), (0, 3));
+        assert_eq!(definition.syntax_kind, "function");
+    }
+
+    #[cfg(feature = "scip-proto")]
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert!(parse_scip_bytes(b"mock scip data").is_err());
     }
 }
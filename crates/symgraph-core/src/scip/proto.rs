@@ -0,0 +1,138 @@
+//! Protobuf messages of the SCIP index format
+//!
+//! Hand-written `prost` mirror of the subset of
+//! <https://github.com/sourcegraph/scip/blob/main/scip.proto> that symgraph
+//! reads. Field tags must match the upstream schema; fields not listed here
+//! are skipped by the decoder.
+
+/// `scip.Index`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Index {
+    #[prost(message, optional, tag = "1")]
+    pub metadata: Option<Metadata>,
+    #[prost(message, repeated, tag = "2")]
+    pub documents: Vec<Document>,
+    #[prost(message, repeated, tag = "3")]
+    pub external_symbols: Vec<SymbolInformation>,
+}
+
+/// `scip.Metadata`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Metadata {
+    #[prost(int32, tag = "1")]
+    pub version: i32,
+    #[prost(message, optional, tag = "2")]
+    pub tool_info: Option<ToolInfo>,
+    #[prost(string, tag = "3")]
+    pub project_root: String,
+    #[prost(int32, tag = "4")]
+    pub text_document_encoding: i32,
+}
+
+/// `scip.ToolInfo`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolInfo {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+    #[prost(string, repeated, tag = "3")]
+    pub arguments: Vec<String>,
+}
+
+/// `scip.Document`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Document {
+    #[prost(string, tag = "1")]
+    pub relative_path: String,
+    #[prost(message, repeated, tag = "2")]
+    pub occurrences: Vec<Occurrence>,
+    #[prost(message, repeated, tag = "3")]
+    pub symbols: Vec<SymbolInformation>,
+    #[prost(string, tag = "4")]
+    pub language: String,
+}
+
+/// `scip.SymbolInformation`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SymbolInformation {
+    #[prost(string, tag = "1")]
+    pub symbol: String,
+    #[prost(string, repeated, tag = "3")]
+    pub documentation: Vec<String>,
+    #[prost(message, repeated, tag = "4")]
+    pub relationships: Vec<Relationship>,
+    #[prost(int32, tag = "5")]
+    pub kind: i32,
+    #[prost(string, tag = "6")]
+    pub display_name: String,
+    #[prost(string, tag = "8")]
+    pub enclosing_symbol: String,
+}
+
+/// `scip.Relationship`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Relationship {
+    #[prost(string, tag = "1")]
+    pub symbol: String,
+    #[prost(bool, tag = "2")]
+    pub is_reference: bool,
+    #[prost(bool, tag = "3")]
+    pub is_implementation: bool,
+    #[prost(bool, tag = "4")]
+    pub is_type_definition: bool,
+    #[prost(bool, tag = "5")]
+    pub is_definition: bool,
+}
+
+/// `scip.Occurrence`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Occurrence {
+    /// `[startLine, startChar, endChar]` or `[startLine, startChar, endLine, endChar]`
+    #[prost(int32, repeated, tag = "1")]
+    pub range: Vec<i32>,
+    #[prost(string, tag = "2")]
+    pub symbol: String,
+    #[prost(int32, tag = "3")]
+    pub symbol_roles: i32,
+    #[prost(int32, tag = "5")]
+    pub syntax_kind: i32,
+}
+
+/// `scip.SymbolRole` bit flags with their symgraph names
+pub const SYMBOL_ROLES: &[(i32, &str)] = &[
+    (0x1, "definition"),
+    (0x2, "import"),
+    (0x4, "write_access"),
+    (0x8, "read_access"),
+    (0x10, "generated"),
+    (0x20, "test"),
+    (0x40, "forward_definition"),
+];
+
+/// symgraph name for a `scip.SymbolInformation.Kind` value, if it is one we map
+pub fn kind_name(kind: i32) -> Option<&'static str> {
+    let name = match kind {
+        7 => "class",
+        8 => "constant",
+        11 => "enum",
+        12 => "enum_member",
+        15 => "field",
+        17 => "function",
+        21 => "interface",
+        25 => "macro",
+        26 => "method",
+        29 => "module",
+        30 => "namespace",
+        37 => "parameter",
+        49 => "struct",
+        53 => "trait",
+        54 => "type",
+        55 => "type_alias",
+        58 => "type_parameter",
+        59 => "union",
+        61 => "variable",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -0,0 +1,261 @@
+//! Human-readable names for Clang USRs
+//!
+//! Turns e.g. `c:@N@utils@F@helper#I#` into `utils::helper(int)`. Only the
+//! common C and C++ patterns are decoded; anything else, including Objective-C
+//! USRs and non-Clang identifiers, is returned unchanged.
+
+/// Format a USR as a qualified C++-style name with parameter types.
+pub fn usr_to_display_name(usr: &str) -> String {
+    let Some(body) = usr.strip_prefix("c:") else {
+        return usr.to_string();
+    };
+    if body.starts_with("objc(") {
+        return usr.to_string();
+    }
+
+    // Static functions and macros carry a file (and offset) prefix before the first '@'
+    let mut parser = Parser::new(body);
+    parser.skip_until('@');
+
+    let mut scopes: Vec<String> = Vec::new();
+    let mut signature = String::new();
+    while parser.eat('@') {
+        let kind = parser.take_while(|c| c != '@');
+        match kind {
+            // Source offset of a local declaration
+            k if !k.is_empty() && k.chars().all(|c| c.is_ascii_digit()) => {}
+            "aN" => scopes.push("(anonymous namespace)".to_string()),
+            "Ea" | "Sa" | "Ua" => {}
+            "N" | "S" | "U" | "E" | "ST" | "SP" | "T" | "FI" | "macro" => {
+                parser.eat('@');
+                scopes.push(parser.name());
+                // Template specialization arguments are not decoded
+                if parser.peek() == Some('>') {
+                    parser.skip_until('@');
+                }
+            }
+            "F" | "FT" => {
+                parser.eat('@');
+                if kind == "FT" {
+                    parser.skip_template_parameters();
+                }
+                scopes.push(parser.name());
+                signature = parser.signature();
+            }
+            // A bare name: global variable, enum constant or local variable
+            name => {
+                signature.clear();
+                scopes.push(name.to_string());
+            }
+        }
+    }
+
+    if scopes.is_empty() {
+        return usr.to_string();
+    }
+    format!("{}{}", scopes.join("::"), signature)
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { rest: input }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(c)
+    }
+
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> &'a str {
+        let end = self.rest.find(|c| !keep(c)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        taken
+    }
+
+    fn skip_until(&mut self, stop: char) {
+        self.take_while(|c| c != stop);
+    }
+
+    /// Identifier up to the next separator
+    fn name(&mut self) -> String {
+        self.take_while(|c| !matches!(c, '@' | '#' | '>' | '$')).to_string()
+    }
+
+    /// `>N#T#T...` template parameter list of a function template
+    fn skip_template_parameters(&mut self) {
+        if self.eat('>') {
+            self.take_while(|c| c.is_ascii_digit());
+            while self.rest.starts_with('#') && !self.rest[1..].starts_with('#') {
+                self.bump();
+                self.bump();
+            }
+        }
+    }
+
+    /// `#type#type...#quals` after a function name, rendered as `(type, type) quals`.
+    ///
+    /// C functions have no parameter encoding and yield an empty string.
+    fn signature(&mut self) -> String {
+        if self.peek() != Some('#') {
+            return String::new();
+        }
+        let mut params = Vec::new();
+        let mut qualifiers = String::new();
+        while self.eat('#') {
+            // Trailing method qualifiers: static, cv and ref qualifiers
+            let quals_len = self
+                .rest
+                .find(|c: char| !matches!(c, 'S' | '0'..='9' | '&'))
+                .unwrap_or(self.rest.len());
+            if matches!(self.rest[quals_len..].chars().next(), None | Some('@')) {
+                let quals = self.take_while(|c| c != '@');
+                if quals.chars().any(|c| c.to_digit(10).is_some_and(|d| d & 1 != 0)) {
+                    qualifiers.push_str(" const");
+                }
+                if quals.ends_with("&&") {
+                    qualifiers.push_str(" &&");
+                } else if quals.ends_with('&') {
+                    qualifiers.push_str(" &");
+                }
+                break;
+            }
+            if self.eat('.') {
+                params.push("...".to_string());
+                continue;
+            }
+            params.push(self.parse_type());
+            if self.eat('.') {
+                params.push("...".to_string());
+            }
+        }
+        format!("({}){}", params.join(", "), qualifiers)
+    }
+
+    fn parse_type(&mut self) -> String {
+        let Some(c) = self.bump() else {
+            return "?".to_string();
+        };
+        match c {
+            '0'..='9' => {
+                let bits = c.to_digit(10).unwrap_or(0);
+                let inner = self.parse_type();
+                let mut prefix = String::new();
+                if bits & 1 != 0 {
+                    prefix.push_str("const ");
+                }
+                if bits & 2 != 0 {
+                    prefix.push_str("volatile ");
+                }
+                format!("{}{}", prefix, inner)
+            }
+            '*' => format!("{}*", self.parse_type()),
+            '&' => {
+                if self.eat('&') {
+                    format!("{}&&", self.parse_type())
+                } else {
+                    format!("{}&", self.parse_type())
+                }
+            }
+            '$' => {
+                let mut scopes = Vec::new();
+                while self.eat('@') {
+                    let kind = self.take_while(|c| c != '@');
+                    if kind == "aN" {
+                        scopes.push("(anonymous namespace)".to_string());
+                        continue;
+                    }
+                    self.eat('@');
+                    scopes.push(self.name());
+                }
+                scopes.join("::")
+            }
+            other => match builtin_type(other) {
+                Some(name) => name.to_string(),
+                None => {
+                    self.skip_until('#');
+                    "?".to_string()
+                }
+            },
+        }
+    }
+}
+
+/// Clang's one-letter encoding of builtin types
+fn builtin_type(c: char) -> Option<&'static str> {
+    let name = match c {
+        'v' => "void",
+        'b' => "bool",
+        'c' => "unsigned char",
+        'C' => "char",
+        'r' => "signed char",
+        'q' => "char16_t",
+        'w' => "char32_t",
+        'W' => "wchar_t",
+        's' => "unsigned short",
+        'S' => "short",
+        'i' => "unsigned int",
+        'I' => "int",
+        'l' => "unsigned long",
+        'L' => "long",
+        'k' => "unsigned long long",
+        'K' => "long long",
+        'f' => "float",
+        'd' => "double",
+        'D' => "long double",
+        'n' => "nullptr_t",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usr_to_display_name() {
+        let cases = [
+            ("c:@F@main#", "main()"),
+            ("c:@N@utils@F@helper#I#", "utils::helper(int)"),
+            ("c:@F@mix#I#d#", "mix(int, double)"),
+            ("c:@F@puts#*1C#", "puts(const char*)"),
+            ("c:@F@printf", "printf"),
+            ("c:@S@Widget", "Widget"),
+            ("c:@N@gfx@S@Canvas", "gfx::Canvas"),
+            ("c:@S@Widget@F@draw#1", "Widget::draw() const"),
+            ("c:@N@gfx@S@Canvas@F@resize#i#i#", "gfx::Canvas::resize(unsigned int, unsigned int)"),
+            ("c:@S@Widget@FI@width", "Widget::width"),
+            ("c:@E@Color@Red", "Color::Red"),
+            ("c:@F@load#&1$@N@app@S@Config#", "load(const app::Config&)"),
+            ("c:@aN@F@local#", "(anonymous namespace)::local()"),
+            ("c:util.cpp@F@clamp#d#d#d#", "clamp(double, double, double)"),
+            ("c:config.h@120@macro@MAX_SIZE", "MAX_SIZE"),
+            ("c:@counter", "counter"),
+            ("c:objc(cs)NSObject", "c:objc(cs)NSObject"),
+            ("plain_name", "plain_name"),
+        ];
+        for (usr, expected) in cases {
+            assert_eq!(usr_to_display_name(usr), expected, "USR {}", usr);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 pub mod modules;
 
 use clang::{Entity, EntityKind, TranslationUnit};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,7 +44,8 @@ pub fn categorize_cpp_file(path: &str) -> FileCategory {
     }
     
     if filename.ends_with(".cpp") || filename.ends_with(".cc") 
-        || filename.ends_with(".cxx") || filename.ends_with(".c") {
+        || filename.ends_with(".cxx") || filename.ends_with(".c")
+        || filename.ends_with(".m") || filename.ends_with(".mm") {
         return FileCategory::Implementation;
     }
     
@@ -62,6 +64,43 @@ pub fn categorize_cpp_file(path: &str) -> FileCategory {
     FileCategory::Unknown
 }
 
+/// Like [`categorize_cpp_file`], but a file that includes a test framework
+/// (Google Test, Catch2, Boost.Test) or declares test cases is a unit test
+/// whatever its path
+pub fn categorize_cpp_file_from_content(path: &str, content: &str) -> FileCategory {
+    if test_framework(content).is_some() {
+        return FileCategory::UnitTest;
+    }
+    categorize_cpp_file(path)
+}
+
+/// Like [`infer_cpp_purpose`], but names the test framework of unit tests
+pub fn infer_cpp_purpose_from_content(path: &str, content: &str, category: &FileCategory) -> String {
+    match (category, test_framework(content)) {
+        (FileCategory::UnitTest, Some(framework)) => format!("Unit tests ({})", framework),
+        _ => infer_cpp_purpose(path, category),
+    }
+}
+
+/// Test framework a C++ source uses, judged by its includes and test case macros
+fn test_framework(content: &str) -> Option<&'static str> {
+    const FRAMEWORKS: [(&str, &[&str], &[&str]); 3] = [
+        ("Google Test", &["gtest/gtest.h", "gmock/gmock.h"], &["TEST(", "TEST_F(", "TEST_P("]),
+        ("Catch2", &["catch2/", "catch.hpp"], &["TEST_CASE(", "CATCH_TEST_CASE(", "SCENARIO("]),
+        ("Boost.Test", &["boost/test/"], &["BOOST_AUTO_TEST_CASE(", "BOOST_FIXTURE_TEST_CASE("]),
+    ];
+
+    for line in content.lines().map(str::trim_start) {
+        for (name, headers, macros) in FRAMEWORKS {
+            let includes = line.starts_with("#include") && headers.iter().any(|h| line.contains(h));
+            if includes || macros.iter().any(|m| line.starts_with(m)) {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
 pub fn infer_cpp_purpose(path: &str, category: &FileCategory) -> String {
     let path_lower = path.to_lowercase();
     
@@ -110,14 +149,63 @@ fn is_declaration_kind(kind: EntityKind) -> bool {
             | EntityKind::TypedefDecl
             | EntityKind::Method
             | EntityKind::Namespace
+            | EntityKind::NamespaceAlias
             | EntityKind::Constructor
             | EntityKind::Destructor
             | EntityKind::ClassTemplate
             | EntityKind::FunctionTemplate
             | EntityKind::UnionDecl
+            | EntityKind::ObjCInterfaceDecl
+            | EntityKind::ObjCImplementationDecl
+            | EntityKind::ObjCCategoryDecl
+            | EntityKind::ObjCProtocolDecl
+            | EntityKind::ObjCInstanceMethodDecl
+            | EntityKind::ObjCClassMethodDecl
+            | EntityKind::ObjCPropertyDecl
+    )
+}
+
+/// Objective-C containers whose methods and properties are scanned as members
+fn is_objc_container_kind(kind: EntityKind) -> bool {
+    matches!(
+        kind,
+        EntityKind::ObjCInterfaceDecl
+            | EntityKind::ObjCImplementationDecl
+            | EntityKind::ObjCCategoryDecl
+            | EntityKind::ObjCProtocolDecl
     )
 }
 
+/// Declaration scopes whose contents [`scan_tu`] visits: namespaces,
+/// `extern "C"` blocks and class bodies. Those of system headers are skipped
+/// to keep the standard library out of the symbol table.
+fn is_scope_kind(kind: EntityKind) -> bool {
+    matches!(
+        kind,
+        EntityKind::Namespace
+            | EntityKind::LinkageSpec
+            | EntityKind::ClassDecl
+            | EntityKind::StructDecl
+            | EntityKind::UnionDecl
+            | EntityKind::ClassTemplate
+            | EntityKind::ClassTemplatePartialSpecialization
+    )
+}
+
+/// C++20 concept declaration
+///
+/// libclang reports `CXCursor_ConceptDecl`, but the `clang` crate predates it and
+/// maps the cursor to `NotImplemented`, so concepts are recognised by their
+/// `@CT@` USR instead.
+fn is_concept_decl(entity: &Entity) -> bool {
+    entity.get_kind() == EntityKind::NotImplemented
+        && usr_to_string(entity).is_some_and(|usr| is_concept_usr(&usr))
+}
+
+fn is_concept_usr(usr: &str) -> bool {
+    usr.contains("@CT@")
+}
+
 fn is_expression_or_reference_kind(kind: EntityKind) -> bool {
     matches!(
         kind,
@@ -132,11 +220,25 @@ fn is_expression_or_reference_kind(kind: EntityKind) -> bool {
     )
 }
 
+fn is_record_kind(kind: EntityKind) -> bool {
+    matches!(kind, EntityKind::StructDecl | EntityKind::UnionDecl | EntityKind::ClassDecl)
+}
+
+/// `struct` or `union` without a name or typedef name
+fn is_anonymous_record(entity: &Entity) -> bool {
+    is_record_kind(entity.get_kind()) && entity.is_anonymous()
+}
+
 fn usr_to_string(entity: &Entity) -> Option<String> {
     entity.get_usr().map(|u| u.0.clone())
 }
 
-#[derive(Debug, Serialize)]
+/// USR of the entity's semantic parent; `None` at translation unit scope
+fn parent_usr(entity: &Entity) -> Option<String> {
+    entity.get_semantic_parent().and_then(|p| usr_to_string(&p))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Symbol {
     pub usr: Option<String>,
     pub name: String,
@@ -145,9 +247,24 @@ pub struct Symbol {
     pub file: String,
     pub line: u32,
     pub column: u32,
+    /// Template arguments of a template instantiation, e.g. `int`
+    #[serde(default)]
+    pub template_args: Option<String>,
+    /// Attributes such as `nodiscard` or `deprecated`, stored in the `symbol_meta` column
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// First and last line of a definition, including the function or class body
+    #[serde(default)]
+    pub def_lines: Option<(u32, u32)>,
+    /// Doc comment attached to the declaration, without comment markers
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// USR of the semantic parent: the enclosing namespace, class or function
+    #[serde(default)]
+    pub parent_usr: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Occurrence {
     pub usr: Option<String>,
     pub usage_kind: String,
@@ -164,10 +281,11 @@ pub fn scan_tu(
     let mut edges = Vec::new();
 
     let root = tu.get_entity();
-    root.visit_children(|entity, _parent| {
+    root.visit_children(|entity, parent| {
         let kind = entity.get_kind();
 
-        if is_declaration_kind(kind) {
+        // Anonymous records are opt-in, see `scan_anonymous_records`
+        if is_declaration_kind(kind) && !is_anonymous_record(&entity) {
             let usr = usr_to_string(&entity);
             if let Some(loc) = entity.get_location() {
                 let file_loc = loc.get_file_location();
@@ -177,6 +295,10 @@ pub fn scan_tu(
                     .unwrap_or_default();
                 let line = file_loc.line;
                 let col = file_loc.column;
+                let def_lines = entity
+                    .get_range()
+                    .filter(|_| entity.is_definition())
+                    .map(|range| (range.get_start().get_file_location().line, range.get_end().get_file_location().line));
                 symbols.push(Symbol {
                     usr: usr.clone(),
                     name: entity.get_display_name().unwrap_or_default(),
@@ -185,9 +307,21 @@ pub fn scan_tu(
                     file,
                     line,
                     column: col,
+                    template_args: None,
+                    metadata: HashMap::new(),
+                    def_lines,
+                    doc: entity.get_comment().map(|raw| clean_doc_comment(&raw)).filter(|doc| !doc.is_empty()),
+                    parent_usr: parent_usr(&entity),
                 });
             }
-            if matches!(kind, EntityKind::FieldDecl | EntityKind::Method) {
+            if matches!(
+                kind,
+                EntityKind::FieldDecl
+                    | EntityKind::Method
+                    | EntityKind::ObjCInstanceMethodDecl
+                    | EntityKind::ObjCClassMethodDecl
+                    | EntityKind::ObjCPropertyDecl
+            ) {
                 if let Some(owner) = entity.get_semantic_parent() {
                     let from = usr_to_string(&owner);
                     let to = usr_to_string(&entity);
@@ -196,14 +330,99 @@ pub fn scan_tu(
                     }
                 }
             }
-            if kind == EntityKind::BaseSpecifier {
-                if let Some(derived) = entity.get_semantic_parent().and_then(|p| usr_to_string(&p))
+            if matches!(kind, EntityKind::Constructor | EntityKind::Destructor) {
+                let edge_kind = if kind == EntityKind::Constructor { "constructs" } else { "destructs" };
+                if let Some(class) = entity.get_semantic_parent().and_then(|p| usr_to_string(&p)) {
+                    if let Some(special) = usr_to_string(&entity) {
+                        edges.push((edge_kind.to_string(), special, class));
+                    }
+                }
+            }
+            if matches!(
+                kind,
+                EntityKind::FunctionDecl
+                    | EntityKind::FunctionTemplate
+                    | EntityKind::ClassDecl
+                    | EntityKind::StructDecl
+                    | EntityKind::ClassTemplate
+            ) {
+                if let Some(namespace) = entity
+                    .get_semantic_parent()
+                    .filter(|p| p.get_kind() == EntityKind::Namespace)
+                    .and_then(|p| usr_to_string(&p))
                 {
-                    if let Some(base) = entity.get_reference().and_then(|r| usr_to_string(&r)) {
-                        edges.push(("inherit".to_string(), base, derived));
+                    if let Some(member) = usr_to_string(&entity) {
+                        edges.push(("namespace-member".to_string(), namespace, member));
                     }
                 }
             }
+            if kind == EntityKind::NamespaceAlias {
+                // `namespace fs = std::filesystem;` names the aliased namespace
+                // through its last `NamespaceRef` child
+                let original = entity
+                    .get_children()
+                    .into_iter()
+                    .rfind(|c| c.get_kind() == EntityKind::NamespaceRef)
+                    .and_then(|c| c.get_reference())
+                    .and_then(|r| usr_to_string(&r));
+                if let (Some(alias), Some(original)) = (usr_to_string(&entity), original) {
+                    edges.push(("namespace-alias".to_string(), alias, original));
+                }
+            }
+            if kind == EntityKind::ObjCCategoryDecl {
+                // A category extends the class it references
+                let extended = entity
+                    .get_children()
+                    .into_iter()
+                    .find(|c| c.get_kind() == EntityKind::ObjCClassRef)
+                    .and_then(|c| c.get_reference())
+                    .and_then(|r| usr_to_string(&r));
+                if let (Some(category), Some(class)) = (usr_to_string(&entity), extended) {
+                    edges.push(("objc-category".to_string(), category, class));
+                }
+            }
+        }
+
+        // A base specifier is a reference, not a declaration; libclang gives it
+        // no semantic parent, so the derived class is the visited parent
+        if kind == EntityKind::BaseSpecifier {
+            if let (Some(base), Some(derived)) =
+                (entity.get_reference().and_then(|r| usr_to_string(&r)), usr_to_string(&parent))
+            {
+                edges.push(("inherit".to_string(), base, derived));
+            }
+        }
+
+        // System headers define thousands of macros per translation unit
+        if kind == EntityKind::MacroDefinition && !entity.is_in_system_header() {
+            if let Some((file, line, column)) = file_location(&entity) {
+                symbols.push(Symbol {
+                    usr: usr_to_string(&entity),
+                    name: entity.get_name().unwrap_or_default(),
+                    kind: "MacroDefinition".to_string(),
+                    is_definition: true,
+                    file,
+                    line,
+                    column,
+                    template_args: None,
+                    metadata: HashMap::new(),
+                    def_lines: None,
+                    doc: None,
+                    parent_usr: None,
+                });
+            }
+        }
+
+        if kind == EntityKind::MacroExpansion {
+            if let Some((file, line, column)) = file_location(&entity) {
+                occs.push(Occurrence {
+                    usr: entity.get_reference().and_then(|r| usr_to_string(&r)),
+                    usage_kind: "macro_expansion".to_string(),
+                    file,
+                    line,
+                    column,
+                });
+            }
         }
 
         if is_expression_or_reference_kind(kind) {
@@ -237,12 +456,369 @@ pub fn scan_tu(
             }
         }
 
-        clang::EntityVisitResult::Continue
+        // Anonymous records are left to `scan_anonymous_records` along with their fields
+        let is_scope = is_scope_kind(kind) && !is_anonymous_record(&entity) && !entity.is_in_system_header();
+        if is_objc_container_kind(kind) || is_scope {
+            clang::EntityVisitResult::Recurse
+        } else {
+            clang::EntityVisitResult::Continue
+        }
     });
 
+    scan_concepts(&root, &mut symbols, &mut occs);
+    scan_instantiations(&root, &mut symbols, &mut edges);
+    scan_attributes(&root, &mut symbols, &mut occs);
+
     (symbols, occs, edges)
 }
 
+/// Collect anonymous structs and unions of the main file, e.g. `struct { int x; } data;`.
+///
+/// [`scan_tu`] leaves them out because they have no name to build a USR from. Each one
+/// gets the synthetic USR `anon:<parent USR>:<index>`, where the index is its
+/// position among the parent's children and file-scope records use the file path
+/// as parent. The records are emitted as `AnonStruct` or `AnonUnion` symbols
+/// together with their fields, linked by `member` edges from the enclosing record
+/// to the anonymous one and from the anonymous record to each field.
+pub fn scan_anonymous_records(tu: &TranslationUnit) -> (Vec<Symbol>, Vec<(String, String, String)>) {
+    let mut symbols = Vec::new();
+    let mut edges = Vec::new();
+    let root = tu.get_entity();
+    let file = root.get_name().unwrap_or_default();
+    collect_anonymous_records(&root, &file, false, &mut symbols, &mut edges);
+    (symbols, edges)
+}
+
+fn collect_anonymous_records(
+    parent: &Entity,
+    parent_usr: &str,
+    parent_is_record: bool,
+    symbols: &mut Vec<Symbol>,
+    edges: &mut Vec<(String, String, String)>,
+) {
+    for (index, child) in parent.get_children().into_iter().enumerate() {
+        if !child.is_in_main_file() {
+            continue;
+        }
+        let kind = child.get_kind();
+        let is_record = is_record_kind(kind);
+        if is_anonymous_record(&child) {
+            let usr = format!("anon:{}:{}", parent_usr, index);
+            let (symbol_kind, name) = if kind == EntityKind::UnionDecl {
+                ("AnonUnion", "(anonymous union)")
+            } else {
+                ("AnonStruct", "(anonymous struct)")
+            };
+            if let Some((file, line, column)) = file_location(&child) {
+                symbols.push(Symbol {
+                    usr: Some(usr.clone()),
+                    name: name.to_string(),
+                    kind: symbol_kind.to_string(),
+                    is_definition: child.is_definition(),
+                    file,
+                    line,
+                    column,
+                    template_args: None,
+                    metadata: HashMap::new(),
+                    def_lines: None,
+                    doc: None,
+                    parent_usr: (parent.get_kind() != EntityKind::TranslationUnit).then(|| parent_usr.to_string()),
+                });
+            }
+            if parent_is_record {
+                edges.push(("member".to_string(), parent_usr.to_string(), usr.clone()));
+            }
+            for field in child.get_children().into_iter().filter(|c| c.get_kind() == EntityKind::FieldDecl) {
+                let (Some(field_usr), Some((file, line, column))) = (usr_to_string(&field), file_location(&field)) else {
+                    continue;
+                };
+                symbols.push(Symbol {
+                    usr: Some(field_usr.clone()),
+                    name: field.get_display_name().unwrap_or_default(),
+                    kind: "FieldDecl".to_string(),
+                    is_definition: true,
+                    file,
+                    line,
+                    column,
+                    template_args: None,
+                    metadata: HashMap::new(),
+                    def_lines: None,
+                    doc: None,
+                    parent_usr: Some(usr.clone()),
+                });
+                edges.push(("member".to_string(), usr.clone(), field_usr));
+            }
+            collect_anonymous_records(&child, &usr, true, symbols, edges);
+        } else if is_record || kind == EntityKind::Namespace {
+            if let Some(usr) = usr_to_string(&child) {
+                collect_anonymous_records(&child, &usr, is_record, symbols, edges);
+            }
+        }
+    }
+}
+
+/// Collect template specializations and `instantiates` edges to their templates.
+///
+/// Explicit and partial specializations declared in the main file are linked
+/// to their primary template. Implicit instantiations have no declaration in
+/// the AST, so they are found through the types of variables, fields and
+/// parameters, e.g. `std::vector<int> v;`, and recorded as symbols with their
+/// template arguments.
+fn scan_instantiations(
+    root: &Entity,
+    symbols: &mut Vec<Symbol>,
+    edges: &mut Vec<(String, String, String)>,
+) {
+    let mut seen = std::collections::HashSet::new();
+    root.visit_children(|entity, _parent| {
+        if !entity.is_in_main_file() {
+            return clang::EntityVisitResult::Continue;
+        }
+
+        let kind = entity.get_kind();
+        if matches!(
+            kind,
+            EntityKind::ClassDecl
+                | EntityKind::StructDecl
+                | EntityKind::ClassTemplatePartialSpecialization
+                | EntityKind::FunctionDecl
+                | EntityKind::Method
+        ) {
+            let template = entity.get_template().and_then(|t| usr_to_string(&t));
+            if let (Some(specialization), Some(template)) = (usr_to_string(&entity), template) {
+                edges.push(("instantiates".to_string(), specialization, template));
+            }
+        }
+
+        if matches!(kind, EntityKind::VarDecl | EntityKind::FieldDecl | EntityKind::ParmDecl) {
+            if let Some(ty) = entity.get_type() {
+                record_type_instantiation(ty, &mut seen, symbols, edges);
+            }
+        }
+        clang::EntityVisitResult::Recurse
+    });
+}
+
+/// Record the class template specialization named by `ty`, once per USR
+fn record_type_instantiation(
+    ty: clang::Type,
+    seen: &mut std::collections::HashSet<String>,
+    symbols: &mut Vec<Symbol>,
+    edges: &mut Vec<(String, String, String)>,
+) {
+    let ty = ty.get_canonical_type();
+    let Some(arguments) = ty.get_template_argument_types() else {
+        return;
+    };
+    let Some(declaration) = ty.get_declaration() else {
+        return;
+    };
+    let (Some(usr), Some(template)) = (
+        usr_to_string(&declaration),
+        declaration.get_template().and_then(|t| usr_to_string(&t)),
+    ) else {
+        return;
+    };
+    if !seen.insert(usr.clone()) {
+        return;
+    }
+
+    let template_args = arguments
+        .iter()
+        .map(|arg| arg.map(|t| t.get_display_name()).unwrap_or_else(|| "?".to_string()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Some((file, line, column)) = file_location(&declaration) {
+        symbols.push(Symbol {
+            usr: Some(usr.clone()),
+            name: ty.get_display_name(),
+            kind: "ClassTemplateInstantiation".to_string(),
+            is_definition: false,
+            file,
+            line,
+            column,
+            template_args: Some(template_args),
+            metadata: HashMap::new(),
+            def_lines: None,
+            doc: None,
+            parent_usr: parent_usr(&declaration),
+        });
+    }
+    edges.push(("instantiates".to_string(), usr, template));
+}
+
+/// Attach standard attributes to function symbols and record discarded results.
+///
+/// `nodiscard`, `deprecated` and `maybe_unused` on functions and methods in
+/// the main file end up in the symbol metadata. A call to a `nodiscard`
+/// function that is a statement of its own ignores the returned value and is
+/// recorded as a `discarded_result` occurrence (best effort: results dropped
+/// through a temporary or a comma expression are not detected).
+fn scan_attributes(root: &Entity, symbols: &mut Vec<Symbol>, occs: &mut Vec<Occurrence>) {
+    root.visit_children(|entity, parent| {
+        if !entity.is_in_main_file() {
+            return clang::EntityVisitResult::Continue;
+        }
+
+        let kind = entity.get_kind();
+        if matches!(kind, EntityKind::FunctionDecl | EntityKind::Method) {
+            let attributes = function_attributes(&entity);
+            if !attributes.is_empty() {
+                let usr = usr_to_string(&entity);
+                let metadata: HashMap<String, String> =
+                    attributes.iter().map(|a| (a.to_string(), "true".to_string())).collect();
+                match symbols.iter_mut().find(|s| s.usr.is_some() && s.usr == usr) {
+                    Some(symbol) => symbol.metadata.extend(metadata),
+                    None => {
+                        if let Some((file, line, column)) = file_location(&entity) {
+                            symbols.push(Symbol {
+                                usr,
+                                name: entity.get_display_name().unwrap_or_default(),
+                                kind: format!("{:?}", kind),
+                                is_definition: entity.is_definition(),
+                                file,
+                                line,
+                                column,
+                                template_args: None,
+                                metadata,
+                                def_lines: None,
+                                doc: None,
+                                parent_usr: parent_usr(&entity),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        if kind == EntityKind::CallExpr && parent.get_kind() == EntityKind::CompoundStmt {
+            if let Some(callee) = entity.get_reference() {
+                if function_attributes(&callee).contains(&"nodiscard") {
+                    if let Some((file, line, column)) = file_location(&entity) {
+                        occs.push(Occurrence {
+                            usr: usr_to_string(&callee),
+                            usage_kind: "discarded_result".to_string(),
+                            file,
+                            line,
+                            column,
+                        });
+                    }
+                }
+            }
+        }
+        clang::EntityVisitResult::Recurse
+    });
+}
+
+/// `nodiscard`, `deprecated` and `maybe_unused` attributes of a function
+///
+/// libclang exposes `nodiscard` as its own cursor and `deprecated` through the
+/// availability; other attributes are only visible in their source tokens.
+fn function_attributes(entity: &Entity) -> Vec<&'static str> {
+    let mut attributes = Vec::new();
+    if entity.get_availability() == clang::Availability::Deprecated {
+        attributes.push("deprecated");
+    }
+    for child in entity.get_children() {
+        let spelling = match child.get_kind() {
+            EntityKind::WarnUnusedResultAttr => "nodiscard".to_string(),
+            EntityKind::UnexposedAttr => child
+                .get_range()
+                .map(|r| r.tokenize().iter().map(|t| t.get_spelling()).collect::<String>())
+                .unwrap_or_default(),
+            _ => continue,
+        };
+        for attribute in ["nodiscard", "deprecated", "maybe_unused"] {
+            if spelling.contains(attribute) && !attributes.contains(&attribute) {
+                attributes.push(attribute);
+            }
+        }
+    }
+    attributes
+}
+
+/// Collect concept declarations and `concept_use` occurrences.
+///
+/// Concepts are mostly used inside template declarations, which the main
+/// visitor does not descend into, so the main file is walked in full here.
+/// Namespaces in other files are entered to find concepts declared there.
+fn scan_concepts(root: &Entity, symbols: &mut Vec<Symbol>, occs: &mut Vec<Occurrence>) {
+    let mut seen = std::collections::HashSet::new();
+    root.visit_children(|entity, _parent| {
+        if is_concept_decl(&entity) {
+            if let Some((file, line, column)) = file_location(&entity) {
+                symbols.push(Symbol {
+                    usr: usr_to_string(&entity),
+                    name: entity.get_name().unwrap_or_default(),
+                    kind: "ConceptDecl".to_string(),
+                    is_definition: true,
+                    file,
+                    line,
+                    column,
+                    template_args: None,
+                    metadata: HashMap::new(),
+                    def_lines: None,
+                    doc: None,
+                    parent_usr: parent_usr(&entity),
+                });
+            }
+            return clang::EntityVisitResult::Continue;
+        }
+
+        if !entity.is_in_main_file() {
+            return if entity.get_kind() == EntityKind::Namespace {
+                clang::EntityVisitResult::Recurse
+            } else {
+                clang::EntityVisitResult::Continue
+            };
+        }
+
+        // A concept-id such as `Printable<T>` and its name reference share a location
+        let target = entity.get_reference().and_then(|r| usr_to_string(&r));
+        if let Some(usr) = target.filter(|usr| is_concept_usr(usr)) {
+            if let Some((file, line, column)) = file_location(&entity) {
+                if seen.insert((file.clone(), line, column)) {
+                    occs.push(Occurrence {
+                        usr: Some(usr),
+                        usage_kind: "concept_use".to_string(),
+                        file,
+                        line,
+                        column,
+                    });
+                }
+            }
+        }
+        clang::EntityVisitResult::Recurse
+    });
+}
+
+/// File path, line and column of an entity's location
+fn file_location(entity: &Entity) -> Option<(String, u32, u32)> {
+    let file_loc = entity.get_location()?.get_file_location();
+    let file = file_loc.file?.get_path().display().to_string();
+    Some((file, file_loc.line, file_loc.column))
+}
+
+/// Doc comment text without the `/**`, `*/`, `///`, `//!` markers and leading `*`
+fn clean_doc_comment(raw: &str) -> String {
+    let raw = raw.trim();
+    let raw = raw.strip_suffix("*/").unwrap_or(raw);
+    raw.lines()
+        .map(|line| {
+            let line = line.trim();
+            ["/**", "/*!", "///", "//!", "*"]
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+                .unwrap_or(line)
+                .trim()
+        })
+        .skip_while(|line| line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
 fn classify_usage(entity: &Entity) -> String {
     match entity.get_kind() {
         EntityKind::CallExpr => "call",
@@ -265,6 +841,58 @@ mod categorization_tests {
         assert_eq!(categorize_cpp_file("test_utils.cpp"), FileCategory::UnitTest);
         assert_eq!(categorize_cpp_file("CMakeLists.txt"), FileCategory::Configuration);
         assert_eq!(categorize_cpp_file("src/network/client.cpp"), FileCategory::Implementation);
+
+        // The content-aware variant agrees on files without test markers
+        let plain = "#include <vector>\nint parse();\n";
+        assert_eq!(categorize_cpp_file_from_content("main.cpp", plain), FileCategory::EntryPoint);
+        assert_eq!(categorize_cpp_file_from_content("utils.h", plain), FileCategory::Header);
+        assert_eq!(categorize_cpp_file_from_content("test_utils.cpp", plain), FileCategory::UnitTest);
+        assert_eq!(categorize_cpp_file_from_content("src/network/client.cpp", plain), FileCategory::Implementation);
+
+        // A Google Test suite is a unit test even when its path does not say so
+        let gtest = "#include <gtest/gtest.h>\n#include \"parser.h\"\n\nTEST(Parser, Empty) {}\n";
+        assert_eq!(categorize_cpp_file_from_content("src/parser.cpp", gtest), FileCategory::UnitTest);
+        assert_eq!(
+            infer_cpp_purpose_from_content("src/parser.cpp", gtest, &FileCategory::UnitTest),
+            "Unit tests (Google Test)"
+        );
+    }
+
+    #[test]
+    fn test_cpp_categorization_catch2() {
+        let catch2 = "#include <catch2/catch_test_macros.hpp>\n\nTEST_CASE(\"parses numbers\") {}\n";
+        assert_eq!(categorize_cpp_file_from_content("src/parser.cpp", catch2), FileCategory::UnitTest);
+        assert_eq!(
+            infer_cpp_purpose_from_content("src/parser.cpp", catch2, &FileCategory::UnitTest),
+            "Unit tests (Catch2)"
+        );
+
+        // Test case macros alone are enough
+        let prefixed = "#include \"catch_amalgamated.hpp\"\nCATCH_TEST_CASE(\"lexes\") {}\n";
+        assert_eq!(categorize_cpp_file_from_content("src/lexer.cpp", prefixed), FileCategory::UnitTest);
+    }
+
+    #[test]
+    fn test_cpp_categorization_boost_test() {
+        let boost = "#define BOOST_TEST_MODULE parser\n#include <boost/test/unit_test.hpp>\n\nBOOST_AUTO_TEST_CASE(empty) {}\n";
+        assert_eq!(categorize_cpp_file_from_content("src/parser.cpp", boost), FileCategory::UnitTest);
+        assert_eq!(
+            infer_cpp_purpose_from_content("src/parser.cpp", boost, &FileCategory::UnitTest),
+            "Unit tests (Boost.Test)"
+        );
+
+        // Mentioning a macro inside other code is not a test case
+        let mention = "// see BOOST_AUTO_TEST_CASE( in the docs\nint run() { return MY_TEST(1); }\n";
+        assert_eq!(categorize_cpp_file_from_content("src/run.cpp", mention), FileCategory::Implementation);
+    }
+
+    #[test]
+    fn test_objc_categorization() {
+        assert_eq!(categorize_cpp_file("AppDelegate.m"), FileCategory::Implementation);
+        assert_eq!(categorize_cpp_file("ViewController.mm"), FileCategory::Implementation);
+        assert_eq!(categorize_cpp_file("src/ui/Window.m"), FileCategory::Implementation);
+        assert_eq!(categorize_cpp_file("AppDelegate.h"), FileCategory::Header);
+        assert_eq!(categorize_cpp_file("tests/ParserTests.m"), FileCategory::UnitTest);
     }
 
     #[test]
@@ -278,4 +906,455 @@ mod categorization_tests {
         assert_eq!(infer_cpp_purpose("src/ui/window.cpp", &FileCategory::Implementation), "User interface");
         assert_eq!(infer_cpp_purpose("src/core/app.cpp", &FileCategory::Implementation), "Implementation code");
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod macro_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_macros() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("macros.c");
+        std::fs::write(
+            &path,
+            "#define SQUARE(x) ((x) * (x))\nint area(int side) { return SQUARE(side); }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index
+            .parser(&path)
+            .detailed_preprocessing_record(true)
+            .parse()
+            .unwrap();
+
+        let (symbols, occs, _) = scan_tu(&tu);
+
+        let def = symbols
+            .iter()
+            .find(|s| s.kind == "MacroDefinition" && s.name == "SQUARE")
+            .expect("macro definition not found");
+        assert_eq!(def.line, 1);
+        assert!(def.file.ends_with("macros.c"));
+
+        let expansion = occs
+            .iter()
+            .find(|o| o.usage_kind == "macro_expansion")
+            .expect("macro expansion not found");
+        assert_eq!(expansion.line, 2);
+        assert_eq!(expansion.usr, def.usr);
+    }
+
+    #[test]
+    fn test_scan_tu_skips_system_header_macros() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let system_dir = temp_dir.path().join("system");
+        std::fs::create_dir(&system_dir).unwrap();
+        std::fs::write(system_dir.join("platform.h"), "#define PLATFORM_LIMIT 64\n").unwrap();
+        let path = temp_dir.path().join("limits.c");
+        std::fs::write(&path, "#include <platform.h>\n#define LOCAL_LIMIT 8\n").unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let system_arg = format!("-isystem{}", system_dir.display());
+        let tu = index
+            .parser(&path)
+            .arguments(&[system_arg.as_str()])
+            .detailed_preprocessing_record(true)
+            .parse()
+            .unwrap();
+
+        let (symbols, _, _) = scan_tu(&tu);
+        let macros: Vec<&str> = symbols
+            .iter()
+            .filter(|s| s.kind == "MacroDefinition")
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(macros, ["LOCAL_LIMIT"]);
+    }
+}
+
+#[cfg(test)]
+mod instantiation_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_template_instantiations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("instantiations.cpp");
+        std::fs::write(
+            &path,
+            "#include <vector>\n\
+             std::vector<int> numbers;\n\
+             int first() { return numbers.front(); }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index
+            .parser(&path)
+            .arguments(&["-std=c++17"])
+            .parse()
+            .unwrap();
+
+        let (symbols, _, edges) = scan_tu(&tu);
+
+        let instantiation = symbols
+            .iter()
+            .find(|s| s.kind == "ClassTemplateInstantiation" && s.name.starts_with("std::vector<int"))
+            .expect("std::vector<int> instantiation not found");
+        assert!(instantiation.template_args.as_deref().unwrap().starts_with("int"));
+
+        let (_, _, template) = edges
+            .iter()
+            .find(|(kind, from, _)| kind == "instantiates" && Some(from) == instantiation.usr.as_ref())
+            .expect("instantiates edge not found");
+        assert!(template.contains("@ST>") && template.ends_with("@vector"));
+    }
+}
+
+#[cfg(test)]
+mod concept_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    /// Требует libclang 18+: более ранние версии не сообщают о концептах
+    #[test]
+    fn test_scan_tu_concepts() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("concepts.cpp");
+        std::fs::write(
+            &path,
+            "#include <iostream>\n\
+             template<typename T> concept Printable = requires(T x) { std::cout << x; };\n\
+             template<typename T> requires Printable<T>\n\
+             void show(const T& value) { std::cout << value; }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index
+            .parser(&path)
+            .arguments(&["-std=c++20"])
+            .parse()
+            .unwrap();
+
+        let (symbols, occs, _) = scan_tu(&tu);
+
+        let concept = symbols
+            .iter()
+            .find(|s| s.kind == "ConceptDecl" && s.name == "Printable")
+            .expect("concept declaration not found");
+        assert_eq!(concept.line, 2);
+
+        let used = occs
+            .iter()
+            .find(|o| o.usage_kind == "concept_use")
+            .expect("concept use not found");
+        assert_eq!(used.line, 3);
+        assert_eq!(used.usr, concept.usr);
+    }
+}
+
+#[cfg(test)]
+mod attribute_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_function_attributes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("attributes.cpp");
+        std::fs::write(
+            &path,
+            "[[nodiscard]] int compute() { return 1; }\n\
+             [[deprecated]] void old_api() {}\n\
+             [[maybe_unused]] static int helper() { return 0; }\n\
+             void run() { compute(); int x = compute(); (void)x; }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index
+            .parser(&path)
+            .arguments(&["-std=c++17"])
+            .parse()
+            .unwrap();
+
+        let (symbols, occs, _) = scan_tu(&tu);
+        let metadata = |name: &str| &symbols.iter().find(|s| s.name.starts_with(name)).unwrap().metadata;
+        assert_eq!(metadata("compute").get("nodiscard").map(String::as_str), Some("true"));
+        assert_eq!(metadata("old_api").get("deprecated").map(String::as_str), Some("true"));
+        assert_eq!(metadata("helper").get("maybe_unused").map(String::as_str), Some("true"));
+        assert!(metadata("run").is_empty());
+
+        // Only the call whose result is not assigned is reported
+        let discarded: Vec<_> = occs.iter().filter(|o| o.usage_kind == "discarded_result").collect();
+        assert_eq!(discarded.len(), 1);
+        assert_eq!(discarded[0].line, 4);
+    }
+}
+
+#[cfg(test)]
+mod def_range_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_definition_lines() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("ranges.cpp");
+        std::fs::write(
+            &path,
+            "int declared();\n\
+             int\n\
+             defined(int x)\n\
+             {\n\
+                 return x;\n\
+             }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, _) = scan_tu(&tu);
+        let symbol = |name: &str| symbols.iter().find(|s| s.name.starts_with(name)).unwrap();
+        assert_eq!(symbol("defined").def_lines, Some((2, 6)));
+        assert_eq!(symbol("declared").def_lines, None);
+    }
+}
+
+#[cfg(test)]
+mod doc_comment_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_clean_doc_comment() {
+        assert_eq!(
+            clean_doc_comment("/**\n * @brief Limit a value.\n * @param value Input\n */"),
+            "@brief Limit a value.\n@param value Input"
+        );
+        assert_eq!(clean_doc_comment("/// Line one\n/// Line two"), "Line one\nLine two");
+        assert_eq!(clean_doc_comment("/*! Qt style */"), "Qt style");
+    }
+
+    #[test]
+    fn test_scan_tu_doxygen_comment() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("math.cpp");
+        std::fs::write(
+            &path,
+            "/**\n\
+              * @brief Limit a value to a range.\n\
+              */\n\
+             int clamp(int value, int lo, int hi) { return value < lo ? lo : value > hi ? hi : value; }\n\
+             int lerp() { return 0; }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, _) = scan_tu(&tu);
+        let symbol = |name: &str| symbols.iter().find(|s| s.name.starts_with(name)).unwrap();
+        assert_eq!(symbol("clamp").doc.as_deref(), Some("@brief Limit a value to a range."));
+        assert_eq!(symbol("lerp").doc, None);
+    }
+}
+
+#[cfg(test)]
+mod namespace_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_namespace_edges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("geometry.cpp");
+        std::fs::write(
+            &path,
+            "namespace geo {\n\
+                 int area(int w, int h) { return w * h; }\n\
+                 int perimeter(int w, int h) { return 2 * (w + h); }\n\
+             }\n\
+             namespace g = geo;\n\
+             int outside() { return 0; }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, edges) = scan_tu(&tu);
+        let usr = |name: &str| {
+            let symbol = symbols.iter().find(|s| s.name.split('(').next() == Some(name)).unwrap();
+            symbol.usr.clone().unwrap()
+        };
+        let members: Vec<&String> = edges
+            .iter()
+            .filter(|(kind, from, _)| kind == "namespace-member" && *from == usr("geo"))
+            .map(|(_, _, to)| to)
+            .collect();
+        assert_eq!(members, [&usr("area"), &usr("perimeter")]);
+        assert!(edges.contains(&("namespace-alias".to_string(), usr("g"), usr("geo"))));
+    }
+}
+
+#[cfg(test)]
+mod inheritance_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_inherit_edges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("shapes.cpp");
+        std::fs::write(
+            &path,
+            "class B {};\n\
+             class D : public B {};\n\
+             namespace geo { struct E : D {}; }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, edges) = scan_tu(&tu);
+        let usr = |name: &str| symbols.iter().find(|s| s.name == name).unwrap().usr.clone().unwrap();
+        let inherits: Vec<_> = edges.iter().filter(|(kind, _, _)| kind == "inherit").cloned().collect();
+        assert_eq!(
+            inherits,
+            [
+                ("inherit".to_string(), usr("B"), usr("D")),
+                ("inherit".to_string(), usr("D"), usr("E")),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod hierarchy_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_parent_usr() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("shape.cpp");
+        std::fs::write(
+            &path,
+            "namespace geo {\n\
+                 class Shape {\n\
+                 public:\n\
+                     int area() const { return 0; }\n\
+                 };\n\
+             }\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, _) = scan_tu(&tu);
+        let symbol = |name: &str| symbols.iter().find(|s| s.name.split('(').next() == Some(name)).unwrap();
+        assert_eq!(symbol("geo").parent_usr, None);
+        assert_eq!(symbol("Shape").parent_usr, symbol("geo").usr);
+        assert_eq!(symbol("area").parent_usr, symbol("Shape").usr);
+    }
+}
+
+#[cfg(test)]
+mod constructor_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_tu_constructors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("point.cpp");
+        std::fs::write(
+            &path,
+            "class Point {\n\
+             public:\n\
+                 Point() : x(0), y(0) {}\n\
+                 Point(int x, int y) : x(x), y(y) {}\n\
+                 ~Point() {}\n\
+             private:\n\
+                 int x;\n\
+                 int y;\n\
+             };\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, _, edges) = scan_tu(&tu);
+        let class_usr = symbols.iter().find(|s| s.name == "Point" && s.kind == "ClassDecl").unwrap().usr.clone().unwrap();
+        let constructors: Vec<&Symbol> = symbols.iter().filter(|s| s.kind == "Constructor").collect();
+        assert_eq!(constructors.len(), 2);
+        for constructor in constructors {
+            let usr = constructor.usr.clone().unwrap();
+            assert!(edges.contains(&("constructs".to_string(), usr, class_usr.clone())));
+        }
+        let destructor = symbols.iter().find(|s| s.kind == "Destructor").unwrap();
+        assert!(edges.contains(&("destructs".to_string(), destructor.usr.clone().unwrap(), class_usr)));
+    }
+}
+
+#[cfg(test)]
+mod anonymous_tests {
+    use super::*;
+    use clang::{Clang, Index};
+
+    #[test]
+    fn test_scan_anonymous_records() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.c");
+        std::fs::write(
+            &path,
+            "struct { int x; float y; } data;\n\
+             struct packet { union { int raw; char bytes[4]; }; };\n",
+        )
+        .unwrap();
+
+        let clang = Clang::new().unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&path).parse().unwrap();
+
+        let (symbols, edges) = scan_anonymous_records(&tu);
+        let anon_struct = symbols.iter().find(|s| s.kind == "AnonStruct").unwrap();
+        let usr = anon_struct.usr.clone().unwrap();
+        assert!(usr.starts_with("anon:") && usr.ends_with(":0"), "{}", usr);
+        let fields: Vec<&str> = edges
+            .iter()
+            .filter(|(kind, from, _)| kind == "member" && *from == usr)
+            .filter_map(|(_, _, to)| symbols.iter().find(|s| s.usr.as_ref() == Some(to)))
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(fields, ["x", "y"]);
+
+        // A nested anonymous union is a member of the enclosing struct
+        let anon_union = symbols.iter().find(|s| s.kind == "AnonUnion").unwrap();
+        let union_usr = anon_union.usr.clone().unwrap();
+        assert!(edges
+            .iter()
+            .any(|(kind, from, to)| kind == "member" && from == "c:@S@packet" && *to == union_usr));
+    }
+}
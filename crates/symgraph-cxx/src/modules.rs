@@ -1,5 +1,6 @@
 use anyhow::Result;
 use regex::Regex;
+use std::collections::HashMap;
 use std::fs;
 
 // Shared models
@@ -12,37 +13,19 @@ pub type CppSymbol = GenericSymbol;
 pub type CppRelation = GenericRelation;
 pub fn scan_cpp20_module(file_path: &str) -> Result<Option<ModuleInfo>> {
     let text = fs::read_to_string(file_path)?;
-    let re_export = Regex::new(r#"(?m)^\s*export\s+module\s+([A-Za-z0-9_:.]+)\s*;"#)?;
-    let re_import = Regex::new(r#"(?m)^\s*import\s+([A-Za-z0-9_:.]+)\s*;"#)?;
-
-    if let Some(cap) = re_export.captures(&text) {
-        let name = cap.get(1).unwrap().as_str().to_string();
-        let imports = re_import
-            .captures_iter(&text)
-            .filter_map(|m| m.get(1).map(|s| s.as_str().to_string()))
-            .collect();
-        Ok(Some(ModuleInfo {
-            name,
-            path: file_path.to_string(),
-            imports,
-        }))
-    } else {
-        Ok(None)
-    }
+    Ok(scan_cpp20_module_from_text(&text, file_path))
 }
 
 /// Internal function for testing without file system
 pub fn scan_cpp20_module_from_text(text: &str, path: &str) -> Option<ModuleInfo> {
     let re_export = Regex::new(r#"(?m)^\s*export\s+module\s+([A-Za-z0-9_:.]+)\s*;"#).ok()?;
-    let re_import = Regex::new(r#"(?m)^\s*import\s+([A-Za-z0-9_:.]+)\s*;"#).ok()?;
+    let re_import = Regex::new(IMPORT_PATTERN).ok()?;
 
     if let Some(cap) = re_export.captures(text) {
         let name = cap.get(1).unwrap().as_str().to_string();
-        let imports = re_import
-            .captures_iter(text)
-            .filter_map(|m| m.get(1).map(|s| s.as_str().to_string()))
-            .collect();
+        let imports = module_imports(&re_import, text, &name);
         Some(ModuleInfo {
+            partition: module_partition(&name),
             name,
             path: path.to_string(),
             imports,
@@ -52,6 +35,31 @@ pub fn scan_cpp20_module_from_text(text: &str, path: &str) -> Option<ModuleInfo>
     }
 }
 
+/// `import` declarations, including re-exported (`export import`) and partition (`import :part;`) imports
+const IMPORT_PATTERN: &str = r#"(?m)^\s*(?:export\s+)?import\s+(:?[A-Za-z0-9_:.]+)\s*;"#;
+
+/// Primary module of a module name: `foo` for both `foo` and the partition `foo:bar`
+pub fn primary_module_name(name: &str) -> &str {
+    name.split_once(':').map_or(name, |(primary, _)| primary)
+}
+
+/// Partition part of a module name: `bar` for `foo:bar`, `None` for `foo`
+fn module_partition(name: &str) -> Option<String> {
+    name.split_once(':').map(|(_, partition)| partition.to_string())
+}
+
+/// Imported module names; partition imports (`import :bar;`) are qualified with the importing module's primary name
+fn module_imports(re_import: &Regex, text: &str, module_name: &str) -> Vec<String> {
+    re_import
+        .captures_iter(text)
+        .filter_map(|m| m.get(1))
+        .map(|m| match m.as_str().strip_prefix(':') {
+            Some(partition) => format!("{}:{}", primary_module_name(module_name), partition),
+            None => m.as_str().to_string(),
+        })
+        .collect()
+}
+
 /// Analyze a C++ module file and extract symbols
 pub fn analyze_cpp_module(file_path: &str) -> Result<Option<ModuleAnalysis>> {
     let text = fs::read_to_string(file_path)?;
@@ -62,7 +70,7 @@ pub fn analyze_cpp_module(file_path: &str) -> Result<Option<ModuleAnalysis>> {
 pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<ModuleAnalysis>> {
     // First check if it's a module
     let re_export_module = Regex::new(r#"(?m)^\s*export\s+module\s+([A-Za-z0-9_:.]+)\s*;"#)?;
-    let re_import = Regex::new(r#"(?m)^\s*import\s+([A-Za-z0-9_:.]+)\s*;"#)?;
+    let re_import = Regex::new(IMPORT_PATTERN)?;
 
     let module_name = if let Some(cap) = re_export_module.captures(text) {
         cap.get(1).unwrap().as_str().to_string()
@@ -70,10 +78,7 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
         return Ok(None);
     };
 
-    let imports: Vec<String> = re_import
-        .captures_iter(text)
-        .filter_map(|m| m.get(1).map(|s| s.as_str().to_string()))
-        .collect();
+    let imports = module_imports(&re_import, text, &module_name);
 
     // Remove comments and strings for cleaner parsing
     let clean_text = remove_comments_and_strings(text);
@@ -88,6 +93,7 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
     for (line_num, line) in clean_text.lines().enumerate() {
         let line_num = (line_num + 1) as u32;
         let trimmed = line.trim();
+        let column = (line.len() - line.trim_start().len() + 1) as u32;
 
         // Skip empty lines
         if trimmed.is_empty() {
@@ -108,6 +114,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                 signature: func.1,
                 is_exported: true,
                 line: line_num,
+                column,
+                cfg: None,
+                metadata: HashMap::new(),
+                doc: None,
             });
 
             // Extract calls from function body would need more context
@@ -134,6 +144,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: line_num,
+                column,
+                cfg: None,
+                metadata: HashMap::new(),
+                doc: None,
             });
 
             // Inheritance relations
@@ -157,6 +171,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                     signature,
                     is_exported: true,
                     line: line_num,
+                    column,
+                    cfg: None,
+                    metadata: HashMap::new(),
+                    doc: None,
                 });
 
                 relations.push(CppRelation {
@@ -183,6 +201,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                     signature: trimmed.to_string(),
                     is_exported: true,
                     line: line_num,
+                    column,
+                    cfg: None,
+                    metadata: HashMap::new(),
+                    doc: None,
                 });
 
                 relations.push(CppRelation {
@@ -207,6 +229,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: line_num,
+                column,
+                cfg: None,
+                metadata: HashMap::new(),
+                doc: None,
             });
         }
 
@@ -218,6 +244,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: line_num,
+                column,
+                cfg: None,
+                metadata: HashMap::new(),
+                doc: None,
             });
 
             relations.push(CppRelation {
@@ -235,6 +265,10 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: line_num,
+                column,
+                cfg: None,
+                metadata: HashMap::new(),
+                doc: None,
             });
 
             relations.push(CppRelation {
@@ -245,8 +279,22 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
         }
     }
 
+    // Coroutines: a function whose body uses co_await, co_yield or co_return
+    let re_coroutine = Regex::new(r"\bco_(?:await|yield|return)\b")?;
+    for symbol in symbols
+        .iter_mut()
+        .filter(|s| s.kind == "function" || s.kind == "method")
+    {
+        if let Some(body) = function_body(&clean_text, symbol.line) {
+            if re_coroutine.is_match(body) {
+                symbol.metadata.insert("is_coroutine".to_string(), "true".to_string());
+            }
+        }
+    }
+
     Ok(Some(ModuleAnalysis {
         info: ModuleInfo {
+            partition: module_partition(&module_name),
             name: module_name,
             path: path.to_string(),
             imports,
@@ -256,6 +304,37 @@ pub fn analyze_cpp_module_from_text(text: &str, path: &str) -> Result<Option<Mod
     }))
 }
 
+/// Body of the function declared on 1-based `line`, between its outermost braces.
+///
+/// Returns `None` for declarations without a body (`;` before any `{`).
+fn function_body(text: &str, line: u32) -> Option<&str> {
+    let start = text
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1) as usize)
+        .map(str::len)
+        .sum::<usize>();
+    let rest = &text[start..];
+    let open = rest.find(['{', ';'])?;
+    if rest.as_bytes()[open] == b';' {
+        return None;
+    }
+
+    let mut depth = 0;
+    for (i, c) in rest[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&rest[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Remove C/C++ comments and string literals for cleaner parsing
 fn remove_comments_and_strings(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
@@ -579,6 +658,22 @@ class Renderer {};
         assert!(mi.imports.contains(&"graphics:math".to_string()));
     }
 
+    /// Демонстрация: разделы модуля из .cppm файлов (`export module geometry:shapes;`, `import :math;`)
+    #[test]
+    fn test_module_partition_fixture() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/partitions");
+
+        let shapes = scan_cpp20_module(&format!("{}/geometry-shapes.cppm", dir)).unwrap().unwrap();
+        assert_eq!(shapes.name, "geometry:shapes");
+        assert_eq!(shapes.partition.as_deref(), Some("shapes"));
+        assert_eq!(shapes.imports, vec!["geometry:math".to_string()]);
+
+        let geometry = scan_cpp20_module(&format!("{}/geometry.cppm", dir)).unwrap().unwrap();
+        assert_eq!(geometry.name, "geometry");
+        assert_eq!(geometry.partition, None);
+        assert_eq!(geometry.imports, vec!["geometry:shapes", "geometry:math", "std"]);
+    }
+
     /// Демонстрация: файл без export module (не модуль)
     #[test]
     fn test_not_a_module() {
@@ -623,6 +718,42 @@ void func() {}
         assert_eq!(mi.imports.len(), 2);
     }
 
+    /// Демонстрация: обнаружение корутин по co_await/co_yield/co_return
+    #[test]
+    fn test_coroutine_detection() {
+        let source = r#"
+export module tasks;
+
+export Task<int> fetch(int id) {
+    auto data = co_await load(id);
+    co_return data.size();
+}
+
+export Generator<int> counter(int n) {
+    for (int i = 0; i < n; ++i) {
+        co_yield i;
+    }
+}
+
+export int plain(int x) {
+    // co_await in a comment does not count
+    return x * 2;
+}
+
+export Task<void> declared_only(int id);
+"#;
+        let analysis = analyze_cpp_module_from_text(source, "tasks.cppm").unwrap().unwrap();
+        let is_coroutine = |name: &str| {
+            let symbol = analysis.symbols.iter().find(|s| s.name == name).unwrap();
+            symbol.metadata.get("is_coroutine").map(String::as_str) == Some("true")
+        };
+
+        assert!(is_coroutine("fetch"));
+        assert!(is_coroutine("counter"));
+        assert!(!is_coroutine("plain"));
+        assert!(!is_coroutine("declared_only"));
+    }
+
     /// Демонстрация: пустой модуль (только export module)
     #[test]
     fn test_empty_module() {
@@ -0,0 +1,7 @@
+export module geometry:shapes;
+
+import :math;
+
+export struct Circle {
+    double radius;
+};
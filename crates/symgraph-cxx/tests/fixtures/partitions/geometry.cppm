@@ -0,0 +1,7 @@
+export module geometry;
+
+export import :shapes;
+export import :math;
+import std;
+
+export double total_area(const Circle& c);
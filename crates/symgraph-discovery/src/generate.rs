@@ -2,6 +2,7 @@
 //!
 //! Модуль для генерации compile_commands.json из различных систем сборки:
 //! - CMake (CMakeLists.txt)
+//! - xmake (xmake.lua)
 //! - Make (Makefile)
 //! - MSBuild (.vcxproj, .sln)
 //! - Cargo (Cargo.toml)
@@ -14,14 +15,25 @@
 //!
 //! ### Make
 //! Использует `bear` или `compiledb` для перехвата команд компиляции,
-//! либо парсит вывод `make -n` (dry-run).
+//! либо парсит вывод `make -n` (dry-run), если ни один из них не установлен.
+//!
+//! ### xmake
+//! Запускает `xmake project -k compile_commands`, который сам пишет
+//! compile_commands.json.
+//!
+//! ### Cargo
+//! Синтезирует вызовы `rustc` из JSON-сообщений `cargo build --message-format=json`.
 //!
 //! ### MSBuild (.vcxproj/.sln)
 //! Парсит XML файлы проекта для извлечения настроек компиляции,
-//! или использует clang-cl совместимые флаги.
+//! или использует clang-cl совместимые флаги. Include директории и
+//! определения берутся также из импортированных `.props`/`.targets` файлов.
 
 use anyhow::{bail, Context, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -31,14 +43,20 @@ use std::process::Command;
 pub enum BuildSystem {
     /// CMake проект (CMakeLists.txt)
     CMake,
+    /// xmake проект (xmake.lua)
+    XMake,
     /// Make проект (Makefile, GNUmakefile, makefile)
     Make,
+    /// GNU Autotools проект (configure.ac, configure.in)
+    Autotools,
     /// Visual Studio проект (.vcxproj)
     VcxProj,
     /// Visual Studio решение (.sln)
     Solution,
     /// Cargo / Rust проект (Cargo.toml)
     Cargo,
+    /// Общие флаги компиляции для всех файлов (compile_flags.txt, простой режим clangd)
+    CompileFlags,
     /// Неизвестная система сборки
     Unknown,
 }
@@ -58,6 +76,66 @@ pub struct CompileCommandEntry {
     pub arguments: Option<Vec<String>>,
 }
 
+/// Внешняя команда, которую запускает генерация compile_commands.json
+///
+/// Планировщики (`plan_*`) строят список команд один раз; по нему работают
+/// и генерация, и режим `--dry-run`, который только печатает план.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedCommand {
+    /// Рабочая директория
+    pub dir: PathBuf,
+    /// Запускаемая программа
+    pub program: String,
+    /// Аргументы программы
+    pub args: Vec<OsString>,
+    /// Запасная стратегия: запускается, только если предыдущая ничего не перехватила
+    pub fallback: bool,
+}
+
+impl PlannedCommand {
+    /// Команда `program` без аргументов в директории `dir`
+    pub fn new(dir: &Path, program: &str) -> Self {
+        Self { dir: dir.to_path_buf(), program: program.to_string(), args: Vec::new(), fallback: false }
+    }
+
+    /// Добавляет аргумент
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    /// Добавляет несколько аргументов
+    pub fn args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, args: I) -> Self {
+        self.args.extend(args.into_iter().map(|a| a.as_ref().to_os_string()));
+        self
+    }
+
+    /// Процесс для запуска команды
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.current_dir(&self.dir).args(&self.args);
+        cmd
+    }
+}
+
+impl fmt::Display for PlannedCommand {
+    /// `(in <dir>) <program> <args>`, аргументы экранированы для shell
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(in {}) {}", self.dir.display(), self.program)?;
+        for arg in &self.args {
+            let arg = arg.to_string_lossy();
+            // shell_words::quote берёт в кавычки и безопасные `-DNAME=value`
+            let plain = !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+            if plain {
+                write!(f, " {}", arg)?;
+            } else {
+                write!(f, " {}", shell_words::quote(&arg))?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Автоматически определяет тип системы сборки в директории
 ///
 /// # Arguments
@@ -71,6 +149,10 @@ pub fn detect_build_system(project_dir: &Path) -> BuildSystem {
         return BuildSystem::CMake;
     }
 
+    if project_dir.join("xmake.lua").exists() {
+        return BuildSystem::XMake;
+    }
+
     // Проверяем Cargo.toml (Rust/Cargo project)
     if project_dir.join("Cargo.toml").exists() {
         return BuildSystem::Cargo;
@@ -91,6 +173,11 @@ pub fn detect_build_system(project_dir: &Path) -> BuildSystem {
         }
     }
 
+    // Проверяем Autotools до Makefile: после ./configure в проекте есть и Makefile
+    if project_dir.join("configure.ac").exists() || project_dir.join("configure.in").exists() {
+        return BuildSystem::Autotools;
+    }
+
     // Проверяем Makefile (разные варианты имён)
     for makefile in &["Makefile", "makefile", "GNUmakefile"] {
         if project_dir.join(makefile).exists() {
@@ -98,6 +185,11 @@ pub fn detect_build_system(project_dir: &Path) -> BuildSystem {
         }
     }
 
+    // compile_flags.txt используется clangd, только когда нет настоящей системы сборки
+    if project_dir.join("compile_flags.txt").exists() {
+        return BuildSystem::CompileFlags;
+    }
+
     BuildSystem::Unknown
 }
 
@@ -111,6 +203,8 @@ pub fn detect_build_system(project_dir: &Path) -> BuildSystem {
 /// * `build_dir` - Директория для сборки (будет создана)
 /// * `generator` - Генератор CMake (например, "Ninja", "Unix Makefiles")
 /// * `extra_args` - Дополнительные аргументы CMake
+/// * `vcpkg_root` - Корень vcpkg (по умолчанию `$VCPKG_ROOT`); для проектов с
+///   `vcpkg.json` передаётся тулчейн vcpkg, если он не задан в `extra_args`
 ///
 /// # Returns
 /// Путь к сгенерированному compile_commands.json
@@ -119,31 +213,15 @@ pub fn generate_from_cmake(
     build_dir: &Path,
     generator: Option<&str>,
     extra_args: &[String],
+    vcpkg_root: Option<&Path>,
 ) -> Result<PathBuf> {
     // Создаём директорию сборки
     fs::create_dir_all(build_dir)
         .with_context(|| format!("Failed to create build directory: {}", build_dir.display()))?;
 
-    // Формируем команду CMake
-    let mut cmd = Command::new("cmake");
-    cmd.arg("-S")
-        .arg(source_dir)
-        .arg("-B")
-        .arg(build_dir)
-        .arg("-DCMAKE_EXPORT_COMPILE_COMMANDS=ON");
-
-    // Добавляем генератор если указан (рекомендуется Ninja)
-    if let Some(gen) = generator {
-        cmd.arg("-G").arg(gen);
-    }
-
-    // Добавляем дополнительные аргументы
-    for arg in extra_args {
-        cmd.arg(arg);
-    }
-
     // Запускаем CMake
-    let output = cmd
+    let output = plan_cmake(source_dir, build_dir, generator, extra_args, vcpkg_root)?
+        .command()
         .output()
         .with_context(|| "Failed to execute cmake. Is CMake installed and in PATH?")?;
 
@@ -154,6 +232,11 @@ pub fn generate_from_cmake(
 
     // Проверяем что compile_commands.json создан
     let compdb_path = build_dir.join("compile_commands.json");
+    let ninja_path = build_dir.join("build.ninja");
+    if !compdb_path.exists() && ninja_path.exists() {
+        // Генератор Ninja без экспорта: команды берутся из build.ninja
+        return extract_from_ninja(&ninja_path, &compdb_path);
+    }
     if !compdb_path.exists() {
         bail!(
             "compile_commands.json was not generated. \
@@ -165,10 +248,517 @@ pub fn generate_from_cmake(
     Ok(compdb_path)
 }
 
-/// Генерирует compile_commands.json из Makefile используя dry-run
+/// Команда CMake, которую запускает [`generate_from_cmake`]
+pub fn plan_cmake(
+    source_dir: &Path,
+    build_dir: &Path,
+    generator: Option<&str>,
+    extra_args: &[String],
+    vcpkg_root: Option<&Path>,
+) -> Result<PlannedCommand> {
+    // Команда запускается в source_dir, поэтому относительные пути не годятся
+    let mut cmd = PlannedCommand::new(source_dir, "cmake")
+        .arg("-S")
+        .arg(std::path::absolute(source_dir)?)
+        .arg("-B")
+        .arg(std::path::absolute(build_dir)?)
+        .arg("-DCMAKE_EXPORT_COMPILE_COMMANDS=ON");
+
+    // Добавляем генератор если указан (рекомендуется Ninja)
+    if let Some(gen) = generator {
+        cmd = cmd.arg("-G").arg(gen);
+    }
+
+    // Добавляем дополнительные аргументы
+    cmd = cmd.args(extra_args);
+
+    // Манифест vcpkg: зависимости ставятся через тулчейн vcpkg
+    if !extra_args.iter().any(|a| a.starts_with("-DCMAKE_TOOLCHAIN_FILE=")) {
+        if let Some(toolchain) = vcpkg_toolchain_file(source_dir, vcpkg_root) {
+            cmd = cmd.arg(format!("-DCMAKE_TOOLCHAIN_FILE={}", toolchain.display()));
+        }
+    }
+    Ok(cmd)
+}
+
+/// Генерирует compile_commands.json из xmake проекта
+///
+/// `xmake project -k compile_commands <dir>` пишет `compile_commands.json` в
+/// указанную директорию; при другом имени выходного файла он переименовывается.
+///
+/// # Arguments
+/// * `project_dir` - Директория с xmake.lua
+/// * `output_path` - Путь для записи compile_commands.json
+///
+/// # Dependencies
+/// - `xmake` (<https://xmake.io>)
+pub fn generate_from_xmake(project_dir: &Path, output_path: &Path) -> Result<PathBuf> {
+    if !is_tool_available("xmake", None) {
+        bail!(
+            "xmake is not installed or not in PATH. Install it from https://xmake.io \
+             (e.g. `curl -fsSL https://xmake.io/shget.text | bash` on Linux/macOS, \
+             `scoop install xmake` on Windows)"
+        );
+    }
+
+    let output_path = std::path::absolute(output_path)?;
+    let output_dir = output_path.parent().unwrap_or(project_dir);
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
+
+    let output = plan_xmake(project_dir, &output_path)?
+        .command()
+        .output()
+        .with_context(|| "Failed to execute xmake")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("xmake project -k compile_commands failed:\n{}", stderr);
+    }
+
+    let generated = output_dir.join("compile_commands.json");
+    if !generated.exists() {
+        bail!("xmake did not write {}", generated.display());
+    }
+    if generated != output_path {
+        fs::rename(&generated, &output_path)
+            .with_context(|| format!("Failed to move {} to {}", generated.display(), output_path.display()))?;
+    }
+
+    Ok(output_path)
+}
+
+/// Команда xmake, которую запускает [`generate_from_xmake`]
+pub fn plan_xmake(project_dir: &Path, output_path: &Path) -> Result<PlannedCommand> {
+    let output_path = std::path::absolute(output_path)?;
+    let output_dir = output_path.parent().unwrap_or(project_dir);
+    Ok(PlannedCommand::new(project_dir, "xmake").args(["project", "-k", "compile_commands"]).arg(output_dir))
+}
+
+/// Генерирует compile_commands.json из compile_flags.txt
+///
+/// В compile_flags.txt по одному флагу на строку; clangd применяет их ко всем
+/// файлам проекта. Для каждого `.c`/`.cc`/`.cpp`/`.cxx` файла (скрытые
+/// директории пропускаются) создаётся запись с этими флагами в `arguments`,
+/// компилятором `clang` для C и `clang++` для C++.
+///
+/// # Arguments
+/// * `project_dir` - Директория с compile_flags.txt
+/// * `output_path` - Путь для записи compile_commands.json
+pub fn generate_from_compile_flags(project_dir: &Path, output_path: &Path) -> Result<PathBuf> {
+    let flags_path = project_dir.join("compile_flags.txt");
+    let flags: Vec<String> = fs::read_to_string(&flags_path)
+        .with_context(|| format!("Failed to read {}", flags_path.display()))?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let project_dir = std::path::absolute(project_dir)?;
+    let mut sources: Vec<PathBuf> = walkdir::WalkDir::new(&project_dir)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|ext| ["c", "cc", "cpp", "cxx"].iter().any(|s| ext == *s)))
+        .collect();
+    sources.sort();
+    if sources.is_empty() {
+        bail!("No C/C++ source files found in {}", project_dir.display());
+    }
+
+    let entries: Vec<CompileCommandEntry> = sources
+        .iter()
+        .map(|source| {
+            let compiler = if source.extension().is_some_and(|ext| ext == "c") { "clang" } else { "clang++" };
+            let file = source.display().to_string();
+            let mut arguments = vec![compiler.to_string()];
+            arguments.extend(flags.iter().cloned());
+            arguments.extend(["-c".to_string(), file.clone()]);
+            CompileCommandEntry {
+                directory: project_dir.display().to_string(),
+                file,
+                command: None,
+                arguments: Some(arguments),
+            }
+        })
+        .collect();
+
+    write_compile_commands(&entries, output_path)?;
+    Ok(output_path.to_path_buf())
+}
+
+/// Извлекает команды компиляции из `build.ninja`
+///
+/// Запасной путь для сборок Ninja без `compile_commands.json`. Читаются
+/// правила (`rule`), операторы `build` с их переменными и подключаемые файлы
+/// (`include`/`subninja`, например `CMakeFiles/rules.ninja` у CMake);
+/// `command` каждого `build` раскрывается (`$in`, `$out`, `$FLAGS`, ...), и
+/// команды с `-c` (или `/c` у MSVC) становятся записями для C/C++ исходников.
+///
+/// # Arguments
+/// * `ninja_path` - Путь к build.ninja
+/// * `output_path` - Путь для записи compile_commands.json
+pub fn extract_from_ninja(ninja_path: &Path, output_path: &Path) -> Result<PathBuf> {
+    let build_dir = std::path::absolute(ninja_path.parent().unwrap_or(Path::new(".")))?;
+    let mut ninja = NinjaFile::default();
+    ninja.parse(ninja_path, &build_dir, 0)?;
+
+    let entries = ninja.compile_commands(&build_dir);
+    if entries.is_empty() {
+        bail!("No compile commands found in {}", ninja_path.display());
+    }
+
+    write_compile_commands(&entries, output_path)?;
+    Ok(output_path.to_path_buf())
+}
+
+/// Оператор `build` из build.ninja
+struct NinjaBuild {
+    outputs: Vec<String>,
+    rule: String,
+    inputs: Vec<String>,
+    vars: HashMap<String, String>,
+}
+
+/// Правила, сборки и глобальные переменные build.ninja и подключаемых файлов
+#[derive(Default)]
+struct NinjaFile {
+    globals: HashMap<String, String>,
+    rules: HashMap<String, HashMap<String, String>>,
+    builds: Vec<NinjaBuild>,
+}
+
+/// Блок, к которому относятся строки с отступом
+enum NinjaScope {
+    None,
+    Rule(String),
+    Build(usize),
+}
+
+impl NinjaFile {
+    fn parse(&mut self, path: &Path, build_dir: &Path, depth: usize) -> Result<()> {
+        use regex::Regex;
+
+        if depth > 16 {
+            bail!("Too many nested includes at {}", path.display());
+        }
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        // `$` в конце строки продолжает её на следующей
+        let content = content.replace("$\r\n", "").replace("$\n", "");
+        let binding_re = Regex::new(r"^(\s*)([A-Za-z0-9_.-]+)\s*=\s*(.*)$")?;
+
+        let mut scope = NinjaScope::None;
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(caps) = binding_re.captures(line) {
+                let (name, value) = (caps[2].to_string(), caps[3].to_string());
+                match (&scope, caps[1].is_empty()) {
+                    (NinjaScope::Rule(rule), false) => {
+                        self.rules.entry(rule.clone()).or_default().insert(name, value);
+                    }
+                    (NinjaScope::Build(index), false) => {
+                        self.builds[*index].vars.insert(name, value);
+                    }
+                    _ => {
+                        scope = NinjaScope::None;
+                        self.globals.insert(name, value);
+                    }
+                }
+                continue;
+            }
+
+            let (keyword, rest) = trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""));
+            scope = NinjaScope::None;
+            match keyword {
+                "rule" => scope = NinjaScope::Rule(rest.trim().to_string()),
+                "build" => {
+                    let Some((outputs, rest)) = rest.split_once(':') else {
+                        continue;
+                    };
+                    // Неявные (`|`) и order-only (`||`) зависимости не являются исходниками
+                    let explicit = rest.split('|').next().unwrap_or_default();
+                    let mut words = explicit.split_whitespace().map(str::to_string);
+                    let rule = words.next().unwrap_or_default();
+                    self.builds.push(NinjaBuild {
+                        outputs: outputs.split_whitespace().map(str::to_string).collect(),
+                        rule,
+                        inputs: words.collect(),
+                        vars: HashMap::new(),
+                    });
+                    scope = NinjaScope::Build(self.builds.len() - 1);
+                }
+                "include" | "subninja" => {
+                    let included = build_dir.join(rest.trim());
+                    self.parse(&included, build_dir, depth + 1)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_commands(&self, build_dir: &Path) -> Vec<CompileCommandEntry> {
+        let mut entries = Vec::new();
+        for build in &self.builds {
+            let Some(rule) = self.rules.get(&build.rule) else {
+                continue;
+            };
+            let Some(source) = build.inputs.iter().find(|input| {
+                Path::new(input).extension().is_some_and(|ext| {
+                    let ext = ext.to_string_lossy().to_lowercase();
+                    matches!(ext.as_str(), "c" | "cc" | "cpp" | "cxx" | "c++" | "m" | "mm")
+                })
+            }) else {
+                continue;
+            };
+            let command = self.expand("command", build, rule, 0);
+            let words: Vec<&str> = command.split_whitespace().collect();
+            if !words.iter().any(|w| *w == "-c" || *w == "/c") {
+                continue;
+            }
+            entries.push(CompileCommandEntry {
+                directory: build_dir.display().to_string(),
+                file: build_dir.join(source).display().to_string(),
+                command: Some(words.join(" ")),
+                arguments: None,
+            });
+        }
+        entries
+    }
+
+    /// Раскрытое значение переменной в контексте `build`: сначала его
+    /// переменные, затем переменные правила, затем глобальные
+    fn expand(&self, name: &str, build: &NinjaBuild, rule: &HashMap<String, String>, depth: usize) -> String {
+        match name {
+            "in" => return build.inputs.join(" "),
+            "out" => return build.outputs.join(" "),
+            _ => {}
+        }
+        let Some(value) = build.vars.get(name).or_else(|| rule.get(name)).or_else(|| self.globals.get(name)) else {
+            return String::new();
+        };
+        if depth > 16 {
+            return value.clone();
+        }
+
+        let mut result = String::new();
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '$' {
+                result.push(c);
+                continue;
+            }
+            match chars.peek().copied() {
+                Some('$') | Some(' ') | Some(':') => result.push(chars.next().unwrap_or_default()),
+                Some('{') => {
+                    chars.next();
+                    let var: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    result.push_str(&self.expand(&var, build, rule, depth + 1));
+                }
+                _ => {
+                    let mut var = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if !(c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                            break;
+                        }
+                        var.push(c);
+                        chars.next();
+                    }
+                    result.push_str(&self.expand(&var, build, rule, depth + 1));
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Читает переменные из `CMakeCache.txt` в директории сборки
+///
+/// Строки имеют вид `VAR:TYPE=VALUE` (тип может отсутствовать); комментарии
+/// (`#`, `//`) и пустые строки пропускаются.
+pub fn read_cmake_cache(build_dir: &Path) -> Result<HashMap<String, String>> {
+    let path = build_dir.join("CMakeCache.txt");
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut vars = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let name = name.split_once(':').map_or(name, |(name, _)| name);
+        vars.insert(name.trim().to_string(), value.to_string());
+    }
+    Ok(vars)
+}
+
+/// Флаги компиляции из кэша CMake для файла на C (`is_c`) или C++
+///
+/// `CMAKE_C_FLAGS`/`CMAKE_CXX_FLAGS` разбиваются по правилам оболочки, а
+/// каталоги из переменных `*_INCLUDE_DIRS` (список через `;`) становятся `-I`.
+pub fn cmake_cache_flags(cache: &HashMap<String, String>, is_c: bool) -> Vec<String> {
+    let flags_var = if is_c { "CMAKE_C_FLAGS" } else { "CMAKE_CXX_FLAGS" };
+    let mut flags = cache
+        .get(flags_var)
+        .and_then(|value| shell_words::split(value).ok())
+        .unwrap_or_default();
+
+    let mut include_vars: Vec<&String> = cache.keys().filter(|name| name.ends_with("_INCLUDE_DIRS")).collect();
+    include_vars.sort();
+    for name in include_vars {
+        for dir in cache[name].split(';').map(str::trim).filter(|dir| !dir.is_empty()) {
+            flags.push(format!("-I{}", dir));
+        }
+    }
+    flags
+}
+
+/// Зависимость из манифеста vcpkg.json
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VcpkgDep {
+    pub name: String,
+    /// `version>=` или `version-semver`, если указаны
+    pub version_constraint: Option<String>,
+    pub features: Vec<String>,
+}
+
+/// Читает зависимости из `vcpkg.json` в директории проекта
+///
+/// Элемент массива `"dependencies"` — либо имя порта строкой, либо объект с
+/// полями `"name"`, `"version>="`/`"version-semver"` и `"features"`.
+/// Без манифеста возвращается пустой список.
+pub fn extract_vcpkg_dependencies(project_dir: &Path) -> Result<Vec<VcpkgDep>> {
+    let manifest = project_dir.join("vcpkg.json");
+    if !manifest.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&manifest)
+        .with_context(|| format!("Failed to read {}", manifest.display()))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+
+    let mut deps = Vec::new();
+    for dep in json["dependencies"].as_array().into_iter().flatten() {
+        match dep {
+            serde_json::Value::String(name) => deps.push(VcpkgDep {
+                name: name.clone(),
+                version_constraint: None,
+                features: Vec::new(),
+            }),
+            serde_json::Value::Object(obj) => {
+                let Some(name) = obj.get("name").and_then(|n| n.as_str()) else {
+                    bail!("Dependency without a name in {}", manifest.display());
+                };
+                let version_constraint = ["version>=", "version-semver"]
+                    .iter()
+                    .find_map(|key| obj.get(*key).and_then(|v| v.as_str()))
+                    .map(|v| v.to_string());
+                // Фича — строка или объект `{ "name": ..., "platform": ... }`
+                let features = obj
+                    .get("features")
+                    .and_then(|f| f.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|f| f.as_str().or_else(|| f["name"].as_str()))
+                    .map(|f| f.to_string())
+                    .collect();
+                deps.push(VcpkgDep {
+                    name: name.to_string(),
+                    version_constraint,
+                    features,
+                });
+            }
+            other => bail!("Unexpected vcpkg dependency entry: {}", other),
+        }
+    }
+    Ok(deps)
+}
+
+/// Пакет из `Cargo.lock` вместе с именами его зависимостей
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CargoDep {
+    pub name: String,
+    pub version: String,
+    /// Имена пакетов без версии и источника
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoLockFile {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Читает граф зависимостей из секций `[[package]]` файла `Cargo.lock`
+///
+/// Зависимость записана как `"name"`, `"name version"` или
+/// `"name version (source)"`, если в lock-файле несколько версий пакета;
+/// в результат попадает только имя.
+pub fn extract_cargo_lock_graph(lock_path: &Path) -> Result<Vec<CargoDep>> {
+    let content = fs::read_to_string(lock_path)
+        .with_context(|| format!("Failed to read {}", lock_path.display()))?;
+    let lock: CargoLockFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", lock_path.display()))?;
+
+    Ok(lock
+        .package
+        .into_iter()
+        .map(|package| CargoDep {
+            name: package.name,
+            version: package.version,
+            dependencies: package
+                .dependencies
+                .iter()
+                .filter_map(|dep| dep.split_whitespace().next())
+                .map(|name| name.to_string())
+                .collect(),
+        })
+        .collect())
+}
+
+/// Путь к `vcpkg.cmake`, если в проекте есть `vcpkg.json` и известен корень vcpkg
+pub fn vcpkg_toolchain_file(project_dir: &Path, vcpkg_root: Option<&Path>) -> Option<PathBuf> {
+    if !project_dir.join("vcpkg.json").exists() {
+        return None;
+    }
+    let root = match vcpkg_root {
+        Some(root) => root.to_path_buf(),
+        None => match std::env::var_os("VCPKG_ROOT") {
+            Some(root) => PathBuf::from(root),
+            None => {
+                eprintln!("Warning: vcpkg.json found but no vcpkg root given; set --vcpkg-root or VCPKG_ROOT");
+                return None;
+            }
+        },
+    };
+    Some(root.join("scripts").join("buildsystems").join("vcpkg.cmake"))
+}
+
+/// Генерирует compile_commands.json из Makefile
 ///
-/// Парсит вывод `make -n` для извлечения команд компиляции.
-/// Это менее надёжно чем `bear`, но не требует дополнительных инструментов.
+/// Стратегии перебираются по порядку, пока одна не даст хотя бы одну запись:
+/// 1. `bear -- make` — перехватывает настоящие вызовы компилятора; самый
+///    надёжный вариант, но проект при этом собирается;
+/// 2. `compiledb --no-build make` — разбирает вывод `make -n` средствами compiledb;
+/// 3. встроенный разбор вывода `make -n` — без дополнительных инструментов.
 ///
 /// # Arguments
 /// * `makefile_dir` - Директория с Makefile
@@ -176,25 +766,110 @@ pub fn generate_from_cmake(
 /// * `make_args` - Дополнительные аргументы для make
 ///
 /// # Limitations
-/// - Может не работать со сложными Makefile
-/// - Не поддерживает все варианты синтаксиса make
-/// - Рекомендуется использовать `bear` для более надёжного результата
+/// Стратегии 2 и 3 не видят команд, скрытых за рекурсивными вызовами make
+/// со сложной логикой, libtool и нестандартными правилами.
 pub fn generate_from_makefile(
     makefile_dir: &Path,
     output_path: &Path,
     make_args: &[String],
 ) -> Result<PathBuf> {
-    // Запускаем make -n (dry-run) для получения команд без выполнения
-    let mut cmd = Command::new("make");
-    cmd.current_dir(makefile_dir)
-        .arg("-n") // Dry-run: печатает команды без выполнения
-        .arg("-w"); // Print working directory
+    run_capture_plan(&plan_makefile(makefile_dir, output_path, make_args)?, makefile_dir, output_path)
+}
+
+/// Команды, которые запускает [`generate_from_makefile`]: `bear` и `compiledb`,
+/// если они установлены, и разбор `make -n`; все после первой — запасные
+pub fn plan_makefile(makefile_dir: &Path, output_path: &Path, make_args: &[String]) -> Result<Vec<PlannedCommand>> {
+    capture_plan(makefile_dir, output_path, make_args, make_args)
+}
 
-    for arg in make_args {
-        cmd.arg(arg);
+/// Проверяет, установлен ли `bear` (<https://github.com/rizsotto/Bear>)
+pub fn is_bear_available() -> bool {
+    is_tool_available("bear", None)
+}
+
+/// Проверяет, установлен ли `compiledb` (<https://github.com/nickdiego/compiledb>)
+pub fn is_compiledb_available() -> bool {
+    is_tool_available("compiledb", None)
+}
+
+/// Запускает `program --version`; `search_path` подменяет PATH дочернего процесса
+fn is_tool_available(program: &str, search_path: Option<&std::ffi::OsStr>) -> bool {
+    let mut cmd = Command::new(program);
+    cmd.arg("--version");
+    if let Some(path) = search_path {
+        cmd.env("PATH", path);
     }
+    cmd.output().is_ok_and(|output| output.status.success())
+}
 
-    let output = cmd
+/// Стратегии перехвата команд make по порядку: `bear -- make <bear_make_args>`
+/// и `compiledb`, если они установлены, затем разбор `make -n`
+fn capture_plan(
+    makefile_dir: &Path,
+    output_path: &Path,
+    bear_make_args: &[String],
+    make_args: &[String],
+) -> Result<Vec<PlannedCommand>> {
+    let capture_path = std::path::absolute(output_path)?;
+    let mut plan = Vec::new();
+    if is_bear_available() {
+        plan.push(
+            PlannedCommand::new(makefile_dir, "bear")
+                .arg("--output")
+                .arg(&capture_path)
+                .args(["--", "make"])
+                .args(bear_make_args),
+        );
+    }
+    if is_compiledb_available() {
+        plan.push(
+            PlannedCommand::new(makefile_dir, "compiledb")
+                .arg("--output")
+                .arg(&capture_path)
+                .args(["--no-build", "make"])
+                .args(make_args),
+        );
+    }
+    // -n: печатает команды без выполнения, -w: печатает рабочую директорию
+    plan.push(PlannedCommand::new(makefile_dir, "make").args(["-n", "-w"]).args(make_args));
+
+    for step in plan.iter_mut().skip(1) {
+        step.fallback = true;
+    }
+    Ok(plan)
+}
+
+/// Запускает стратегии из [`capture_plan`], пока одна не запишет записи в `output_path`
+fn run_capture_plan(plan: &[PlannedCommand], makefile_dir: &Path, output_path: &Path) -> Result<PathBuf> {
+    let (dry_run, captures) = plan.split_last().context("Empty make capture plan")?;
+    if captures.is_empty() {
+        eprintln!("Warning: neither bear nor compiledb is installed, parsing make -n output; install bear for reliable results");
+    }
+
+    for step in captures {
+        let output = step
+            .command()
+            .output()
+            .with_context(|| format!("Failed to execute {}", step.program))?;
+
+        if output.status.success() && has_compile_entries(output_path) {
+            return Ok(output_path.to_path_buf());
+        }
+        eprintln!("Warning: {} captured no compile commands, trying other strategies", step.program);
+    }
+
+    generate_from_make_dry_run(dry_run, makefile_dir, output_path)
+}
+
+/// Генерирует compile_commands.json, разбирая вывод `make -n` (dry-run)
+fn generate_from_make_dry_run(
+    command: &PlannedCommand,
+    makefile_dir: &Path,
+    output_path: &Path,
+) -> Result<PathBuf> {
+    // Запускаем make -n (dry-run) для получения команд без выполнения
+    let output = command
+        .command()
         .output()
         .with_context(|| "Failed to execute make. Is make installed and in PATH?")?;
 
@@ -223,6 +898,72 @@ pub fn generate_from_makefile(
     Ok(output_path.to_path_buf())
 }
 
+/// Генерирует compile_commands.json из GNU Autotools проекта
+///
+/// Если скрипта `configure` ещё нет, сначала выполняется `autoreconf -fi`.
+/// Затем запускается `./configure` с дополнительными аргументами и
+/// `bear -- make -n` для сбора команд компиляции.
+///
+/// # Arguments
+/// * `project_dir` - Директория с configure.ac / configure.in
+/// * `output_path` - Путь для записи compile_commands.json
+/// * `extra_args` - Дополнительные аргументы для `./configure`
+///
+/// # Dependencies
+/// - `autoreconf` (пакет autoconf/automake) — только если нет `configure`
+/// - `bear` (<https://github.com/rizsotto/Bear>) — рекомендуется; без него
+///   используются `compiledb` или разбор вывода `make -n`, как в [`generate_from_makefile`]
+pub fn generate_from_autotools(
+    project_dir: &Path,
+    output_path: &Path,
+    extra_args: &[String],
+) -> Result<PathBuf> {
+    // Генерируем configure при необходимости и конфигурируем проект, чтобы получить Makefile
+    for step in autotools_setup_plan(project_dir, extra_args) {
+        let output = step
+            .command()
+            .output()
+            .with_context(|| format!("Failed to execute {} in {}", step.program, project_dir.display()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("{} failed:\n{}", step.program, stderr);
+        }
+    }
+
+    run_capture_plan(&autotools_capture_plan(project_dir, output_path)?, project_dir, output_path)
+}
+
+/// Команды, которые запускает [`generate_from_autotools`]
+pub fn plan_autotools(project_dir: &Path, output_path: &Path, extra_args: &[String]) -> Result<Vec<PlannedCommand>> {
+    let mut plan = autotools_setup_plan(project_dir, extra_args);
+    plan.extend(autotools_capture_plan(project_dir, output_path)?);
+    Ok(plan)
+}
+
+/// `autoreconf -fi`, если ещё нет скрипта `configure`, и `./configure`
+fn autotools_setup_plan(project_dir: &Path, extra_args: &[String]) -> Vec<PlannedCommand> {
+    let mut plan = Vec::new();
+    if !project_dir.join("configure").exists() {
+        plan.push(PlannedCommand::new(project_dir, "autoreconf").arg("-fi"));
+    }
+    plan.push(PlannedCommand::new(project_dir, "./configure").args(extra_args));
+    plan
+}
+
+/// Перехват команд сборки: `bear -- make -n`, затем стратегии без bear
+fn autotools_capture_plan(project_dir: &Path, output_path: &Path) -> Result<Vec<PlannedCommand>> {
+    capture_plan(project_dir, output_path, &["-n".to_string()], &[])
+}
+
+/// Проверяет, что compile_commands.json существует и содержит хотя бы одну запись
+fn has_compile_entries(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<serde_json::Value>>(&content).ok())
+        .is_some_and(|entries| !entries.is_empty())
+}
+
 /// Парсит вывод `make -n` для извлечения команд компиляции
 fn parse_make_dry_run(output: &str, working_dir: &Path) -> Result<Vec<CompileCommandEntry>> {
     use regex::Regex;
@@ -318,6 +1059,48 @@ pub fn generate_from_vcxproj(
     Ok(output_path.to_path_buf())
 }
 
+/// Извлекает пути из `<Import Project="..."/>` в .vcxproj, .props или .targets
+///
+/// Относительные пути разрешаются от `project_dir` (директории импортирующего
+/// файла), `$(MSBuildThisFileDirectory)` подставляется. Импорты с другими
+/// свойствами MSBuild (например `$(VCTargetsPath)`) пропускаются, так как их
+/// значения известны только самому MSBuild.
+pub fn resolve_vcxproj_imports(content: &str, project_dir: &Path) -> Vec<PathBuf> {
+    use regex::Regex;
+
+    let import_re = Regex::new(r#"<Import\s+[^>]*?Project="([^"]+)""#).unwrap();
+    let this_dir = format!("{}/", project_dir.display());
+    import_re
+        .captures_iter(content)
+        .filter_map(|cap| {
+            let project = cap[1].replace("$(MSBuildThisFileDirectory)", &this_dir).replace('\\', "/");
+            (!project.contains("$(")).then(|| project_dir.join(project))
+        })
+        .collect()
+}
+
+/// Рекурсивно читает импортированные .props/.targets файлы
+///
+/// Содержимое каждого файла добавляется в `imported` один раз, с уже
+/// подставленным `$(MSBuildThisFileDirectory)`. Отсутствующие файлы пропускаются.
+fn load_vcxproj_imports(content: &str, dir: &Path, seen: &mut HashSet<PathBuf>, imported: &mut Vec<String>) {
+    for path in resolve_vcxproj_imports(content, dir) {
+        let Ok(canonical) = fs::canonicalize(&path) else {
+            continue;
+        };
+        if !seen.insert(canonical) {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let props_dir = path.parent().unwrap_or(dir);
+        let text = text.replace("$(MSBuildThisFileDirectory)", &format!("{}/", props_dir.display()));
+        load_vcxproj_imports(&text, props_dir, seen, imported);
+        imported.push(text);
+    }
+}
+
 /// Парсит .vcxproj XML для извлечения команд компиляции
 fn parse_vcxproj(
     content: &str,
@@ -339,9 +1122,15 @@ fn parse_vcxproj(
     // Ищем PreprocessorDefinitions
     let define_re = Regex::new(r#"<PreprocessorDefinitions>([^<]+)</PreprocessorDefinitions>"#)?;
 
+    // Настройки самого проекта, затем импортированных property sheets
+    let mut imported = Vec::new();
+    load_vcxproj_imports(content, project_dir, &mut HashSet::new(), &mut imported);
+    let settings: Vec<&str> = std::iter::once(content).chain(imported.iter().map(String::as_str)).collect();
+
     // Извлекаем include директории
-    let includes: Vec<String> = include_re
-        .captures_iter(content)
+    let includes: Vec<String> = settings
+        .iter()
+        .flat_map(|text| include_re.captures_iter(text))
         .filter_map(|cap| cap.get(1))
         .flat_map(|m| m.as_str().split(';'))
         .filter(|s| !s.is_empty() && !s.starts_with('%'))
@@ -354,8 +1143,9 @@ fn parse_vcxproj(
         .collect();
 
     // Извлекаем препроцессорные определения
-    let defines: Vec<String> = define_re
-        .captures_iter(content)
+    let defines: Vec<String> = settings
+        .iter()
+        .flat_map(|text| define_re.captures_iter(text))
         .filter_map(|cap| cap.get(1))
         .flat_map(|m| m.as_str().split(';'))
         .filter(|s| !s.is_empty() && !s.starts_with('%'))
@@ -432,9 +1222,16 @@ pub fn generate_from_solution(
 
     for cap in project_re.captures_iter(&content) {
         if let Some(proj_match) = cap.get(1) {
+            // Пути в .sln задаются относительно решения в стиле Windows
             let proj_path = sln_dir.join(proj_match.as_str().replace("\\", "/"));
 
-            if proj_path.exists() {
+            if !proj_path.exists() {
+                eprintln!(
+                    "Warning: Project {} listed in {} not found, skipping",
+                    proj_path.display(),
+                    sln_path.display()
+                );
+            } else {
                 let proj_content = fs::read_to_string(&proj_path)?;
                 let proj_dir = proj_path.parent().unwrap_or(sln_dir);
 
@@ -493,9 +1290,11 @@ pub fn generate_compile_commands(
         BuildSystem::CMake => {
             let default_build = project_dir.join("build");
             let build = build_dir.unwrap_or(&default_build);
-            generate_from_cmake(project_dir, build, Some("Ninja"), &[])
+            generate_from_cmake(project_dir, build, Some("Ninja"), &[], None)
         }
+        BuildSystem::XMake => generate_from_xmake(project_dir, output_path),
         BuildSystem::Make => generate_from_makefile(project_dir, output_path, &[]),
+        BuildSystem::Autotools => generate_from_autotools(project_dir, output_path, &[]),
         BuildSystem::VcxProj => {
             // Находим первый .vcxproj файл
             let vcxproj = find_file_with_extension(project_dir, "vcxproj")?;
@@ -507,9 +1306,10 @@ pub fn generate_compile_commands(
             generate_from_solution(&sln, output_path, "Debug", "x64")
         }
         BuildSystem::Cargo => generate_from_cargo(project_dir, output_path, build_dir),
+        BuildSystem::CompileFlags => generate_from_compile_flags(project_dir, output_path),
         BuildSystem::Unknown => {
             bail!(
-                "Could not detect build system in {}. \nSupported: CMakeLists.txt, Makefile, .vcxproj, .sln, Cargo.toml",
+                "Could not detect build system in {}. \nSupported: CMakeLists.txt, xmake.lua, configure.ac, Makefile, .vcxproj, .sln, Cargo.toml, compile_flags.txt",
                 project_dir.display()
             )
         }
@@ -562,11 +1362,133 @@ pub fn generate_from_cargo(project_dir: &Path, output_path: &Path, _build_dir: O
     }
 }
 
+/// Генерирует compile_commands.json для Cargo проекта по сообщениям `cargo build`
+///
+/// Запускает `cargo build --message-format=json` в каталоге проекта и для
+/// каждого сообщения `compiler-artifact` синтезирует вызов `rustc` для
+/// корневого файла цели. В отличие от [`generate_from_cargo`], результат —
+/// обычный compile_commands.json, а не SCIP индекс.
+///
+/// # Arguments
+/// * `project_dir` - Директория с Cargo.toml
+/// * `output_path` - Путь для записи compile_commands.json
+pub fn generate_cargo_compile_commands(project_dir: &Path, output_path: &Path) -> Result<PathBuf> {
+    println!("Running cargo build --message-format=json...");
+
+    let output = Command::new("cargo")
+        .args(["build", "--message-format=json"])
+        .current_dir(project_dir)
+        .output()
+        .context("Failed to run cargo. Is it installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("cargo build failed:\n{}", stderr);
+    }
+
+    // Сообщения в формате JSON Lines идут в stdout, диагностика — в stderr
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let entries = parse_cargo_build_messages(&stdout, project_dir);
+    if entries.is_empty() {
+        bail!("cargo build produced no compiler artifacts in {}", project_dir.display());
+    }
+
+    write_compile_commands(&entries, output_path)?;
+
+    println!("Generated {} entries to {}", entries.len(), output_path.display());
+    Ok(output_path.to_path_buf())
+}
+
+/// Парсит вывод `cargo build --message-format=json` в записи compile_commands.json
+///
+/// Строки, которые не являются JSON или не относятся к `compiler-artifact`,
+/// пропускаются. Каждый корневой файл цели попадает в результат один раз.
+fn parse_cargo_build_messages(output: &str, project_dir: &Path) -> Vec<CompileCommandEntry> {
+    let mut entries: Vec<CompileCommandEntry> = Vec::new();
+
+    for line in output.lines() {
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-artifact" {
+            continue;
+        }
+        let target = &message["target"];
+        let Some(src_path) = target["src_path"].as_str() else {
+            continue;
+        };
+        if entries.iter().any(|e| e.file == src_path) {
+            continue;
+        }
+
+        // Каталог пакета: из manifest_path, если cargo его сообщает
+        let directory = message["manifest_path"]
+            .as_str()
+            .and_then(|m| Path::new(m).parent())
+            .unwrap_or(project_dir);
+
+        let mut arguments = vec!["rustc".to_string()];
+        if let Some(name) = target["name"].as_str() {
+            arguments.extend(["--crate-name".to_string(), name.replace('-', "_")]);
+        }
+        if let Some(edition) = target["edition"].as_str() {
+            arguments.push(format!("--edition={}", edition));
+        }
+        for crate_type in target["crate_types"].as_array().into_iter().flatten().filter_map(|t| t.as_str()) {
+            arguments.extend(["--crate-type".to_string(), crate_type.to_string()]);
+        }
+        arguments.push(src_path.to_string());
+        if let Some(artifact) = message["filenames"].as_array().and_then(|f| f.first()).and_then(|f| f.as_str()) {
+            arguments.extend(["-o".to_string(), artifact.to_string()]);
+        }
+        if let Some(package_id) = message["package_id"].as_str() {
+            log::debug!("{}: {}", package_id, src_path);
+        }
+
+        entries.push(CompileCommandEntry {
+            directory: directory.to_string_lossy().to_string(),
+            file: src_path.to_string(),
+            command: None,
+            arguments: Some(arguments),
+        });
+    }
+
+    entries
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_read_cmake_cache() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("CMakeCache.txt"),
+            "# This is the CMakeCache file.\n\
+             //Flags used by the CXX compiler\n\
+             CMAKE_CXX_FLAGS:STRING=-std=c++17 -Wall\n\
+             CMAKE_C_FLAGS:STRING=-std=c11\n\
+             ZLIB_INCLUDE_DIRS:PATH=/opt/zlib/include;/opt/zlib/extra\n\
+             CMAKE_BUILD_TYPE=Debug\n\
+             \n",
+        )
+        .unwrap();
+
+        let cache = read_cmake_cache(dir.path()).unwrap();
+        assert_eq!(cache["CMAKE_CXX_FLAGS"], "-std=c++17 -Wall");
+        assert_eq!(cache["CMAKE_BUILD_TYPE"], "Debug");
+        assert_eq!(cache.len(), 4);
+
+        assert_eq!(
+            cmake_cache_flags(&cache, false),
+            ["-std=c++17", "-Wall", "-I/opt/zlib/include", "-I/opt/zlib/extra"]
+        );
+        assert_eq!(cmake_cache_flags(&cache, true), ["-std=c11", "-I/opt/zlib/include", "-I/opt/zlib/extra"]);
+        assert!(read_cmake_cache(&dir.path().join("missing")).is_err());
+    }
+
     #[test]
     fn test_detect_build_system_cmake() {
         // Тест требует временной директории, пропускаем в unit tests
@@ -580,6 +1502,200 @@ mod tests {
         assert_eq!(detect_build_system(td.path()), BuildSystem::Cargo);
     }
 
+    #[test]
+    fn test_detect_build_system_xmake() {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(td.path().join("xmake.lua"), "target(\"app\")\n    set_kind(\"binary\")\n").unwrap();
+        std::fs::write(td.path().join("Makefile"), "all:\n").unwrap();
+        assert_eq!(detect_build_system(td.path()), BuildSystem::XMake);
+    }
+
+    #[test]
+    fn test_generate_from_compile_flags() {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(td.path().join("compile_flags.txt"), "-std=c++20\n-Iinclude\n\n").unwrap();
+        std::fs::create_dir(td.path().join("src")).unwrap();
+        std::fs::write(td.path().join("src/main.cpp"), "int main() { return 0; }\n").unwrap();
+        std::fs::write(td.path().join("src/util.cpp"), "int util() { return 1; }\n").unwrap();
+        std::fs::write(td.path().join("README.md"), "docs\n").unwrap();
+        assert_eq!(detect_build_system(td.path()), BuildSystem::CompileFlags);
+
+        let output = td.path().join("compile_commands.json");
+        generate_compile_commands(td.path(), &output, None).unwrap();
+        let cmds = crate::load_compile_commands(output.to_str().unwrap()).unwrap();
+        assert_eq!(cmds.len(), 2);
+        assert!(cmds[0].file.ends_with("main.cpp") && cmds[1].file.ends_with("util.cpp"));
+        let arguments = cmds[0].arguments.as_ref().unwrap();
+        assert_eq!(arguments[..3], ["clang++", "-std=c++20", "-Iinclude"]);
+        assert_eq!(arguments.last(), Some(&cmds[0].file));
+    }
+
+    #[test]
+    fn test_extract_from_ninja() {
+        let td = tempdir().expect("tempdir");
+        std::fs::create_dir(td.path().join("CMakeFiles")).unwrap();
+        std::fs::write(
+            td.path().join("CMakeFiles/rules.ninja"),
+            "rule CXX_COMPILER__app_Debug\n\
+             \x20 depfile = $DEP_FILE\n\
+             \x20 command = /usr/bin/c++ $DEFINES $INCLUDES $FLAGS -o $out -c $in\n\
+             \x20 description = Building CXX object $out\n\
+             \n\
+             rule CXX_EXECUTABLE_LINKER__app_Debug\n\
+             \x20 command = /usr/bin/c++ $FLAGS $in -o $TARGET_FILE\n",
+        )
+        .unwrap();
+        std::fs::write(
+            td.path().join("build.ninja"),
+            "ninja_required_version = 1.5\n\
+             include CMakeFiles/rules.ninja\n\
+             \n\
+             build CMakeFiles/app.dir/src/main.cpp.o: CXX_COMPILER__app_Debug /src/main.cpp || cmake_object_order_depends_target_app\n\
+             \x20 DEFINES = -DDEBUG\n\
+             \x20 INCLUDES = -I/src/include\n\
+             \x20 FLAGS = -g $\n\
+             \x20     -std=c++20\n\
+             \n\
+             build app: CXX_EXECUTABLE_LINKER__app_Debug CMakeFiles/app.dir/src/main.cpp.o\n\
+             \x20 TARGET_FILE = app\n",
+        )
+        .unwrap();
+
+        let output = td.path().join("compile_commands.json");
+        extract_from_ninja(&td.path().join("build.ninja"), &output).unwrap();
+        let cmds = crate::load_compile_commands(output.to_str().unwrap()).unwrap();
+        assert_eq!(cmds.len(), 1);
+        assert_eq!(cmds[0].file, "/src/main.cpp");
+        assert_eq!(
+            cmds[0].command.as_deref(),
+            Some("/usr/bin/c++ -DDEBUG -I/src/include -g -std=c++20 -o CMakeFiles/app.dir/src/main.cpp.o -c /src/main.cpp")
+        );
+    }
+
+    #[test]
+    fn test_detect_build_system_autotools() {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(td.path().join("configure.ac"), "AC_INIT([x], [0.1])\n").unwrap();
+        std::fs::write(td.path().join("Makefile"), "all:\n").unwrap();
+        assert_eq!(detect_build_system(td.path()), BuildSystem::Autotools);
+
+        let legacy = tempdir().expect("tempdir");
+        std::fs::write(legacy.path().join("configure.in"), "AC_INIT(x)\n").unwrap();
+        assert_eq!(detect_build_system(legacy.path()), BuildSystem::Autotools);
+    }
+
+    #[test]
+    fn test_extract_vcpkg_dependencies() {
+        let td = tempdir().expect("tempdir");
+        assert!(extract_vcpkg_dependencies(td.path()).unwrap().is_empty());
+
+        std::fs::write(
+            td.path().join("vcpkg.json"),
+            r#"{
+                "name": "demo",
+                "version": "1.0.0",
+                "dependencies": [
+                    "fmt",
+                    { "name": "boost-asio", "version>=": "1.83.0" },
+                    { "name": "curl", "version-semver": "8.4.0", "features": ["ssl", { "name": "http2", "platform": "linux" }] },
+                    { "name": "zlib" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let deps = extract_vcpkg_dependencies(td.path()).unwrap();
+        assert_eq!(deps.len(), 4);
+        assert_eq!(deps[0], VcpkgDep { name: "fmt".into(), version_constraint: None, features: vec![] });
+        assert_eq!(deps[1].version_constraint.as_deref(), Some("1.83.0"));
+        assert_eq!(deps[2].name, "curl");
+        assert_eq!(deps[2].version_constraint.as_deref(), Some("8.4.0"));
+        assert_eq!(deps[2].features, vec!["ssl", "http2"]);
+        assert!(deps[3].features.is_empty());
+
+        assert_eq!(
+            vcpkg_toolchain_file(td.path(), Some(Path::new("/opt/vcpkg"))),
+            Some(PathBuf::from("/opt/vcpkg/scripts/buildsystems/vcpkg.cmake"))
+        );
+    }
+
+    #[test]
+    fn test_extract_cargo_lock_graph() {
+        let td = tempdir().expect("tempdir");
+        let lock = td.path().join("Cargo.lock");
+        std::fs::write(
+            &lock,
+            r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "serde",
+ "syn 2.0.48",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.195"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = [
+ "syn 2.0.48",
+]
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.48"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        )
+        .unwrap();
+
+        let packages = extract_cargo_lock_graph(&lock).unwrap();
+        assert_eq!(packages.len(), 4);
+        assert_eq!(packages[0].dependencies, vec!["serde", "syn"]);
+        assert_eq!(packages[1].version, "1.0.195");
+        assert_eq!(packages.iter().map(|p| p.dependencies.len()).sum::<usize>(), 3);
+        assert!(packages[3].dependencies.is_empty());
+
+        assert!(extract_cargo_lock_graph(&td.path().join("missing.lock")).is_err());
+    }
+
+    #[test]
+    fn test_is_tool_available_without_path() {
+        let empty = tempdir().unwrap();
+        assert!(!is_tool_available("bear", Some(empty.path().as_os_str())));
+        assert!(!is_tool_available("compiledb", Some(empty.path().as_os_str())));
+    }
+
+    #[test]
+    fn test_parse_cargo_build_messages() {
+        let output = r#"{"reason":"compiler-artifact","package_id":"path+file:///work/demo#0.1.0","manifest_path":"/work/demo/Cargo.toml","target":{"kind":["lib"],"crate_types":["lib"],"name":"demo-core","src_path":"/work/demo/src/lib.rs","edition":"2021"},"filenames":["/work/demo/target/debug/libdemo_core.rlib"],"fresh":false}
+{"reason":"compiler-message","package_id":"path+file:///work/demo#0.1.0","message":{"rendered":"warning: unused variable"}}
+{"reason":"compiler-artifact","package_id":"path+file:///work/demo#0.1.0","manifest_path":"/work/demo/Cargo.toml","target":{"kind":["bin"],"crate_types":["bin"],"name":"demo","src_path":"/work/demo/src/main.rs","edition":"2021"},"filenames":["/work/demo/target/debug/demo"],"executable":"/work/demo/target/debug/demo","fresh":false}
+   Compiling demo v0.1.0 (/work/demo)
+{"reason":"build-finished","success":true}
+"#;
+        let entries = parse_cargo_build_messages(output, Path::new("/elsewhere"));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].directory, "/work/demo");
+        assert_eq!(entries[0].file, "/work/demo/src/lib.rs");
+        assert_eq!(
+            entries[0].arguments.as_deref().unwrap(),
+            [
+                "rustc", "--crate-name", "demo_core", "--edition=2021", "--crate-type", "lib",
+                "/work/demo/src/lib.rs", "-o", "/work/demo/target/debug/libdemo_core.rlib",
+            ]
+        );
+        assert_eq!(entries[1].file, "/work/demo/src/main.rs");
+        assert_eq!(entries[1].arguments.as_ref().unwrap()[0], "rustc");
+    }
+
     #[test]
     fn test_parse_make_dry_run() {
         let output = r#"
@@ -594,6 +1710,26 @@ make[1]: Leaving directory '/home/user/project'
         assert!(entries[1].file.contains("app.cpp"));
     }
 
+    #[test]
+    fn test_plan_autotools_ends_with_make_dry_run() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("configure.ac"), "AC_INIT([demo], [1.0])\n").unwrap();
+        let output = temp.path().join("compile_commands.json");
+
+        // Набор шагов перехвата зависит от установленных bear и compiledb,
+        // но разбор `make -n` есть всегда и идёт последним
+        let plan = plan_autotools(temp.path(), &output, &["--prefix=/usr".to_string()]).unwrap();
+        assert_eq!(plan[0].to_string(), format!("(in {}) autoreconf -fi", temp.path().display()));
+        assert_eq!(plan[1].program, "./configure");
+        assert_eq!(plan[1].args, vec!["--prefix=/usr"]);
+        let last = plan.last().unwrap();
+        assert_eq!(last.program, "make");
+        assert_eq!(last.args, vec!["-n", "-w"]);
+        assert!(!plan[2].fallback);
+        assert!(plan[3..].iter().all(|step| step.fallback));
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1, "planning must not run anything");
+    }
+
     #[test]
     fn test_parse_vcxproj_basic() {
         let vcxproj = r#"
@@ -615,6 +1751,95 @@ make[1]: Leaving directory '/home/user/project'
         assert_eq!(entries.len(), 2);
     }
 
+    #[test]
+    fn test_generate_from_vcxproj_with_props_imports() {
+        let td = tempdir().expect("tempdir");
+        let app_dir = td.path().join("app");
+        let shared_dir = td.path().join("shared");
+        std::fs::create_dir_all(&app_dir).unwrap();
+        std::fs::create_dir_all(&shared_dir).unwrap();
+        std::fs::write(
+            shared_dir.join("common.props"),
+            r#"<Project>
+  <Import Project="warnings.props" />
+  <ItemDefinitionGroup>
+    <ClCompile>
+      <AdditionalIncludeDirectories>$(MSBuildThisFileDirectory)include;%(AdditionalIncludeDirectories)</AdditionalIncludeDirectories>
+      <PreprocessorDefinitions>USE_SHARED;%(PreprocessorDefinitions)</PreprocessorDefinitions>
+    </ClCompile>
+  </ItemDefinitionGroup>
+</Project>"#,
+        )
+        .unwrap();
+        std::fs::write(
+            shared_dir.join("warnings.props"),
+            r#"<Project><ItemDefinitionGroup><ClCompile><PreprocessorDefinitions>STRICT</PreprocessorDefinitions></ClCompile></ItemDefinitionGroup>
+<Import Project="common.props" /></Project>"#,
+        )
+        .unwrap();
+        let vcxproj = app_dir.join("app.vcxproj");
+        std::fs::write(
+            &vcxproj,
+            r#"<Project>
+  <Import Project="$(VCTargetsPath)\Microsoft.Cpp.Default.props" />
+  <ImportGroup Label="PropertySheets">
+    <Import Project="..\shared\common.props" Condition="exists('..\shared\common.props')" />
+    <Import Project="..\shared\missing.props" />
+  </ImportGroup>
+  <ItemDefinitionGroup>
+    <ClCompile>
+      <AdditionalIncludeDirectories>src</AdditionalIncludeDirectories>
+      <PreprocessorDefinitions>APP</PreprocessorDefinitions>
+    </ClCompile>
+  </ItemDefinitionGroup>
+  <ItemGroup><ClCompile Include="main.cpp" /></ItemGroup>
+</Project>"#,
+        )
+        .unwrap();
+
+        let imports = resolve_vcxproj_imports(&std::fs::read_to_string(&vcxproj).unwrap(), &app_dir);
+        assert_eq!(imports, vec![app_dir.join("../shared/common.props"), app_dir.join("../shared/missing.props")]);
+
+        let output = td.path().join("compile_commands.json");
+        generate_from_vcxproj(&vcxproj, &output, "Debug", "x64").unwrap();
+        let cmds = crate::load_compile_commands(output.to_str().unwrap()).unwrap();
+        let arguments = cmds[0].arguments.as_ref().unwrap();
+        let shared_include = format!("-I{}/../shared/include", app_dir.display());
+        for expected in ["-Isrc", shared_include.as_str(), "-DAPP", "-DUSE_SHARED", "-DSTRICT"] {
+            assert!(arguments.iter().any(|a| a == expected), "{} missing from {:?}", expected, arguments);
+        }
+    }
+
+    #[test]
+    fn test_generate_from_solution_skips_missing_project() {
+        let td = tempdir().expect("tempdir");
+        let app_dir = td.path().join("src").join("app");
+        std::fs::create_dir_all(&app_dir).unwrap();
+        std::fs::write(
+            app_dir.join("app.vcxproj"),
+            r#"<Project><ItemGroup><ClCompile Include="main.cpp" /></ItemGroup></Project>"#,
+        )
+        .unwrap();
+
+        let sln = td.path().join("demo.sln");
+        std::fs::write(
+            &sln,
+            r#"Project("{8BC9CEB8-8B4A-11D0-8D11-00A0C91BC942}") = "app", "src\app\app.vcxproj", "{11111111-1111-1111-1111-111111111111}"
+EndProject
+Project("{8BC9CEB8-8B4A-11D0-8D11-00A0C91BC942}") = "gone", "lib\gone\gone.vcxproj", "{22222222-2222-2222-2222-222222222222}"
+EndProject
+"#,
+        )
+        .unwrap();
+
+        let output = td.path().join("compile_commands.json");
+        generate_from_solution(&sln, &output, "Debug", "x64").expect("missing project must not fail");
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0]["file"].as_str().unwrap().ends_with("main.cpp"));
+    }
+
     #[test]
     fn test_generate_from_cargo_with_mocked_rust_analyzer() {
         // Create temp project with Cargo.toml
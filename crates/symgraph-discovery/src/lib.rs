@@ -10,25 +10,35 @@
 
 pub mod generate;
 pub mod scip;
+pub mod watch;
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 // Реэкспорт основных типов и функций из модуля generate
 pub use generate::{
-    detect_build_system, generate_compile_commands, generate_from_cmake, generate_from_makefile,
-    generate_from_solution, generate_from_vcxproj, generate_from_cargo, BuildSystem, CompileCommandEntry,
+    cmake_cache_flags, detect_build_system, extract_cargo_lock_graph, extract_vcpkg_dependencies, generate_compile_commands, generate_from_autotools,
+    extract_from_ninja, generate_from_cmake, generate_from_compile_flags, generate_from_makefile, generate_from_solution, generate_from_vcxproj,
+    generate_from_cargo, generate_from_xmake, generate_cargo_compile_commands, is_bear_available, is_compiledb_available, plan_autotools, plan_cmake,
+    plan_makefile, plan_xmake, read_cmake_cache, resolve_vcxproj_imports, vcpkg_toolchain_file, BuildSystem,
+    CargoDep, CompileCommandEntry, PlannedCommand, VcpkgDep,
 };
 
 // Реэкспорт SCIP типов и функций
 pub use scip::{
-    ScipConfig, ScipLanguage, generate_scip_index, detect_language, 
+    ScipConfig, ScipLanguage, generate_scip_index, detect_language, detect_all_languages,
     check_scip_tool_availability, get_installation_instruction,
 };
 
-#[derive(Debug, Deserialize)]
+pub use watch::Debouncer;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompileCommand {
     pub directory: String,
+    #[serde(default)]
     pub file: String,
     pub command: Option<String>,
     pub arguments: Option<Vec<String>>,
@@ -40,12 +50,352 @@ pub fn load_compile_commands(path: &str) -> Result<Vec<CompileCommand>> {
     Ok(cmds)
 }
 
+/// Потоковое чтение compile_commands.json: записи передаются в `callback` по одной
+///
+/// Сам разбор держит в памяти только текущую запись; какие записи сохранить,
+/// решает `callback`. Возвращает число обработанных записей; ошибка
+/// `callback` прерывает чтение и возвращается как есть.
+pub fn stream_compile_commands<F>(path: &str, mut callback: F) -> Result<usize>
+where
+    F: FnMut(CompileCommand) -> Result<()>,
+{
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let mut callback_error = None;
+    let visitor = CommandVisitor {
+        callback: &mut callback,
+        error: &mut callback_error,
+    };
+    match serde::Deserializer::deserialize_seq(&mut deserializer, visitor) {
+        Ok(count) => {
+            deserializer.end()?;
+            Ok(count)
+        }
+        Err(e) => Err(callback_error.unwrap_or_else(|| e.into())),
+    }
+}
+
+/// Обход массива верхнего уровня без сборки его в `Vec`
+struct CommandVisitor<'a, F> {
+    callback: &'a mut F,
+    error: &'a mut Option<anyhow::Error>,
+}
+
+impl<'de, F> serde::de::Visitor<'de> for CommandVisitor<'_, F>
+where
+    F: FnMut(CompileCommand) -> Result<()>,
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of compile commands")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<usize, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut count = 0;
+        while let Some(cmd) = seq.next_element::<CompileCommand>()? {
+            if let Err(e) = (self.callback)(cmd) {
+                *self.error = Some(e);
+                return Err(serde::de::Error::custom("compile command callback failed"));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Аргументы записи compile_commands.json в виде списка
+///
+/// Поле `arguments` возвращается как есть, `command` разбивается по правилам
+/// командной оболочки (кавычки, экранирование `\`). Без обоих полей — пустой список.
+pub fn normalize_to_arguments(cmd: &CompileCommand) -> Result<Vec<String>> {
+    match (&cmd.arguments, &cmd.command) {
+        (Some(arguments), _) => Ok(arguments.clone()),
+        (None, Some(command)) => shell_words::split(command)
+            .map_err(|e| anyhow::anyhow!("Cannot split command for {}: {}", cmd.file, e)),
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
 /// Парсинг compile_commands.json из строки (для тестирования)
 pub fn parse_compile_commands(json: &str) -> Result<Vec<CompileCommand>> {
     let cmds: Vec<CompileCommand> = serde_json::from_str(json)?;
     Ok(cmds)
 }
 
+/// Ошибка валидации записи compile_commands.json
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// Директория компиляции не существует
+    MissingDirectory { index: usize, directory: String },
+    /// Поле `file` пустое или отсутствует
+    EmptyFile { index: usize },
+    /// Нет ни `command`, ни `arguments`
+    MissingCommand { index: usize, file: String },
+    /// Указаны одновременно `command` и `arguments`
+    ConflictingCommand { index: usize, file: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::MissingDirectory { index, directory } => {
+                write!(f, "entry {}: directory does not exist: {}", index, directory)
+            }
+            ValidationError::EmptyFile { index } => write!(f, "entry {}: file is empty", index),
+            ValidationError::MissingCommand { index, file } => {
+                write!(f, "entry {}: neither command nor arguments present for {}", index, file)
+            }
+            ValidationError::ConflictingCommand { index, file } => {
+                write!(f, "entry {}: both command and arguments present for {}", index, file)
+            }
+        }
+    }
+}
+
+/// Проверка записей compile_commands.json
+///
+/// Для каждой записи проверяется, что `directory` существует, `file` не пустой
+/// и задано ровно одно из полей `command`/`arguments`.
+pub fn validate_compile_commands(cmds: &[CompileCommand]) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    for (index, cmd) in cmds.iter().enumerate() {
+        if !Path::new(&cmd.directory).is_dir() {
+            errors.push(ValidationError::MissingDirectory {
+                index,
+                directory: cmd.directory.clone(),
+            });
+        }
+
+        if cmd.file.trim().is_empty() {
+            errors.push(ValidationError::EmptyFile { index });
+        }
+
+        match (&cmd.command, &cmd.arguments) {
+            (None, None) => errors.push(ValidationError::MissingCommand {
+                index,
+                file: cmd.file.clone(),
+            }),
+            (Some(_), Some(_)) => errors.push(ValidationError::ConflictingCommand {
+                index,
+                file: cmd.file.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// Нормализация путей в compile_commands.json
+///
+/// Относительный `directory` разрешается относительно `base_dir`, относительный
+/// `file` — относительно `directory`. Существующие пути канонизируются.
+pub fn normalize_compile_commands(cmds: &mut [CompileCommand], base_dir: &Path) {
+    for cmd in cmds.iter_mut() {
+        let directory = absolutize(base_dir, Path::new(&cmd.directory));
+        cmd.directory = directory.to_string_lossy().to_string();
+
+        if !cmd.file.is_empty() {
+            let file = absolutize(&directory, Path::new(&cmd.file));
+            cmd.file = file.to_string_lossy().to_string();
+        }
+    }
+}
+
+/// Дополнение аргументов компиляции флагами из кэша CMake
+///
+/// Файлам `.c` добавляются флаги C, остальным — C++ (см.
+/// [`cmake_cache_flags`]). Флаги вставляются сразу после компилятора; уже
+/// присутствующие в записи не дублируются. Запись с `command` переводится
+/// в `arguments`.
+pub fn apply_cmake_cache(cmds: &mut [CompileCommand], cache: &HashMap<String, String>) -> Result<()> {
+    for cmd in cmds.iter_mut() {
+        let is_c = Path::new(&cmd.file).extension().is_some_and(|ext| ext == "c");
+        let mut arguments = normalize_to_arguments(cmd)?;
+        let position = arguments.len().min(1);
+        let missing: Vec<String> = cmake_cache_flags(cache, is_c)
+            .into_iter()
+            .filter(|flag| !arguments.contains(flag))
+            .collect();
+        arguments.splice(position..position, missing);
+        cmd.arguments = Some(arguments);
+        cmd.command = None;
+    }
+    Ok(())
+}
+
+/// Замена префикса путей в compile_commands.json
+///
+/// Нужна, когда база собрана в контейнере (`/workspace/src`), а исходники на
+/// хосте лежат в другом месте (`/home/user/project/src`). В `file` и
+/// `directory` заменяется только начало пути, в `command` и `arguments` —
+/// каждое вхождение префикса (например, в `-I/workspace/include`). Префикс
+/// совпадает только целым компонентом пути; `/` и `\` считаются одинаковыми.
+pub fn rewrite_paths(cmds: &mut [CompileCommand], from_prefix: &str, to_prefix: &str) {
+    let from = from_prefix.trim_end_matches(['/', '\\']);
+    let to = to_prefix.trim_end_matches(['/', '\\']);
+    if from.is_empty() {
+        return;
+    }
+
+    for cmd in cmds.iter_mut() {
+        cmd.directory = replace_path_prefix(&cmd.directory, from, to, false);
+        cmd.file = replace_path_prefix(&cmd.file, from, to, false);
+        if let Some(command) = &mut cmd.command {
+            *command = replace_path_prefix(command, from, to, true);
+        }
+        for argument in cmd.arguments.iter_mut().flatten() {
+            *argument = replace_path_prefix(argument, from, to, true);
+        }
+    }
+}
+
+/// Разбор аргумента `FROM:TO` для [`rewrite_paths`]
+///
+/// Двоеточие после буквы диска (`C:\build:D:\src`) разделителем не считается.
+pub fn parse_path_rewrite(spec: &str) -> Result<(String, String)> {
+    let bytes = spec.as_bytes();
+    let is_drive_colon = |i: usize| {
+        let letter_start = i == 1 || (i >= 2 && bytes[i - 2] == b':');
+        letter_start
+            && bytes[i - 1].is_ascii_alphabetic()
+            && matches!(bytes.get(i + 1), Some(b'/') | Some(b'\\'))
+    };
+    let separator = spec
+        .char_indices()
+        .filter(|&(i, c)| c == ':' && !is_drive_colon(i))
+        .map(|(i, _)| i)
+        .next();
+    match separator {
+        Some(i) if i > 0 && i + 1 < spec.len() => Ok((spec[..i].to_string(), spec[i + 1..].to_string())),
+        _ => anyhow::bail!("Invalid path rewrite '{}', expected FROM:TO", spec),
+    }
+}
+
+/// Опции, к которым путь приписывается без пробела, например `-I/src/include`
+const PATH_OPTION_PREFIXES: &[&str] = &[
+    "-I", "-isystem", "-iquote", "-idirafter", "-include", "-imacros", "-L", "-F", "-o", "-MF",
+    "/I", "/FI", "/Fo", "/Fe", "/Fd", "/Fp",
+];
+
+/// Замена `from` на `to` в начале `value`, а если `anywhere` — в начале каждого
+/// слова командной строки: после пробела, `=`, кавычки или опции вроде `-I`/`/I`.
+/// Внутри пути (`/opt/workspace` при `from` = `/workspace`) замены нет.
+fn replace_path_prefix(value: &str, from: &str, to: &str, anywhere: bool) -> String {
+    if !anywhere {
+        if starts_with_path(value, from) {
+            return format!("{}{}", to, &value[from.len()..]);
+        }
+        return value.to_string();
+    }
+
+    let mut result = String::with_capacity(value.len());
+    // Начало текущего слова до позиции `rest`
+    let mut word = String::new();
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        let at_boundary = word.is_empty() || PATH_OPTION_PREFIXES.contains(&word.as_str());
+        if at_boundary && starts_with_path(rest, from) {
+            result.push_str(to);
+            word.push_str(to);
+            rest = &rest[from.len()..];
+            continue;
+        }
+        result.push(c);
+        if c.is_whitespace() || matches!(c, '=' | '"' | '\'') {
+            word.clear();
+        } else {
+            word.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Начинается ли `value` с пути `prefix`, за которым идёт разделитель или конец пути
+fn starts_with_path(value: &str, prefix: &str) -> bool {
+    if value.len() < prefix.len() || !value.is_char_boundary(prefix.len()) {
+        return false;
+    }
+    let (head, tail) = value.split_at(prefix.len());
+    let same = head
+        .chars()
+        .zip(prefix.chars())
+        .all(|(a, b)| a == b || (is_path_separator(a) && is_path_separator(b)));
+    same && tail
+        .chars()
+        .next()
+        .is_none_or(|c| is_path_separator(c) || !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+}
+
+fn is_path_separator(c: char) -> bool {
+    c == '/' || c == '\\'
+}
+
+/// Присоединение относительного пути к базе с канонизацией, если путь существует
+fn absolutize(base: &Path, path: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    };
+    std::fs::canonicalize(&joined).unwrap_or(joined)
+}
+
+/// Фильтр файлов compile_commands.json по glob-шаблонам
+///
+/// Файл проходит фильтр, если он совпадает хотя бы с одним include-шаблоном
+/// (или include-шаблоны не заданы) и не совпадает ни с одним exclude-шаблоном.
+#[derive(Debug, Default)]
+pub struct FileFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl FileFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<glob::Pattern>> {
+            patterns
+                .iter()
+                .map(|p| glob::Pattern::new(p).map_err(|e| anyhow::anyhow!("Invalid pattern '{}': {}", p, e)))
+                .collect()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        // Шаблоны пишутся с `/`, пути из Windows-генераторов приводим к тому же виду
+        let path = path.replace('\\', "/");
+        let included = self.include.is_empty()
+            || self.include.iter().any(|p| p.matches_with(&path, options));
+        included && !self.exclude.iter().any(|p| p.matches_with(&path, options))
+    }
+}
+
+/// Отбор записей compile_commands.json по фильтру; возвращает оставшиеся записи и число пропущенных
+pub fn filter_compile_commands(cmds: Vec<CompileCommand>, filter: &FileFilter) -> (Vec<CompileCommand>, usize) {
+    let total = cmds.len();
+    let kept: Vec<_> = cmds.into_iter().filter(|cc| filter.matches(&cc.file)).collect();
+    let skipped = total - kept.len();
+    (kept, skipped)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +488,44 @@ mod tests {
         assert!(cmds[0].file.ends_with("main.cpp"));
     }
 
+    /// Демонстрация: разбиение "command" с кавычками и экранированием
+    #[test]
+    fn test_normalize_to_arguments() {
+        let json = r#"[
+            {
+                "directory": "/build",
+                "file": "/src/main.cpp",
+                "command": "clang++ -DNAME=\"my app\" '-I/opt/my include' -DPATH=a\\ b -c main.cpp"
+            },
+            {
+                "directory": "/build",
+                "file": "/src/util.cpp",
+                "arguments": ["clang++", "-DNAME=my app", "-c", "util.cpp"]
+            },
+            {
+                "directory": "/build",
+                "file": "/src/empty.cpp"
+            },
+            {
+                "directory": "/build",
+                "file": "/src/broken.cpp",
+                "command": "clang++ -DNAME=\"unterminated"
+            }
+        ]"#;
+
+        let cmds = parse_compile_commands(json).unwrap();
+        assert_eq!(
+            normalize_to_arguments(&cmds[0]).unwrap(),
+            ["clang++", "-DNAME=my app", "-I/opt/my include", "-DPATH=a b", "-c", "main.cpp"]
+        );
+        assert_eq!(
+            normalize_to_arguments(&cmds[1]).unwrap(),
+            ["clang++", "-DNAME=my app", "-c", "util.cpp"]
+        );
+        assert!(normalize_to_arguments(&cmds[2]).unwrap().is_empty());
+        assert!(normalize_to_arguments(&cmds[3]).is_err());
+    }
+
     /// Демонстрация: пустой compile_commands.json
     #[test]
     fn test_parse_empty() {
@@ -146,6 +534,46 @@ mod tests {
         assert!(cmds.is_empty());
     }
 
+    /// Демонстрация: потоковое чтение большого compile_commands.json
+    #[test]
+    fn test_stream_compile_commands() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("compile_commands.json");
+        let cmds: Vec<CompileCommand> = (0..1000)
+            .map(|i| CompileCommand {
+                directory: "/build".to_string(),
+                file: format!("src/file{}.cpp", i),
+                command: Some(format!("clang++ -c src/file{}.cpp", i)),
+                arguments: None,
+            })
+            .collect();
+        std::fs::write(&path, serde_json::to_string(&cmds).unwrap()).unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut files = Vec::new();
+        let count = stream_compile_commands(path, |cmd| {
+            files.push(cmd.file);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 1000);
+        assert_eq!(files.len(), 1000);
+        assert_eq!(files[999], "src/file999.cpp");
+
+        // Ошибка обработчика останавливает чтение
+        let mut seen = 0;
+        let err = stream_compile_commands(path, |_| {
+            seen += 1;
+            if seen == 10 {
+                anyhow::bail!("stop");
+            }
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "stop");
+        assert_eq!(seen, 10);
+    }
+
     /// Демонстрация: обработка разных директорий
     #[test]
     fn test_different_directories() {
@@ -169,4 +597,184 @@ mod tests {
         // Один и тот же файл может компилироваться по-разному
         assert_eq!(cmds[0].file, cmds[1].file);
     }
+
+    /// Демонстрация: валидация записей с намеренными нарушениями
+    #[test]
+    fn test_validate_compile_commands() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().to_string_lossy().replace('\\', "/");
+        let json = format!(r#"[
+            {{ "directory": "{dir}", "file": "ok.cpp", "command": "clang++ -c ok.cpp" }},
+            {{ "directory": "{dir}/missing", "file": "a.cpp", "arguments": ["clang++", "-c", "a.cpp"] }},
+            {{ "directory": "{dir}", "command": "clang++ -c" }},
+            {{ "directory": "{dir}", "file": "b.cpp" }},
+            {{ "directory": "{dir}", "file": "c.cpp", "command": "clang++ -c c.cpp", "arguments": ["clang++"] }}
+        ]"#);
+
+        let cmds = parse_compile_commands(&json).unwrap();
+        let errors = validate_compile_commands(&cmds);
+
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(errors[0], ValidationError::MissingDirectory { index: 1, .. }));
+        assert_eq!(errors[1], ValidationError::EmptyFile { index: 2 });
+        assert!(matches!(errors[2], ValidationError::MissingCommand { index: 3, .. }));
+        assert!(matches!(errors[3], ValidationError::ConflictingCommand { index: 4, .. }));
+    }
+
+    /// Демонстрация: нормализация относительных путей
+    #[test]
+    fn test_normalize_compile_commands() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let build = temp_dir.path().join("build");
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir_all(&build).unwrap();
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("main.cpp"), "int main() {}").unwrap();
+
+        let json = r#"[
+            { "directory": "build", "file": "../src/main.cpp", "command": "clang++ -c ../src/main.cpp" },
+            { "directory": "build", "file": "generated.cpp", "command": "clang++ -c generated.cpp" }
+        ]"#;
+        let mut cmds = parse_compile_commands(json).unwrap();
+        normalize_compile_commands(&mut cmds, temp_dir.path());
+
+        let expected_main = std::fs::canonicalize(src.join("main.cpp")).unwrap();
+        let expected_build = std::fs::canonicalize(&build).unwrap();
+        assert_eq!(Path::new(&cmds[0].file), expected_main);
+        assert_eq!(Path::new(&cmds[0].directory), expected_build);
+        // Несуществующий файл присоединяется к директории без канонизации
+        assert_eq!(Path::new(&cmds[1].file), expected_build.join("generated.cpp"));
+        assert!(validate_compile_commands(&cmds).is_empty());
+    }
+
+    /// Демонстрация: фильтрация по glob-шаблонам
+    #[test]
+    fn test_filter_compile_commands() {
+        let json = r#"[
+            { "directory": "/build", "file": "/src/network/socket.cpp", "command": "clang++ -c socket.cpp" },
+            { "directory": "/build", "file": "/src/network/http/client.cpp", "command": "clang++ -c client.cpp" },
+            { "directory": "/build", "file": "/src/network/socket_test.cpp", "command": "clang++ -c socket_test.cpp" },
+            { "directory": "/build", "file": "/src/ui/window.cpp", "command": "clang++ -c window.cpp" }
+        ]"#;
+
+        let filter = FileFilter::new(&["**/network/*.cpp".to_string()], &[]).unwrap();
+        let (kept, skipped) = filter_compile_commands(parse_compile_commands(json).unwrap(), &filter);
+        let files: Vec<&str> = kept.iter().map(|c| c.file.as_str()).collect();
+        assert_eq!(files, vec!["/src/network/socket.cpp", "/src/network/socket_test.cpp"]);
+        assert_eq!(skipped, 2);
+
+        // Несколько include-шаблонов объединяются по ИЛИ, exclude применяется после
+        let filter = FileFilter::new(
+            &["**/network/**/*.cpp".to_string(), "**/ui/*.cpp".to_string()],
+            &["**/*_test.cpp".to_string()],
+        )
+        .unwrap();
+        let (kept, skipped) = filter_compile_commands(parse_compile_commands(json).unwrap(), &filter);
+        assert_eq!(kept.len(), 3);
+        assert_eq!(skipped, 1);
+        assert!(kept.iter().all(|c| !c.file.ends_with("_test.cpp")));
+
+        assert!(FileFilter::new(&[], &[]).unwrap().matches("C:\\src\\main.cpp"));
+        assert!(FileFilter::new(&["[".to_string()], &[]).is_err());
+    }
+
+    /// Демонстрация: флаги из CMakeCache.txt дополняют команды компиляции
+    #[test]
+    fn test_apply_cmake_cache() {
+        let json = r#"[
+            {"directory": "/b", "file": "/src/main.cpp", "command": "c++ -Wall -c /src/main.cpp"},
+            {"directory": "/b", "file": "/src/legacy.c", "arguments": ["cc", "-c", "/src/legacy.c"]}
+        ]"#;
+        let mut cmds = parse_compile_commands(json).unwrap();
+        let cache = HashMap::from([
+            ("CMAKE_CXX_FLAGS".to_string(), "-std=c++17 -Wall".to_string()),
+            ("CMAKE_C_FLAGS".to_string(), "-std=c11".to_string()),
+            ("ZLIB_INCLUDE_DIRS".to_string(), "/opt/zlib/include".to_string()),
+        ]);
+        apply_cmake_cache(&mut cmds, &cache).unwrap();
+
+        assert_eq!(cmds[0].command, None);
+        assert_eq!(
+            cmds[0].arguments.as_deref().unwrap(),
+            ["c++", "-std=c++17", "-I/opt/zlib/include", "-Wall", "-c", "/src/main.cpp"]
+        );
+        assert_eq!(
+            cmds[1].arguments.as_deref().unwrap(),
+            ["cc", "-std=c11", "-I/opt/zlib/include", "-c", "/src/legacy.c"]
+        );
+    }
+
+    /// Демонстрация: пути из контейнера переписываются на пути хоста
+    #[test]
+    fn test_rewrite_paths() {
+        let json = r#"[
+            {
+                "directory": "/workspace/build",
+                "file": "/workspace/src/main.cpp",
+                "command": "clang++ -I/workspace/include -I/workspace2/include -c /workspace/src/main.cpp"
+            },
+            {
+                "directory": "/workspace",
+                "file": "src/util.cpp",
+                "arguments": ["clang++", "-I", "/workspace/include", "-DROOT=\"/workspace\"", "-c", "src/util.cpp"]
+            }
+        ]"#;
+        let mut cmds = parse_compile_commands(json).unwrap();
+        rewrite_paths(&mut cmds, "/workspace", "/home/user/");
+
+        assert_eq!(cmds[0].directory, "/home/user/build");
+        assert_eq!(cmds[0].file, "/home/user/src/main.cpp");
+        assert_eq!(
+            cmds[0].command.as_deref(),
+            Some("clang++ -I/home/user/include -I/workspace2/include -c /home/user/src/main.cpp")
+        );
+        assert_eq!(cmds[1].directory, "/home/user");
+        assert_eq!(cmds[1].file, "src/util.cpp");
+        assert_eq!(
+            cmds[1].arguments.as_deref().unwrap(),
+            ["clang++", "-I", "/home/user/include", "-DROOT=\"/home/user\"", "-c", "src/util.cpp"]
+        );
+
+        // Разделители Windows и буквы дисков
+        let mut cmds = vec![CompileCommand {
+            directory: "C:\\build\\out".to_string(),
+            file: "C:/build/src/main.cpp".to_string(),
+            command: None,
+            arguments: Some(vec!["/IC:\\build\\include".to_string()]),
+        }];
+        let (from, to) = parse_path_rewrite("C:\\build:D:\\src").unwrap();
+        assert_eq!((from.as_str(), to.as_str()), ("C:\\build", "D:\\src"));
+        rewrite_paths(&mut cmds, &from, &to);
+        assert_eq!(cmds[0].directory, "D:\\src\\out");
+        assert_eq!(cmds[0].file, "D:\\src/src/main.cpp");
+        assert_eq!(cmds[0].arguments.as_deref().unwrap(), ["/ID:\\src\\include"]);
+
+        assert_eq!(
+            parse_path_rewrite("/workspace:/home/user").unwrap(),
+            ("/workspace".to_string(), "/home/user".to_string())
+        );
+        assert!(parse_path_rewrite("/workspace").is_err());
+    }
+
+    #[test]
+    fn test_rewrite_paths_ignores_mid_path_matches() {
+        let mut cmds = vec![CompileCommand {
+            directory: "/src/workspace".to_string(),
+            file: "/src/workspace/a.cpp".to_string(),
+            command: Some("clang++ -I/opt/workspace/include -c /src/workspace/a.cpp".to_string()),
+            arguments: Some(vec!["-I/opt/workspace/include".to_string(), "/src/workspace/a.cpp".to_string()]),
+        }];
+        rewrite_paths(&mut cmds, "/workspace", "/home/user");
+
+        assert_eq!(cmds[0].directory, "/src/workspace");
+        assert_eq!(cmds[0].file, "/src/workspace/a.cpp");
+        assert_eq!(
+            cmds[0].command.as_deref(),
+            Some("clang++ -I/opt/workspace/include -c /src/workspace/a.cpp")
+        );
+        assert_eq!(
+            cmds[0].arguments.as_deref().unwrap(),
+            ["-I/opt/workspace/include", "/src/workspace/a.cpp"]
+        );
+    }
 }
@@ -20,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use walkdir::WalkDir;
 
 /// Язык программирования для SCIP индексации
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -389,40 +390,79 @@ fn generate_lua_scip(config: &ScipConfig) -> Result<PathBuf> {
 }
 
 /// Автоматически определяет язык проекта по файлам в директории
+///
+/// Выбирает язык с наибольшим числом исходных файлов по
+/// [`detect_all_languages`]; при равенстве приоритет:
+/// Rust > C++ > Python > TypeScript > JavaScript > остальные.
 pub fn detect_language(project_dir: &Path) -> ScipLanguage {
-    // Проверяем наличие файлов для каждого языка
+    // Манифест Cargo однозначно указывает на Rust
     if project_dir.join("Cargo.toml").exists() {
         return ScipLanguage::Rust;
     }
 
-    if let Ok(dir_entries) = std::fs::read_dir(project_dir) {
-        for entry in dir_entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(e) => {
-                    log::warn!("Failed to read directory entry in '{}': {}", project_dir.display(), e);
-                    continue;
-                }
-            };
-            let path = entry.path();
-            if let Some(ext) = path.extension() {
-                match ext.to_str().unwrap_or("") {
-                    "rs" => return ScipLanguage::Rust,
-                    "cpp" | "cxx" | "cc" | "c" | "h" | "hpp" | "hxx" => return ScipLanguage::Cpp,
-                    "py" => return ScipLanguage::Python,
-                    "js" | "mjs" => return ScipLanguage::JavaScript,
-                    "ts" => return ScipLanguage::TypeScript,
-                    "sh" | "bash" => return ScipLanguage::Shell,
-                    "rb" => return ScipLanguage::Ruby,
-                    "php" => return ScipLanguage::PHP,
-                    "lua" => return ScipLanguage::Lua,
-                    _ => {}
-                }
+    detect_all_languages(project_dir)
+        .into_iter()
+        .next()
+        .map(|(language, _)| language)
+        .unwrap_or(ScipLanguage::Unknown)
+}
+
+/// Число исходных файлов каждого языка в проекте
+///
+/// Обходит проект на глубину до трёх уровней (например, `src/module/*.rs`).
+/// Языки без файлов не включаются; результат отсортирован по убыванию числа
+/// файлов, а при равенстве — по приоритету из [`detect_language`].
+pub fn detect_all_languages(project_dir: &Path) -> Vec<(ScipLanguage, usize)> {
+    // Порядок задаёт приоритет при равном числе файлов
+    let priority = [
+        ScipLanguage::Rust,
+        ScipLanguage::Cpp,
+        ScipLanguage::Python,
+        ScipLanguage::TypeScript,
+        ScipLanguage::JavaScript,
+        ScipLanguage::Shell,
+        ScipLanguage::Ruby,
+        ScipLanguage::PHP,
+        ScipLanguage::Lua,
+    ];
+    let mut counts = [0usize; 9];
+
+    for entry in WalkDir::new(project_dir).max_depth(3) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!("Failed to read directory entry in '{}': {}", project_dir.display(), e);
+                continue;
             }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let language = match entry.path().extension().and_then(|ext| ext.to_str()).unwrap_or("") {
+            "rs" => ScipLanguage::Rust,
+            "cpp" | "cxx" | "cc" | "c" | "h" | "hpp" | "hxx" => ScipLanguage::Cpp,
+            "py" => ScipLanguage::Python,
+            "js" | "mjs" => ScipLanguage::JavaScript,
+            "ts" => ScipLanguage::TypeScript,
+            "sh" | "bash" => ScipLanguage::Shell,
+            "rb" => ScipLanguage::Ruby,
+            "php" => ScipLanguage::PHP,
+            "lua" => ScipLanguage::Lua,
+            _ => continue,
+        };
+        if let Some(index) = priority.iter().position(|l| *l == language) {
+            counts[index] += 1;
         }
     }
 
-    ScipLanguage::Unknown
+    let mut languages: Vec<(ScipLanguage, usize)> = priority
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    // Сортировка устойчива, поэтому порядок приоритета сохраняется при равенстве
+    languages.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    languages
 }
 
 /// Проверяет доступность SCIP инструмента для указанного языка
@@ -467,12 +507,45 @@ mod tests {
 
     #[test]
     fn test_language_detection() {
-        assert_eq!(detect_language(Path::new("/tmp/Cargo.toml").parent().unwrap()), ScipLanguage::Rust);
+        let td = tempfile::tempdir().unwrap();
+        std::fs::write(td.path().join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        assert_eq!(detect_language(td.path()), ScipLanguage::Rust);
         assert_eq!(ScipLanguage::from("rust"), ScipLanguage::Rust);
         assert_eq!(ScipLanguage::from("cpp"), ScipLanguage::Cpp);
         assert_eq!(ScipLanguage::from("python"), ScipLanguage::Python);
     }
 
+    #[test]
+    fn test_detect_language_nested_sources() {
+        let td = tempfile::tempdir().unwrap();
+        let src = td.path().join("src");
+        std::fs::create_dir_all(src.join("nested")).unwrap();
+        std::fs::write(src.join("main.rs"), "fn main() {}").unwrap();
+        std::fs::write(src.join("lib.rs"), "").unwrap();
+        std::fs::write(src.join("nested").join("util.rs"), "").unwrap();
+        std::fs::write(td.path().join("build.py"), "").unwrap();
+        assert_eq!(detect_language(td.path()), ScipLanguage::Rust);
+
+        // Равное число файлов: C++ важнее Python
+        let tie = tempfile::tempdir().unwrap();
+        std::fs::write(tie.path().join("a.py"), "").unwrap();
+        std::fs::write(tie.path().join("a.cpp"), "").unwrap();
+        assert_eq!(detect_language(tie.path()), ScipLanguage::Cpp);
+    }
+
+    #[test]
+    fn test_detect_all_languages_polyglot() {
+        let td = tempfile::tempdir().unwrap();
+        for name in ["a.py", "b.py", "c.py", "main.cpp", "README.md"] {
+            std::fs::write(td.path().join(name), "").unwrap();
+        }
+        assert_eq!(
+            detect_all_languages(td.path()),
+            vec![(ScipLanguage::Python, 3), (ScipLanguage::Cpp, 1)]
+        );
+        assert_eq!(detect_language(td.path()), ScipLanguage::Python);
+    }
+
     #[test]
     fn test_scip_config() {
         let config = ScipConfig::new(
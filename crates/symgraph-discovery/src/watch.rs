@@ -0,0 +1,64 @@
+//! Группировка событий файловой системы для инкрементального пересканирования
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Накопитель изменённых файлов с задержкой (debounce)
+///
+/// Изменения копятся, пока после последнего события не пройдёт `delay`;
+/// затем весь набор отдаётся одной пачкой. Время передаётся явно, поэтому
+/// логику можно проверить без файловой системы и ожиданий:
+///
+/// ```
+/// use std::path::PathBuf;
+/// use std::time::{Duration, Instant};
+/// use symgraph_discovery::Debouncer;
+///
+/// let start = Instant::now();
+/// let ms = Duration::from_millis;
+/// let mut debouncer = Debouncer::new(ms(200));
+///
+/// debouncer.push("src/a.cpp".into(), start);
+/// debouncer.push("src/b.cpp".into(), start + ms(150));
+/// debouncer.push("src/a.cpp".into(), start + ms(180));
+///
+/// // С последнего события прошло меньше 200 мс
+/// assert_eq!(debouncer.take_ready(start + ms(300)), None);
+///
+/// let batch = debouncer.take_ready(start + ms(380)).unwrap();
+/// assert_eq!(batch, [PathBuf::from("src/a.cpp"), PathBuf::from("src/b.cpp")]);
+/// assert_eq!(debouncer.take_ready(start + ms(1000)), None);
+/// ```
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    pending: BTreeSet<PathBuf>,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: BTreeSet::new(),
+            last_event: None,
+        }
+    }
+
+    /// Зарегистрировать изменение файла в момент `now`
+    pub fn push(&mut self, path: PathBuf, now: Instant) {
+        self.pending.insert(path);
+        self.last_event = Some(now);
+    }
+
+    /// Забрать накопленные файлы, если с последнего события прошло не меньше `delay`
+    pub fn take_ready(&mut self, now: Instant) -> Option<Vec<PathBuf>> {
+        let last_event = self.last_event?;
+        if now.saturating_duration_since(last_event) < self.delay {
+            return None;
+        }
+        self.last_event = None;
+        Some(std::mem::take(&mut self.pending).into_iter().collect())
+    }
+}
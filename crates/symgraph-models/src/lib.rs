@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Basic information about a module/file
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -6,6 +7,9 @@ pub struct ModuleInfo {
     pub name: String,
     pub path: String,
     pub imports: Vec<String>,
+    /// Partition name for a C++20 module partition (`export module foo:bar;`)
+    #[serde(default)]
+    pub partition: Option<String>,
 }
 
 /// Generic symbol representation usable for different languages
@@ -16,6 +20,18 @@ pub struct Symbol {
     pub signature: String,
     pub is_exported: bool,
     pub line: u32,
+    /// 1-based column where the symbol is declared; 0 when unknown
+    #[serde(default)]
+    pub column: u32,
+    /// Cargo feature the item is gated behind (`#[cfg(feature = "...")]`)
+    #[serde(default)]
+    pub cfg: Option<String>,
+    /// Language-specific facts, e.g. `is_coroutine` for C++ functions
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Doc comment of the item without comment markers
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 /// Generic relation between symbols
@@ -34,6 +50,55 @@ pub struct ModuleAnalysis {
     pub relations: Vec<Relation>,
 }
 
+/// Relation whose target is defined in another module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossModuleRelation {
+    pub from_module: String,
+    pub from_symbol: String,
+    pub to_module: String,
+    pub to_symbol: String,
+    pub kind: String,
+}
+
+/// Resolve relations that point into other modules.
+///
+/// `to_name` of each relation is looked up among the symbols of all analyses.
+/// Names defined in the relation's own module stay local and are skipped, as
+/// are names no module defines; a name defined in several other modules
+/// resolves to the first of them.
+pub fn resolve_cross_module_relations(analyses: &[ModuleAnalysis]) -> Vec<CrossModuleRelation> {
+    let mut defined_in: HashMap<&str, Vec<&str>> = HashMap::new();
+    for analysis in analyses {
+        for symbol in &analysis.symbols {
+            let modules = defined_in.entry(symbol.name.as_str()).or_default();
+            if !modules.contains(&analysis.info.name.as_str()) {
+                modules.push(&analysis.info.name);
+            }
+        }
+    }
+
+    let mut resolved = Vec::new();
+    for analysis in analyses {
+        let module = analysis.info.name.as_str();
+        for relation in &analysis.relations {
+            let Some(modules) = defined_in.get(relation.to_name.as_str()) else {
+                continue;
+            };
+            if modules.contains(&module) {
+                continue;
+            }
+            resolved.push(CrossModuleRelation {
+                from_module: module.to_string(),
+                from_symbol: relation.from_name.clone(),
+                to_module: modules[0].to_string(),
+                to_symbol: relation.to_name.clone(),
+                kind: relation.kind.clone(),
+            });
+        }
+    }
+    resolved
+}
+
 // Convenience re-exports / aliases for backward compatibility
 pub use Relation as GenericRelation;
 pub use Symbol as GenericSymbol;
@@ -48,6 +113,7 @@ mod tests {
             name: "foo".to_string(),
             path: "foo.rs".to_string(),
             imports: vec!["std::io".to_string()],
+            partition: None,
         };
         let s = serde_json::to_string(&mi).unwrap();
         let got: ModuleInfo = serde_json::from_str(&s).unwrap();
@@ -62,6 +128,10 @@ mod tests {
             signature: "fn foo()".to_string(),
             is_exported: true,
             line: 10,
+            column: 5,
+            cfg: None,
+            metadata: HashMap::new(),
+            doc: None,
         };
         let rel = Relation {
             from_name: "foo".to_string(),
@@ -73,6 +143,7 @@ mod tests {
                 name: "m".into(),
                 path: "m.rs".into(),
                 imports: vec![],
+                partition: None,
             },
             symbols: vec![sym.clone()],
             relations: vec![rel.clone()],
@@ -82,4 +153,53 @@ mod tests {
         assert_eq!(got.symbols[0], sym);
         assert_eq!(got.relations[0], rel);
     }
+
+    #[test]
+    fn cross_module_relations() {
+        let symbol = |name: &str| Symbol {
+            name: name.to_string(),
+            kind: "class".to_string(),
+            signature: String::new(),
+            is_exported: true,
+            line: 1,
+            column: 0,
+            cfg: None,
+            metadata: HashMap::new(),
+            doc: None,
+        };
+        let relation = |from: &str, to: &str| Relation {
+            from_name: from.to_string(),
+            to_name: to.to_string(),
+            kind: "type_ref".to_string(),
+        };
+        let module = |name: &str, symbols: Vec<Symbol>, relations: Vec<Relation>| ModuleAnalysis {
+            info: ModuleInfo {
+                name: name.to_string(),
+                path: format!("{}.cppm", name),
+                imports: vec![],
+                partition: None,
+            },
+            symbols,
+            relations,
+        };
+        let analyses = [
+            module("geometry", vec![symbol("Point")], vec![]),
+            module(
+                "render",
+                vec![symbol("draw"), symbol("Canvas")],
+                vec![relation("draw", "Point"), relation("draw", "Canvas"), relation("draw", "Unknown")],
+            ),
+        ];
+
+        assert_eq!(
+            resolve_cross_module_relations(&analyses),
+            vec![CrossModuleRelation {
+                from_module: "render".to_string(),
+                from_symbol: "draw".to_string(),
+                to_module: "geometry".to_string(),
+                to_symbol: "Point".to_string(),
+                kind: "type_ref".to_string(),
+            }]
+        );
+    }
 }
@@ -1,5 +1,8 @@
+pub mod syn_analyzer;
+
 use anyhow::Result;
 use regex::Regex;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -7,6 +10,8 @@ use symgraph_models::{
     GenericRelation as Relation, GenericSymbol as Symbol, ModuleAnalysis, ModuleInfo,
 };
 
+pub use syn_analyzer::analyze_rust_module_syn;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RustFileCategory {
     EntryPoint,
@@ -17,6 +22,10 @@ pub enum RustFileCategory {
     Example,
     Build,
     Configuration,
+    /// Source of a crate with `proc-macro = true`
+    ProcMacro,
+    /// Any other `.rs` file
+    Module,
     Unknown,
 }
 
@@ -28,10 +37,16 @@ pub fn categorize_rust_file(path: &str) -> RustFileCategory {
         .to_lowercase();
     
     // Entry points
-    if filename == "main.rs" || filename == "bin.rs" {
+    if filename == "main.rs" || filename == "bin.rs"
+        || (filename.ends_with(".rs") && has_dir_component(&path_lower, "bin")) {
         return RustFileCategory::EntryPoint;
     }
     
+    // Proc-macro crates are recognised by their Cargo.toml
+    if filename.ends_with(".rs") && filename != "build.rs" && in_proc_macro_crate(Path::new(path)) {
+        return RustFileCategory::ProcMacro;
+    }
+    
     if filename == "lib.rs" {
         return RustFileCategory::Library;
     }
@@ -70,9 +85,30 @@ pub fn categorize_rust_file(path: &str) -> RustFileCategory {
         return RustFileCategory::UnitTest;
     }
     
+    if filename.ends_with(".rs") {
+        // Модуль со встроенными тестами
+        let has_tests = fs::read_to_string(path).is_ok_and(|text| text.contains("#[cfg(test)]"));
+        return if has_tests { RustFileCategory::UnitTest } else { RustFileCategory::Module };
+    }
+    
     RustFileCategory::Unknown
 }
 
+/// Whether a `/`- or `\`-separated path has a directory named `dir`
+fn has_dir_component(path: &str, dir: &str) -> bool {
+    let components: Vec<&str> = path.split(['/', '\\']).collect();
+    components[..components.len().saturating_sub(1)].contains(&dir)
+}
+
+/// Whether the nearest Cargo.toml above `path` declares `proc-macro = true`
+fn in_proc_macro_crate(path: &Path) -> bool {
+    let re = Regex::new(r"(?m)^\s*proc-macro\s*=\s*true\b").unwrap();
+    path.ancestors()
+        .skip(1)
+        .find_map(|dir| fs::read_to_string(dir.join("Cargo.toml")).ok())
+        .is_some_and(|manifest| re.is_match(&manifest))
+}
+
 pub fn infer_rust_purpose(path: &str, category: &RustFileCategory) -> String {
     match category {
         RustFileCategory::EntryPoint => "Application entry point".to_string(),
@@ -83,6 +119,7 @@ pub fn infer_rust_purpose(path: &str, category: &RustFileCategory) -> String {
         RustFileCategory::Example => "Usage examples".to_string(),
         RustFileCategory::Build => "Build script".to_string(),
         RustFileCategory::Configuration => "Configuration".to_string(),
+        RustFileCategory::ProcMacro => "Procedural macros".to_string(),
         _ => {
             let path_lower = path.to_lowercase();
             if path_lower.contains("network") || path_lower.contains("net") {
@@ -135,9 +172,137 @@ pub fn scan_rust_module_from_text(text: &str, path: &str) -> Option<ModuleInfo>
         name: module_name,
         path: path.to_string(),
         imports,
+        partition: None,
     })
 }
 
+fn cfg_feature_regex() -> Regex {
+    Regex::new(r#"#\[cfg\(feature\s*=\s*"([^"]+)"\)\]"#).unwrap()
+}
+
+/// `impl Future` / `dyn Future` in a signature or type
+fn future_regex() -> Regex {
+    Regex::new(r"\b(?:impl|dyn)\s+Future\b").unwrap()
+}
+
+/// Metadata of a function: `is_async` for `async fn` and functions returning `impl Future`,
+/// `is_unsafe` for `unsafe fn`
+fn function_metadata(is_async: bool, is_unsafe: bool, signature: &str) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if is_async || future_regex().is_match(signature.split("->").nth(1).unwrap_or("")) {
+        metadata.insert("is_async".to_string(), "true".to_string());
+    }
+    if is_unsafe {
+        metadata.insert("is_unsafe".to_string(), "true".to_string());
+    }
+    metadata
+}
+
+/// Start of an `unsafe { ... }` block
+fn unsafe_block_regex() -> Regex {
+    Regex::new(r"(?m)\bunsafe\s*\{").unwrap()
+}
+
+/// Number of `unsafe { ... }` blocks in Rust source, ignoring comments
+pub fn count_unsafe_blocks(text: &str) -> usize {
+    unsafe_block_regex().find_iter(&remove_comments_and_strings(text)).count()
+}
+
+/// Record `unsafe_blocks` metadata on functions.
+///
+/// Each block is counted for the nearest function declared above it, which
+/// approximates the enclosing function without tracking braces.
+fn attach_unsafe_blocks(text: &str, symbols: &mut [Symbol]) {
+    let clean = remove_comments_and_strings(text);
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for block in unsafe_block_regex().find_iter(&clean) {
+        let line = clean[..block.start()].matches('\n').count() as u32 + 1;
+        let enclosing = symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.kind == "function" && s.line <= line)
+            .max_by_key(|(_, s)| s.line)
+            .map(|(i, _)| i);
+        if let Some(i) = enclosing {
+            *counts.entry(i).or_default() += 1;
+        }
+    }
+    for (i, count) in counts {
+        symbols[i].metadata.insert("unsafe_blocks".to_string(), count.to_string());
+    }
+}
+
+/// Metadata of a type alias: `is_future` when it names an `impl Future` or `dyn Future`
+fn type_alias_metadata(aliased: &str) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if future_regex().is_match(aliased) {
+        metadata.insert("is_future".to_string(), "true".to_string());
+    }
+    metadata
+}
+
+/// Doc comments (`///` and `/** */`) keyed by the 1-based line of the item they document.
+///
+/// Attribute and blank lines between a comment and its item are skipped.
+fn doc_comments(text: &str) -> HashMap<u32, String> {
+    let re_line_doc = Regex::new(r"^\s*///([^/].*)?$").unwrap();
+    let re_block_doc = Regex::new(r"(?s)^\s*/\*\*([^*/].*?)?\*/\s*$").unwrap();
+
+    let mut docs = HashMap::new();
+    let mut pending: Vec<String> = Vec::new();
+    // Lines of a `/** ... */` comment that is not closed yet
+    let mut block: Option<String> = None;
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if block.is_none() && trimmed.starts_with("/**") && !trimmed.starts_with("/**/") {
+            block = Some(String::new());
+        }
+        if let Some(comment) = block.as_mut() {
+            comment.push_str(line);
+            comment.push('\n');
+            if trimmed.ends_with("*/") {
+                if let Some(cap) = re_block_doc.captures(comment) {
+                    pending.extend(cap.get(1).map_or("", |m| m.as_str()).lines().map(str::to_string));
+                }
+                block = None;
+            }
+            continue;
+        }
+
+        if let Some(cap) = re_line_doc.captures(line) {
+            pending.push(cap.get(1).map_or("", |m| m.as_str()).to_string());
+        } else if trimmed.is_empty() || trimmed.starts_with("#[") {
+            continue;
+        } else {
+            let doc = clean_doc_lines(pending.iter().map(String::as_str));
+            if !doc.is_empty() {
+                docs.insert((i + 1) as u32, doc);
+            }
+            pending.clear();
+        }
+    }
+    docs
+}
+
+/// Join doc comment lines, dropping the leading `*` of block comments and surrounding blank lines
+pub(crate) fn clean_doc_lines<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let lines: Vec<&str> = lines
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix('*').unwrap_or(line).trim()
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Features named in `#[cfg(feature = "...")]` attributes, in order of appearance
+pub fn extract_cfg_features(text: &str) -> Vec<String> {
+    cfg_feature_regex()
+        .captures_iter(text)
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
 /// Analyze a Rust source file and extract exported symbols/relations
 pub fn analyze_rust_module(file_path: &str) -> Result<Option<ModuleAnalysis>> {
     let text = fs::read_to_string(file_path)?;
@@ -164,31 +329,60 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
     let mut current_impl: Option<String> = None;
 
     // Match `pub fn` anywhere on the line (handles `impl S { pub fn ... }` inline)
-    let re_pub_fn = Regex::new(r"pub\s+fn\s+([A-Za-z0-9_]+)\s*\(").unwrap();
+    let re_pub_fn = Regex::new(r"pub\s+(async\s+)?(unsafe\s+)?fn\s+([A-Za-z0-9_]+)\s*\(").unwrap();
     let re_pub_struct = Regex::new(r"^\s*pub\s+struct\s+([A-Za-z0-9_]+)").unwrap();
     let re_pub_enum = Regex::new(r"^\s*pub\s+enum\s+([A-Za-z0-9_]+)").unwrap();
     let re_pub_type = Regex::new(r"^\s*pub\s+type\s+([A-Za-z0-9_]+)\s*=\s*(.+);?").unwrap();
     let re_pub_const =
         Regex::new(r"^\s*pub\s+(?:const|static)\s+([A-Za-z0-9_]+)\s*:\s*([^=;]+)").unwrap();
     let re_impl = Regex::new(r"^\s*impl\s+(?:<[^>]*>\s*)?([A-Za-z0-9_:<>::]+)\s*\{").unwrap();
+    let re_impl_trait =
+        Regex::new(r"(?m)^\s*impl\s+(?:<[^>]*>\s*)?([A-Za-z0-9_:]+)\s+for\s+([A-Za-z0-9_:]+)")
+            .unwrap();
     let re_impl_end = Regex::new(r"^\s*}\s*$").unwrap();
     let _re_fn_in_impl = Regex::new(r"^\s*pub\s+fn\s+([A-Za-z0-9_]+)\s*\(").unwrap();
 
+    let docs = doc_comments(text);
+    let re_cfg = cfg_feature_regex();
+    // Feature of a `#[cfg(feature = "...")]` seen on preceding attribute lines
+    let mut pending_cfg: Option<String> = None;
+
     for (i, line) in clean.lines().enumerate() {
         let ln = (i + 1) as u32;
         let trimmed = line.trim();
+        let column = (line.len() - line.trim_start().len() + 1) as u32;
         if trimmed.is_empty() {
             continue;
         }
 
+        let cfg = re_cfg
+            .captures(trimmed)
+            .map(|cap| cap[1].to_string())
+            .or_else(|| pending_cfg.take());
+        if trimmed.starts_with("#[") && trimmed.ends_with(']') {
+            pending_cfg = cfg;
+            continue;
+        }
+
         // Impl start (match even when `{` and content are on the same line)
         if let Some(cap) = re_impl.captures(trimmed) {
             let typ = cap.get(1).unwrap().as_str().to_string();
             current_impl = Some(typ);
             // do not `continue` — allow matching `pub fn` on the same line
         }
+
+        // Trait implementation: `impl Trait for Type`
+        if let Some(cap) = re_impl_trait.captures(trimmed) {
+            let trait_name = cap.get(1).unwrap().as_str().to_string();
+            let type_name = cap.get(2).unwrap().as_str().to_string();
+            relations.push(Relation {
+                from_name: type_name,
+                to_name: trait_name,
+                kind: "impl_trait".to_string(),
+            });
+        }
         if let Some(cap) = re_pub_fn.captures(trimmed) {
-            let name = cap.get(1).unwrap().as_str().to_string();
+            let name = cap.get(3).unwrap().as_str().to_string();
             let full_name = if let Some(ref typ) = current_impl {
                 format!("{}::{}", typ, name)
             } else {
@@ -200,6 +394,10 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: ln,
+                column,
+                cfg: cfg.clone(),
+                metadata: function_metadata(cap.get(1).is_some(), cap.get(2).is_some(), trimmed),
+                doc: docs.get(&ln).cloned(),
             });
         }
 
@@ -211,6 +409,10 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: ln,
+                column,
+                cfg: cfg.clone(),
+                metadata: HashMap::new(),
+                doc: docs.get(&ln).cloned(),
             });
         }
 
@@ -222,6 +424,10 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: ln,
+                column,
+                cfg: cfg.clone(),
+                metadata: HashMap::new(),
+                doc: docs.get(&ln).cloned(),
             });
         }
 
@@ -237,6 +443,10 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: ln,
+                column,
+                cfg: cfg.clone(),
+                metadata: type_alias_metadata(&orig),
+                doc: docs.get(&ln).cloned(),
             });
             relations.push(Relation {
                 from_name: name,
@@ -254,6 +464,10 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
                 signature: trimmed.to_string(),
                 is_exported: true,
                 line: ln,
+                column,
+                cfg: cfg.clone(),
+                metadata: HashMap::new(),
+                doc: docs.get(&ln).cloned(),
             });
             relations.push(Relation {
                 from_name: name,
@@ -269,6 +483,8 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
         }
     }
 
+    attach_unsafe_blocks(text, &mut symbols);
+
     // Top-level imports
     let re_use = Regex::new(r"(?m)^\s*(?:pub\s+)?use\s+([A-Za-z0-9_:]+)").unwrap();
     let imports: Vec<String> = re_use
@@ -281,6 +497,7 @@ pub fn analyze_rust_module_from_text(text: &str, path: &str) -> Result<Option<Mo
             name: module_name,
             path: path.to_string(),
             imports,
+            partition: None,
         },
         symbols,
         relations,
@@ -311,6 +528,10 @@ fn remove_comments_and_strings(text: &str) -> String {
                             result.push(' ');
                             break;
                         }
+                        // Keep line numbers in step with the original text
+                        if cc == '\n' {
+                            result.push('\n');
+                        }
                     }
                 } else {
                     result.push('/');
@@ -390,6 +611,193 @@ mod tests {
         let res = analyze_rust_module_from_text(s, "s.rs").unwrap().unwrap();
         assert!(res.symbols.iter().any(|s| s.name == "S::do_it"));
     }
+
+    #[test]
+    fn test_doc_comments_attach_to_items() {
+        let s = "/// Says hello.\n///\n/// Twice.\n#[inline]\npub fn hello() {}\n\n/**\n * A point.\n */\npub struct Point;\n\npub fn bare() {}";
+        let res = analyze_rust_module_from_text(s, "d.rs").unwrap().unwrap();
+        let doc = |name: &str| res.symbols.iter().find(|s| s.name == name).unwrap().doc.clone();
+        assert_eq!(doc("hello").as_deref(), Some("Says hello.\n\nTwice."));
+        assert_eq!(doc("Point").as_deref(), Some("A point."));
+        assert_eq!(doc("bare"), None);
+    }
+
+    #[test]
+    fn test_syn_analyzer_covers_more_than_regex() {
+        let s = r#"
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+pub struct Registry {
+    items: HashMap<String, Entry>,
+}
+
+struct Entry {
+    id: u32,
+}
+
+enum State { Idle, Busy }
+
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+impl Registry {
+    pub fn new() -> Self { Registry { items: HashMap::new() } }
+    fn lookup(&self, key: &str) -> Option<&Entry> { self.items.get(key) }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", "}") }
+}
+
+fn helper() -> HashSet<u32> {
+    fn nested() {}
+    HashSet::new()
+}
+
+mod inner {
+    pub fn deep() {}
+}
+"#;
+        let regex_res = analyze_rust_module_from_text(s, "registry.rs").unwrap().unwrap();
+        let syn_res = analyze_rust_module_syn(s, "registry.rs").unwrap().unwrap();
+
+        let has = |res: &ModuleAnalysis, name: &str, kind: &str| {
+            res.symbols.iter().any(|s| s.name == name && s.kind == kind)
+        };
+
+        // Everything the regex analyzer finds is found by syn as well
+        for sym in &regex_res.symbols {
+            assert!(has(&syn_res, &sym.name, &sym.kind), "syn missed {}", sym.name);
+        }
+        assert!(syn_res.symbols.len() > regex_res.symbols.len());
+
+        // Private and nested items are only visible to syn
+        for (name, kind) in [
+            ("Entry", "struct"),
+            ("State", "enum"),
+            ("Named", "trait"),
+            ("Named::name", "function"),
+            ("Registry::lookup", "function"),
+            ("Registry::fmt", "function"),
+            ("helper", "function"),
+            ("nested", "function"),
+        ] {
+            assert!(has(&syn_res, name, kind), "syn missed {}", name);
+            assert!(!has(&regex_res, name, kind), "regex unexpectedly found {}", name);
+        }
+        assert!(has(&syn_res, "deep", "function"));
+        assert!(!syn_res.symbols.iter().find(|s| s.name == "Entry").unwrap().is_exported);
+
+        assert!(syn_res.relations.iter().any(|r| r.from_name == "Registry"
+            && r.to_name == "fmt::Display"
+            && r.kind == "impl_trait"));
+        assert!(syn_res.relations.iter().any(|r| r.from_name == "Registry"
+            && r.to_name == "Registry::lookup"
+            && r.kind == "member"));
+
+        // Grouped imports are expanded
+        assert!(syn_res.info.imports.contains(&"std::collections::HashMap".to_string()));
+        assert!(syn_res.info.imports.contains(&"std::collections::HashSet".to_string()));
+        assert!(syn_res.relations.iter().any(|r| r.to_name == "std::fmt" && r.kind == "import"));
+    }
+
+    #[test]
+    fn test_cfg_feature_gated_functions() {
+        let s = r#"
+#[cfg(feature = "tls")]
+pub fn connect_tls() {}
+
+pub fn connect() {}
+
+#[cfg(feature="metrics")]
+#[inline]
+pub fn record() {}
+"#;
+        assert_eq!(extract_cfg_features(s), vec!["tls", "metrics"]);
+
+        let cfg_of = |res: &ModuleAnalysis, name: &str| {
+            res.symbols.iter().find(|s| s.name == name).unwrap().cfg.clone()
+        };
+        for res in [
+            analyze_rust_module_from_text(s, "net.rs").unwrap().unwrap(),
+            analyze_rust_module_syn(s, "net.rs").unwrap().unwrap(),
+        ] {
+            assert_eq!(cfg_of(&res, "connect_tls").as_deref(), Some("tls"));
+            assert_eq!(cfg_of(&res, "connect"), None);
+            assert_eq!(cfg_of(&res, "record").as_deref(), Some("metrics"));
+        }
+    }
+
+    #[test]
+    fn test_async_functions() {
+        let s = r#"
+pub async fn fetch(url: &str) -> Result<String> { todo!() }
+
+pub fn parse(body: &str) -> usize { 0 }
+
+pub fn spawn_fetch() -> impl Future<Output = ()> { async {} }
+
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+"#;
+        let metadata_of = |res: &ModuleAnalysis, name: &str, key: &str| {
+            res.symbols.iter().find(|s| s.name == name).unwrap().metadata.get(key).cloned()
+        };
+        for res in [
+            analyze_rust_module_from_text(s, "client.rs").unwrap().unwrap(),
+            analyze_rust_module_syn(s, "client.rs").unwrap().unwrap(),
+        ] {
+            assert_eq!(metadata_of(&res, "fetch", "is_async").as_deref(), Some("true"));
+            assert_eq!(metadata_of(&res, "parse", "is_async"), None);
+            assert_eq!(metadata_of(&res, "spawn_fetch", "is_async").as_deref(), Some("true"));
+            assert_eq!(metadata_of(&res, "BoxFuture", "is_future").as_deref(), Some("true"));
+        }
+    }
+
+    #[test]
+    fn test_unsafe_functions() {
+        let s = r#"
+pub unsafe fn read_raw(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+pub unsafe fn copy_raw(src: *const u8, dst: *mut u8) {
+    unsafe { *dst = *src; }
+    // unsafe { not a block }
+}
+
+pub fn safe_read(bytes: &[u8]) -> u8 {
+    let ptr = bytes.as_ptr();
+    unsafe { read_raw(ptr) }
+}
+"#;
+        assert_eq!(count_unsafe_blocks(s), 2);
+
+        let metadata_of = |res: &ModuleAnalysis, name: &str, key: &str| {
+            res.symbols.iter().find(|s| s.name == name).unwrap().metadata.get(key).cloned()
+        };
+        for res in [
+            analyze_rust_module_from_text(s, "raw.rs").unwrap().unwrap(),
+            analyze_rust_module_syn(s, "raw.rs").unwrap().unwrap(),
+        ] {
+            assert_eq!(metadata_of(&res, "read_raw", "is_unsafe").as_deref(), Some("true"));
+            assert_eq!(metadata_of(&res, "copy_raw", "is_unsafe").as_deref(), Some("true"));
+            assert_eq!(metadata_of(&res, "safe_read", "is_unsafe"), None);
+            assert_eq!(metadata_of(&res, "read_raw", "unsafe_blocks"), None);
+            assert_eq!(metadata_of(&res, "copy_raw", "unsafe_blocks").as_deref(), Some("1"));
+            assert_eq!(metadata_of(&res, "safe_read", "unsafe_blocks").as_deref(), Some("1"));
+        }
+    }
+
+    #[test]
+    fn test_impl_trait_relation() {
+        let s = "pub struct MyStruct;\nimpl Display for MyStruct {}";
+        let res = analyze_rust_module_from_text(s, "d.rs").unwrap().unwrap();
+        assert!(res.relations.iter().any(|r| r.from_name == "MyStruct"
+            && r.to_name == "Display"
+            && r.kind == "impl_trait"));
+    }
 }
 #[cfg(test)]
 mod categorization_tests {
@@ -404,6 +812,27 @@ mod categorization_tests {
         assert_eq!(categorize_rust_file("benches/benchmark.rs"), RustFileCategory::Benchmark);
         assert_eq!(categorize_rust_file("examples/example.rs"), RustFileCategory::Example);
         assert_eq!(categorize_rust_file("build.rs"), RustFileCategory::Build);
+        assert_eq!(categorize_rust_file("src/bin/tool.rs"), RustFileCategory::EntryPoint);
+        assert_eq!(categorize_rust_file("Cargo.toml"), RustFileCategory::Configuration);
+        assert_eq!(categorize_rust_file("src/parser.rs"), RustFileCategory::Module);
+        assert_eq!(categorize_rust_file("README.md"), RustFileCategory::Unknown);
+    }
+
+    #[test]
+    fn test_rust_categorization_from_contents() {
+        let td = tempfile::tempdir().unwrap();
+        let src = td.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(td.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(src.join("inline.rs"), "fn f() {}\n#[cfg(test)]\nmod tests {}\n").unwrap();
+        std::fs::write(src.join("plain.rs"), "fn f() {}\n").unwrap();
+        let path = |name: &str| src.join(name).to_string_lossy().to_string();
+        assert_eq!(categorize_rust_file(&path("inline.rs")), RustFileCategory::UnitTest);
+        assert_eq!(categorize_rust_file(&path("plain.rs")), RustFileCategory::Module);
+
+        std::fs::write(td.path().join("Cargo.toml"), "[package]\nname = \"x\"\n\n[lib]\nproc-macro = true\n").unwrap();
+        assert_eq!(categorize_rust_file(&path("lib.rs")), RustFileCategory::ProcMacro);
+        assert_eq!(infer_rust_purpose(&path("lib.rs"), &RustFileCategory::ProcMacro), "Procedural macros");
     }
 
     #[test]
@@ -0,0 +1,289 @@
+//! AST-based Rust module analysis using `syn`.
+//!
+//! Unlike the line-oriented regex analyzer this sees private items, items
+//! nested in modules and function bodies, trait methods, and grouped `use` trees.
+
+use anyhow::Result;
+use quote::ToTokens;
+use std::collections::HashMap;
+use std::path::Path;
+use syn::visit::{self, Visit};
+
+use symgraph_models::{
+    GenericRelation as Relation, GenericSymbol as Symbol, ModuleAnalysis, ModuleInfo,
+};
+
+/// Analyze Rust source text by parsing it with `syn`.
+///
+/// Returns `Ok(None)` for files without any items and an error for files that
+/// do not parse.
+pub fn analyze_rust_module_syn(text: &str, path: &str) -> Result<Option<ModuleAnalysis>> {
+    let file = syn::parse_file(text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path, e))?;
+    if file.items.is_empty() {
+        return Ok(None);
+    }
+
+    let module_name = crate::scan_rust_module_from_text(text, path)
+        .map(|mi| mi.name)
+        .unwrap_or_else(|| {
+            Path::new(path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string()
+        });
+
+    let mut visitor = ItemCollector {
+        module_name: module_name.clone(),
+        ..Default::default()
+    };
+    visitor.visit_file(&file);
+    crate::attach_unsafe_blocks(text, &mut visitor.symbols);
+
+    Ok(Some(ModuleAnalysis {
+        info: ModuleInfo {
+            name: module_name,
+            path: path.to_string(),
+            imports: visitor.imports,
+            partition: None,
+        },
+        symbols: visitor.symbols,
+        relations: visitor.relations,
+    }))
+}
+
+#[derive(Default)]
+struct ItemCollector {
+    module_name: String,
+    symbols: Vec<Symbol>,
+    relations: Vec<Relation>,
+    imports: Vec<String>,
+    /// Enclosing `impl` type or trait for methods
+    owners: Vec<String>,
+}
+
+impl ItemCollector {
+    fn push_symbol(
+        &mut self,
+        name: String,
+        kind: &str,
+        signature: String,
+        vis: &syn::Visibility,
+        attrs: &[syn::Attribute],
+        start: proc_macro2::LineColumn,
+    ) {
+        self.symbols.push(Symbol {
+            name,
+            kind: kind.to_string(),
+            signature,
+            is_exported: matches!(vis, syn::Visibility::Public(_)),
+            line: start.line as u32,
+            column: start.column as u32 + 1,
+            cfg: cfg_feature(attrs),
+            metadata: HashMap::new(),
+            doc: doc_comment(attrs),
+        });
+    }
+
+    /// Attach metadata to the symbol pushed last
+    fn extend_last_metadata(&mut self, metadata: HashMap<String, String>) {
+        if let Some(symbol) = self.symbols.last_mut() {
+            symbol.metadata.extend(metadata);
+        }
+    }
+
+    fn push_relation(&mut self, from_name: String, to_name: String, kind: &str) {
+        self.relations.push(Relation {
+            from_name,
+            to_name,
+            kind: kind.to_string(),
+        });
+    }
+
+    /// Record a method of the innermost owner as `Owner::method`
+    fn push_method(&mut self, sig: &syn::Signature, vis: &syn::Visibility, attrs: &[syn::Attribute]) {
+        let name = sig.ident.to_string();
+        let start = sig.ident.span().start();
+        let signature = tokens(sig);
+        match self.owners.last().cloned() {
+            Some(owner) => {
+                let full_name = format!("{}::{}", owner, name);
+                self.push_symbol(full_name.clone(), "function", signature, vis, attrs, start);
+                self.extend_last_metadata(signature_metadata(sig));
+                self.push_relation(owner, full_name, "member");
+            }
+            None => {
+                self.push_symbol(name, "function", signature, vis, attrs, start);
+                self.extend_last_metadata(signature_metadata(sig));
+            }
+        }
+    }
+
+    fn push_use_tree(&mut self, prefix: &str, tree: &syn::UseTree) {
+        let join = |name: &str| {
+            if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}::{}", prefix, name)
+            }
+        };
+        match tree {
+            syn::UseTree::Path(p) => self.push_use_tree(&join(&p.ident.to_string()), &p.tree),
+            syn::UseTree::Name(n) => self.push_import(join(&n.ident.to_string())),
+            syn::UseTree::Rename(r) => self.push_import(join(&r.ident.to_string())),
+            syn::UseTree::Glob(_) => self.push_import(join("*")),
+            syn::UseTree::Group(g) => {
+                for item in &g.items {
+                    self.push_use_tree(prefix, item);
+                }
+            }
+        }
+    }
+
+    fn push_import(&mut self, path: String) {
+        let module_name = self.module_name.clone();
+        self.push_relation(module_name, path.clone(), "import");
+        self.imports.push(path);
+    }
+}
+
+impl<'ast> Visit<'ast> for ItemCollector {
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        let start = item.sig.ident.span().start();
+        self.push_symbol(item.sig.ident.to_string(), "function", tokens(&item.sig), &item.vis, &item.attrs, start);
+        self.extend_last_metadata(signature_metadata(&item.sig));
+        visit::visit_item_fn(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        let start = item.ident.span().start();
+        let signature = format!("struct {}", item.ident);
+        self.push_symbol(item.ident.to_string(), "struct", signature, &item.vis, &item.attrs, start);
+        visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast syn::ItemEnum) {
+        let start = item.ident.span().start();
+        let signature = format!("enum {}", item.ident);
+        self.push_symbol(item.ident.to_string(), "enum", signature, &item.vis, &item.attrs, start);
+        visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_type(&mut self, item: &'ast syn::ItemType) {
+        let name = item.ident.to_string();
+        let start = item.ident.span().start();
+        self.push_symbol(name.clone(), "type", tokens(item), &item.vis, &item.attrs, start);
+        self.extend_last_metadata(crate::type_alias_metadata(&tokens(&item.ty)));
+        self.push_relation(name, tokens(&item.ty), "type_ref");
+        visit::visit_item_type(self, item);
+    }
+
+    fn visit_item_const(&mut self, item: &'ast syn::ItemConst) {
+        let name = item.ident.to_string();
+        let start = item.ident.span().start();
+        let signature = format!("const {}: {}", name, tokens(&item.ty));
+        self.push_symbol(name.clone(), "constant", signature, &item.vis, &item.attrs, start);
+        self.push_relation(name, tokens(&item.ty), "type_ref");
+        visit::visit_item_const(self, item);
+    }
+
+    fn visit_item_static(&mut self, item: &'ast syn::ItemStatic) {
+        let name = item.ident.to_string();
+        let start = item.ident.span().start();
+        let signature = format!("static {}: {}", name, tokens(&item.ty));
+        self.push_symbol(name.clone(), "constant", signature, &item.vis, &item.attrs, start);
+        self.push_relation(name, tokens(&item.ty), "type_ref");
+        visit::visit_item_static(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast syn::ItemTrait) {
+        let name = item.ident.to_string();
+        let start = item.ident.span().start();
+        self.push_symbol(name.clone(), "trait", format!("trait {}", name), &item.vis, &item.attrs, start);
+        self.owners.push(name);
+        visit::visit_item_trait(self, item);
+        self.owners.pop();
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast syn::TraitItemFn) {
+        // Trait methods share the trait's visibility
+        self.push_method(&item.sig, &syn::Visibility::Public(Default::default()), &item.attrs);
+        visit::visit_trait_item_fn(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let type_name = match item.self_ty.as_ref() {
+            syn::Type::Path(tp) => path_name(&tp.path),
+            other => tokens(other),
+        };
+        if let Some((_, trait_path, _)) = &item.trait_ {
+            self.push_relation(type_name.clone(), path_name(trait_path), "impl_trait");
+        }
+        self.owners.push(type_name);
+        visit::visit_item_impl(self, item);
+        self.owners.pop();
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast syn::ImplItemFn) {
+        self.push_method(&item.sig, &item.vis, &item.attrs);
+        visit::visit_impl_item_fn(self, item);
+    }
+
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        self.push_use_tree("", &item.tree);
+    }
+}
+
+/// Feature of the first `#[cfg(feature = "...")]` attribute
+fn cfg_feature(attrs: &[syn::Attribute]) -> Option<String> {
+    let mut feature = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("cfg")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("feature") {
+                feature = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            }
+            Ok(())
+        });
+        if feature.is_some() {
+            break;
+        }
+    }
+    feature
+}
+
+/// Text of the `///` and `/** */` comments, which reach `syn` as `#[doc = "..."]` attributes
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let doc = crate::clean_doc_lines(lines.iter().flat_map(|l| l.lines()));
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Metadata of a function signature: `is_async` for `async fn` or an `impl Future` return type,
+/// `is_unsafe` for `unsafe fn`
+fn signature_metadata(sig: &syn::Signature) -> HashMap<String, String> {
+    crate::function_metadata(sig.asyncness.is_some(), sig.unsafety.is_some(), &tokens(&sig.output))
+}
+
+/// `a::b::C` without generic arguments
+fn path_name(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn tokens<T: ToTokens>(node: &T) -> String {
+    node.to_token_stream().to_string()
+}